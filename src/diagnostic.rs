@@ -0,0 +1,271 @@
+use std::fmt;
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::parser::ParsingError;
+
+const RED: &str = "\u{001b}[1;31m";
+const BLUE: &str = "\u{001b}[1;96m";
+const BOLD: &str = "\u{001b}[1m";
+const RESET: &str = "\u{001b}[0m";
+
+const TAB_WIDTH: usize = 4;
+
+/// Renders a parsing error in the style of rustc, with the line of source that caused it
+///
+/// ```text
+/// error: expected a url or a string
+///  --> styles.css:1:9
+///   |
+/// 1 | @import 5;
+///   |         ^ found the number 5
+///   |
+///   = note: only a subset of CSS is supported, see GRAMMAR.md
+/// ```
+pub struct Diagnostic<'a> {
+    error: &'a ParsingError,
+    file_name: &'a str,
+    source: &'a str,
+    is_colored: bool,
+}
+
+impl<'a> Diagnostic<'a> {
+    /// Creates a plain text diagnostic for an error found while parsing `source`
+    pub fn new(error: &'a ParsingError, file_name: &'a str, source: &'a str) -> Self {
+        Self {
+            error,
+            file_name,
+            source,
+            is_colored: false,
+        }
+    }
+
+    /// whether to color the output with ANSI escape codes, this should only be used when writing to a terminal
+    pub fn colored(mut self, is_colored: bool) -> Self {
+        self.is_colored = is_colored;
+        self
+    }
+
+    /// the zero based line and column to point at, and how many characters to underline
+    fn location(&self) -> (usize, usize, usize) {
+        match self.error {
+            ParsingError::WrongToken {
+                line, column, span, ..
+            } => {
+                let length = if span.end.line == *line {
+                    span.end.column.saturating_sub(*column)
+                } else {
+                    // the token goes past the end of the line, so underline the rest of it
+                    self.line_text(*line)
+                        .chars()
+                        .count()
+                        .saturating_sub(*column)
+                };
+                (*line, *column, length.max(1))
+            }
+            ParsingError::EndOfFile { .. } => {
+                // point just past the last character that is not whitespace
+                let source = self.source.trim_end();
+                let line = source.lines().count().saturating_sub(1);
+                let column = source.lines().last().unwrap_or("").chars().count();
+                (line, column, 1)
+            }
+        }
+    }
+
+    fn line_text(&self, line: usize) -> &'a str {
+        self.source.lines().nth(line).unwrap_or("")
+    }
+
+    fn paint(&self, color: &'static str) -> &'static str {
+        if self.is_colored {
+            color
+        } else {
+            ""
+        }
+    }
+}
+
+/// serialized as `{ file, line, column, kind, expected, found, message }` with lines and columns starting at one
+///
+/// `kind` is either `wrong_token` or `end_of_file`, and `found` is `null` at the end of the file
+impl Serialize for Diagnostic<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (line, column, _) = self.location();
+        let (kind, expected, found) = match self.error {
+            ParsingError::WrongToken {
+                expected, found, ..
+            } => ("wrong_token", expected, Some(found)),
+            ParsingError::EndOfFile { expected } => ("end_of_file", expected, None),
+        };
+
+        let mut state = serializer.serialize_struct("Diagnostic", 7)?;
+        state.serialize_field("file", self.file_name)?;
+        state.serialize_field("line", &(line + 1))?;
+        state.serialize_field("column", &(column + 1))?;
+        state.serialize_field("kind", kind)?;
+        state.serialize_field("expected", expected)?;
+        state.serialize_field("found", &found)?;
+        state.serialize_field("message", &self.error.to_string())?;
+        state.end()
+    }
+}
+
+/// the width a piece of a line takes up once tabs are expanded
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|character| if character == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let (red, blue, bold, reset) = (
+            self.paint(RED),
+            self.paint(BLUE),
+            self.paint(BOLD),
+            self.paint(RESET),
+        );
+
+        let (expected, label, note) = match self.error {
+            ParsingError::WrongToken {
+                expected, found, ..
+            } => (
+                expected,
+                format!("found {found}"),
+                "only a subset of CSS is supported, see GRAMMAR.md",
+            ),
+            ParsingError::EndOfFile { expected } => (
+                expected,
+                "the file ends here".to_owned(),
+                "the file ended before the rule was finished",
+            ),
+        };
+
+        let (line, column, length) = self.location();
+        let line_text = self.line_text(line);
+        let line_number = (line + 1).to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        let prefix: String = line_text.chars().take(column).collect();
+        let underlined: String = line_text.chars().skip(column).take(length).collect();
+        let padding = " ".repeat(display_width(&prefix));
+        let underline = "^".repeat(display_width(&underlined).max(1));
+
+        writeln!(
+            formatter,
+            "{red}error{reset}{bold}: expected {expected}{reset}"
+        )?;
+        writeln!(
+            formatter,
+            "{gutter}{blue}-->{reset} {}:{}:{}",
+            self.file_name,
+            line + 1,
+            column + 1
+        )?;
+        writeln!(formatter, "{gutter} {blue}|{reset}")?;
+        writeln!(
+            formatter,
+            "{blue}{line_number} |{reset} {}",
+            line_text.replace('\t', &" ".repeat(TAB_WIDTH))
+        )?;
+        writeln!(
+            formatter,
+            "{gutter} {blue}|{reset} {padding}{red}{underline} {label}{reset}"
+        )?;
+        writeln!(formatter, "{gutter} {blue}|{reset}")?;
+        write!(
+            formatter,
+            "{gutter} {blue}={reset} {bold}note{reset}: {note}"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Stylesheet;
+
+    fn render(source: &str) -> String {
+        let error = source.parse::<Stylesheet>().unwrap_err();
+        Diagnostic::new(&error, "test.css", source).to_string()
+    }
+
+    #[test]
+    fn wrong_token() {
+        assert_eq!(
+            "error: expected a url or a string\n\
+             \x20--> test.css:1:9\n\
+             \x20 |\n\
+             1 | @import 5;\n\
+             \x20 |         ^ found the number 5\n\
+             \x20 |\n\
+             \x20 = note: only a subset of CSS is supported, see GRAMMAR.md",
+            render("@import 5;")
+        );
+    }
+
+    #[test]
+    fn underline_whole_token() {
+        let output = render("a {\n  color: rebeccapurple;\n}");
+        assert!(output.contains(" --> test.css:2:10\n"));
+        assert!(output.contains("2 |   color: rebeccapurple;\n"));
+        assert!(
+            output.contains("  |          ^^^^^^^^^^^^^ found the identifier \"rebeccapurple\"\n")
+        );
+    }
+
+    #[test]
+    fn end_of_file() {
+        let output = render("a {\n  color: red;\n");
+        assert!(output.starts_with("error: expected }\n --> test.css:2:14\n"));
+        assert!(output.contains("2 |   color: red;\n  |              ^ the file ends here\n"));
+    }
+
+    #[test]
+    fn tabs() {
+        let output = render("a {\n\tcolor: 5;\n}");
+        assert!(output.contains("2 |     color: 5;\n  |            ^ found the number 5\n"));
+    }
+
+    #[test]
+    fn wide_line_number() {
+        let output = render(&format!("{}@import 5;", "\n".repeat(9)));
+        assert!(output.contains("  --> test.css:10:9\n   |\n10 | @import 5;\n   |         ^"));
+    }
+
+    #[test]
+    fn colored() {
+        let error = "@import 5;".parse::<Stylesheet>().unwrap_err();
+        let output = Diagnostic::new(&error, "test.css", "@import 5;")
+            .colored(true)
+            .to_string();
+        assert!(output.starts_with("\u{001b}[1;31merror\u{001b}[0m"));
+    }
+
+    #[test]
+    fn serialize() {
+        let source = "a {\n  color: 5;\n}";
+        let error = source.parse::<Stylesheet>().unwrap_err();
+        let json = serde_json::to_value(Diagnostic::new(&error, "test.css", source)).unwrap();
+
+        assert_eq!("test.css", json["file"]);
+        assert_eq!(2, json["line"]);
+        assert_eq!(10, json["column"]);
+        assert_eq!("wrong_token", json["kind"]);
+        assert_eq!("the number 5", json["found"]);
+    }
+
+    #[test]
+    fn serialize_end_of_file() {
+        let source = "a {";
+        let error = source.parse::<Stylesheet>().unwrap_err();
+        let json = serde_json::to_value(Diagnostic::new(&error, "test.css", source)).unwrap();
+
+        assert_eq!(1, json["line"]);
+        assert_eq!(4, json["column"]);
+        assert_eq!("end_of_file", json["kind"]);
+        assert_eq!("}", json["expected"]);
+        assert!(json["found"].is_null());
+    }
+}
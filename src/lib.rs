@@ -0,0 +1,62 @@
+//! A CSS parser
+//!
+//! Parses a subset of CSS into a syntax tree. See `GRAMMAR.md` for the supported grammar.
+//!
+//! ```
+//! use css_parser::{Rule, Stylesheet};
+//!
+//! let stylesheet: Stylesheet = "div { color: red }".parse().unwrap();
+//!
+//! assert!(matches!(stylesheet.rules[0], Rule::Ruleset(_)));
+//! ```
+
+pub mod diagnostic;
+pub mod parser;
+pub mod printer;
+pub mod span;
+pub mod tokenizer;
+
+pub use diagnostic::Diagnostic;
+pub use parser::{
+    color::Color,
+    component_value::ComponentValue,
+    container_condition::{
+        Comparison, ContainerCondition, SizeFeature, SizeFeatureName, SizeRange,
+    },
+    declaration::{Declaration, DeclarationItem},
+    font_family::{FontName, GenericFamily},
+    generic_rule::{
+        GenericAtRule, GenericBlockItem, GenericDeclaration, GenericQualifiedRule, GenericRule,
+    },
+    import::{Import, ImportLayer},
+    layer_name::LayerName,
+    length::{Length, LengthUnit},
+    length_or_percentage::LengthOrPercentage,
+    media_query::{MediaFeature, MediaQuery, MediaType},
+    percentage::Percentage,
+    rule::{
+        container_rule::ContainerRule,
+        font_face::{FontDisplay, FontFace, FontFaceDescriptor, FontSource, FontStyle, FontWeight},
+        keyframes::{Keyframe, KeyframeSelector, Keyframes},
+        layer::{LayerBlock, LayerStatement},
+        media_rule::MediaRule,
+        ruleset::Ruleset,
+        supports_rule::SupportsRule,
+        Rule,
+    },
+    selector::{
+        an_plus_b::AnPlusB, attribute_selector::AttributeSelector, combinator::Combinator,
+        pseudo_class::PseudoClass, pseudo_element::PseudoElement, specificity::Specificity,
+        Selector, SelectorRestriction,
+    },
+    side::Sides,
+    supports_condition::SupportsCondition,
+    time::Time,
+    unicode_range::UnicodeRange,
+    url::Url,
+    variable::Variable,
+    Parsable, Parser, ParsingError, Stylesheet,
+};
+pub use printer::{Printer, ToCss};
+pub use span::{Position, Span, Spanned};
+pub use tokenizer::{Token, TokenAt, Tokenizer};
@@ -1,42 +1,305 @@
 use std::env;
 use std::fs;
+use std::io::{self, IsTerminal};
 use std::process::ExitCode;
 
-mod parser;
-mod tokenizer;
-
-use parser::Stylesheet;
+use css_parser::{
+    printer, Diagnostic, Parser, ParsingError, Rule, Selector, Stylesheet, Tokenizer,
+};
+use serde_json::json;
 
 const GREEN_CHECK: &str = "\u{001b}[92m✓\u{001b}[0m";
 const RED_X: &str = "\u{001b}[31mX\u{001b}[0m";
 const BLUE_I: &str = "\u{001b}[96mi\u{001b}[0m";
 
 fn main() -> ExitCode {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+
+    let is_successful = match arguments.first().map(String::as_str) {
+        Some("fmt") => format_files(&arguments[1..]),
+        Some("minify") => minify_files(&arguments[1..]),
+        _ => parse_files(&arguments),
+    };
+
+    if is_successful {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// parses every file and prints any errors, returns false if there were errors
+///
+/// `--format json` prints the results as JSON instead, and `--ast` adds the syntax tree to it
+///
+/// `--strict` makes properties that are not known errors
+///
+/// `--specificity` prints the specificity of every selector in the files that parsed
+fn parse_files(arguments: &[String]) -> bool {
+    let mut file_names = Vec::new();
+    let mut is_json = false;
+    let mut include_ast = false;
+    let mut is_strict = false;
+    let mut include_specificity = false;
+
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--format" => match arguments.next().map(String::as_str) {
+                Some("json") => is_json = true,
+                Some("text") => is_json = false,
+                format => {
+                    eprintln!(
+                        "{} Unknown format {}, expected text or json",
+                        RED_X,
+                        format.unwrap_or("nothing")
+                    );
+                    return false;
+                }
+            },
+            "--ast" => include_ast = true,
+            "--strict" => is_strict = true,
+            "--specificity" => include_specificity = true,
+            _ => file_names.push(argument.clone()),
+        }
+    }
+
+    if is_json {
+        return parse_files_as_json(&file_names, include_ast, is_strict, include_specificity);
+    }
+
     let mut has_encountered_error = false;
 
-    for file_name in env::args().skip(1) {
-        let Ok(string) = fs::read_to_string(&file_name) else {
-            eprintln!("{} Can not read file {}", RED_X, file_name);
+    for file_name in &file_names {
+        let Some(string) = read_file(file_name) else {
             has_encountered_error = true;
             continue;
         };
 
         println!("{} Parsing {}", BLUE_I, file_name);
 
-        match string.parse::<Stylesheet>() {
-            Ok(_) => {
-                println!("{} Ok", GREEN_CHECK);
+        if let Some(stylesheet) = parse(file_name, &string, is_strict) {
+            if include_specificity {
+                for selector in selectors(&stylesheet.rules) {
+                    println!("{} {}", selector.specificity(), selector);
+                }
             }
+
+            println!("{} Ok", GREEN_CHECK);
+        } else {
+            has_encountered_error = true
+        }
+    }
+
+    !has_encountered_error
+}
+
+/// prints a JSON array with an object for every file
+///
+/// each object has the `file`, whether parsing it was a `success`, the `errors` as `Diagnostic` serializes them,
+/// and the `ast` and the `specificity` of every selector if they were asked for
+fn parse_files_as_json(
+    file_names: &[String],
+    include_ast: bool,
+    is_strict: bool,
+    include_specificity: bool,
+) -> bool {
+    let mut has_encountered_error = false;
+    let mut results = Vec::new();
+
+    for file_name in file_names {
+        let string = match fs::read_to_string(file_name) {
+            Ok(string) => string,
             Err(error) => {
-                eprintln!("{} {}", RED_X, error);
-                has_encountered_error = true
+                has_encountered_error = true;
+                results.push(json!({
+                    "file": file_name,
+                    "success": false,
+                    "errors": [{
+                        "file": file_name,
+                        "line": null,
+                        "column": null,
+                        "kind": "io",
+                        "expected": null,
+                        "found": null,
+                        "message": error.to_string(),
+                    }],
+                }));
+                continue;
             }
+        };
+
+        let (stylesheet, errors) = parse_with_recovery(&string, is_strict);
+        let diagnostics: Vec<Diagnostic> = errors
+            .iter()
+            .map(|error| Diagnostic::new(error, file_name, &string))
+            .collect();
+
+        let mut result = json!({
+            "file": file_name,
+            "success": errors.is_empty(),
+            "errors": diagnostics,
+        });
+
+        if include_ast {
+            result["ast"] = json!(stylesheet);
+        }
+
+        if include_specificity {
+            result["specificity"] = selectors(&stylesheet.rules)
+                .into_iter()
+                .map(|selector| {
+                    json!({
+                        "selector": selector.to_string(),
+                        "specificity": selector.specificity(),
+                    })
+                })
+                .collect();
         }
+
+        has_encountered_error |= !errors.is_empty();
+        results.push(result);
     }
 
-    if has_encountered_error {
-        ExitCode::FAILURE
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&results).expect("the results can always be serialized")
+    );
+
+    !has_encountered_error
+}
+
+/// every selector of the rulesets in the rules, including the ones nested in at-rules
+fn selectors(rules: &[Rule]) -> Vec<&Selector> {
+    let mut selectors = Vec::new();
+
+    for rule in rules {
+        match rule {
+            Rule::Ruleset(ruleset) => selectors.extend(&ruleset.selectors),
+            Rule::MediaRule(media_rule) => selectors.extend(self::selectors(&media_rule.rules)),
+            Rule::SupportsRule(supports_rule) => {
+                selectors.extend(self::selectors(&supports_rule.rules))
+            }
+            Rule::ContainerRule(container_rule) => {
+                selectors.extend(self::selectors(&container_rule.rules))
+            }
+            Rule::LayerBlock(layer_block) => selectors.extend(self::selectors(&layer_block.rules)),
+            Rule::Keyframes(_) | Rule::FontFace(_) | Rule::LayerStatement(_) | Rule::Unknown(_) => {
+            }
+        }
+    }
+
+    selectors
+}
+
+/// rewrites every file with `printer::format`
+///
+/// with `--check` the files are left alone and any file that is not formatted counts as an error
+///
+/// a file with comments is never rewritten since the comments are not kept in the syntax tree
+fn format_files(arguments: &[String]) -> bool {
+    let is_check = arguments.iter().any(|argument| argument == "--check");
+    let mut has_encountered_error = false;
+
+    for file_name in arguments.iter().filter(|argument| *argument != "--check") {
+        let Some(string) = read_file(file_name) else {
+            has_encountered_error = true;
+            continue;
+        };
+
+        let Some(stylesheet) = parse(file_name, &string, false) else {
+            eprintln!("{} Can not format {}", RED_X, file_name);
+            has_encountered_error = true;
+            continue;
+        };
+
+        if has_comments(&string) {
+            eprintln!(
+                "{} Can not format {} because its comments would be removed",
+                RED_X, file_name
+            );
+            has_encountered_error = true;
+            continue;
+        }
+
+        let formatted = printer::format(&stylesheet);
+
+        if formatted == string {
+            println!("{} {} is formatted", GREEN_CHECK, file_name);
+        } else if is_check {
+            eprintln!("{} {} is not formatted", RED_X, file_name);
+            has_encountered_error = true;
+        } else if fs::write(file_name, formatted).is_ok() {
+            println!("{} Formatted {}", BLUE_I, file_name);
+        } else {
+            eprintln!("{} Can not write file {}", RED_X, file_name);
+            has_encountered_error = true;
+        }
+    }
+
+    !has_encountered_error
+}
+
+/// prints every file with `printer::minify`
+///
+/// only the minified CSS is written to stdout so it can be redirected into a file
+fn minify_files(file_names: &[String]) -> bool {
+    let mut has_encountered_error = false;
+
+    for file_name in file_names {
+        let Some(stylesheet) =
+            read_file(file_name).and_then(|string| parse(file_name, &string, false))
+        else {
+            eprintln!("{} Can not minify {}", RED_X, file_name);
+            has_encountered_error = true;
+            continue;
+        };
+
+        println!("{}", printer::minify(&stylesheet));
+    }
+
+    !has_encountered_error
+}
+
+/// whether there are comments in a stylesheet, which are lost when it is printed
+fn has_comments(string: &str) -> bool {
+    let mut tokenizer = Tokenizer::new(string.chars());
+    tokenizer.by_ref().for_each(drop);
+    tokenizer.has_comments()
+}
+
+fn read_file(file_name: &str) -> Option<String> {
+    let string = fs::read_to_string(file_name);
+
+    if string.is_err() {
+        eprintln!("{} Can not read file {}", RED_X, file_name);
+    }
+
+    string.ok()
+}
+
+/// parses a stylesheet skipping over any invalid rules or declarations
+fn parse_with_recovery(string: &str, is_strict: bool) -> (Stylesheet, Vec<ParsingError>) {
+    let parser = Parser::with_error_recovery(string.chars());
+
+    if is_strict {
+        parser.strict().into_stylesheet_with_errors()
     } else {
-        ExitCode::SUCCESS
+        parser.into_stylesheet_with_errors()
     }
 }
+
+/// parses a stylesheet and prints every error, returns None if there were errors
+///
+/// the errors are only colored when they are written to a terminal
+fn parse(file_name: &str, string: &str, is_strict: bool) -> Option<Stylesheet> {
+    let (stylesheet, errors) = parse_with_recovery(string, is_strict);
+    let is_colored = io::stderr().is_terminal();
+
+    for error in &errors {
+        let diagnostic = Diagnostic::new(error, file_name, string).colored(is_colored);
+        eprintln!("{}\n", diagnostic);
+    }
+
+    errors.is_empty().then_some(stylesheet)
+}
@@ -1,36 +1,55 @@
-use std::iter::Peekable;
-use std::{fmt, str::FromStr};
+use std::fmt::{self, Write};
+use std::str::FromStr;
 
+use serde::Serialize;
+
+use crate::printer::{Printer, ToCss};
+use crate::span::{Position, Span, Spanned};
 use crate::tokenizer::{Token, TokenAt, Tokenizer};
 
-mod color;
+pub mod color;
 mod comma_separated;
-mod declaration;
-mod font_family;
+pub mod component_value;
+mod condition;
+pub mod container_condition;
+pub mod declaration;
+pub mod font_family;
 mod from_identifier;
-mod import;
-mod length;
-mod length_or_percentage;
-mod media_query;
-mod percentage;
-mod rule;
-mod selector;
-mod side;
+pub mod generic_rule;
+pub mod import;
+pub mod layer_name;
+pub mod length;
+pub mod length_or_percentage;
+pub mod math_function;
+pub mod media_query;
+pub mod percentage;
+pub mod rule;
+pub mod selector;
+pub mod side;
 mod string;
 mod stylesheet;
-mod url;
+pub mod supports_condition;
+pub mod time;
+mod tokens;
+pub mod unicode_range;
+pub mod url;
+pub mod variable;
 
 pub use comma_separated::*;
 pub use from_identifier::*;
+use generic_rule::GenericRule;
 pub use stylesheet::*;
+use tokens::Tokens;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum ParsingError {
     WrongToken {
         line: usize,
         column: usize,
         expected: String,
         found: String,
+        /// the span of the token that was found
+        span: Span,
     },
     EndOfFile {
         expected: String,
@@ -40,10 +59,11 @@ pub enum ParsingError {
 impl ParsingError {
     fn wrong_token(token_at: TokenAt, expected: &str) -> Self {
         Self::WrongToken {
-            line: token_at.line,
-            column: token_at.column,
+            line: token_at.span.start.line,
+            column: token_at.span.start.column,
             expected: expected.to_owned(),
             found: token_at.token.to_string(),
+            span: token_at.span,
         }
     }
 
@@ -62,6 +82,7 @@ impl fmt::Display for ParsingError {
                 column,
                 expected,
                 found,
+                ..
             } => {
                 write!(
                     formatter,
@@ -84,17 +105,141 @@ impl fmt::Display for ParsingError {
 }
 
 pub struct Parser<I: Iterator<Item = char>> {
-    tokens: Peekable<Tokenizer<I>>,
+    tokens: Tokens<I>,
+    /// the errors that have been recovered from, this is `None` when not in error recovery mode
+    errors: Option<Vec<ParsingError>>,
+    /// if properties that are not known are errors instead of `Declaration::Unknown`
+    is_strict: bool,
 }
 
 impl<I: Iterator<Item = char>> Parser<I> {
     pub fn new(input: I) -> Self {
         Self {
-            tokens: Tokenizer::new(input).peekable(),
+            tokens: Tokens::new(input),
+            errors: None,
+            is_strict: false,
+        }
+    }
+
+    /// creates a parser that skips over invalid rules and declarations instead of stopping at the first error
+    ///
+    /// the errors that were skipped over are returned by `into_stylesheet_with_errors`
+    pub fn with_error_recovery(input: I) -> Self {
+        Self {
+            tokens: Tokens::new(input),
+            errors: Some(Vec::new()),
+            is_strict: false,
         }
     }
 
-    fn parse<T: Parsable>(&mut self) -> Result<T, ParsingError> {
+    /// makes properties that are not known errors instead of keeping them as `Declaration::Unknown`
+    ///
+    /// this can be combined with error recovery like `Parser::with_error_recovery(input).strict()`
+    pub fn strict(mut self) -> Self {
+        self.is_strict = true;
+        self
+    }
+
+    /// if this parser is in error recovery mode
+    fn is_recovering(&self) -> bool {
+        self.errors.is_some()
+    }
+
+    /// in error recovery mode this records the error so that parsing can continue otherwise it returns the error
+    fn recover(&mut self, error: ParsingError) -> Result<(), ParsingError> {
+        match &mut self.errors {
+            Some(errors) => {
+                errors.push(error);
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    /// parse a rule and if it is invalid and we are in error recovery mode skip over it and return `None`
+    fn parse_rule<T: Parsable>(&mut self, is_at_rule: bool) -> Result<Option<T>, ParsingError> {
+        let depth = self.tokens.depth();
+
+        match self.parse() {
+            Ok(rule) => Ok(Some(rule)),
+            Err(error) => {
+                self.recover(error)?;
+                self.skip_rule(is_at_rule, depth);
+                Ok(None)
+            }
+        }
+    }
+
+    /// consume the rest of an invalid rule that started when `depth` blocks were open
+    ///
+    /// stops after the rule's block or after a semicolon if it is an at-rule,
+    /// but never consumes the `}` that closes the surrounding block.
+    /// Blocks that were opened by the part of the rule that was already consumed are skipped to their end
+    ///
+    /// adapted from https://www.w3.org/TR/css-syntax-3/#consume-qualified-rule
+    fn skip_rule(&mut self, is_at_rule: bool, depth: usize) {
+        loop {
+            let is_top_level = self.tokens.depth() <= depth;
+
+            match self.tokens.peek().map(|token_at| &token_at.token) {
+                None => return,
+                Some(Token::CloseCurlyBracket()) if is_top_level => return,
+                Some(Token::Semicolon()) if is_top_level && is_at_rule => {
+                    self.tokens.next();
+                    return;
+                }
+                _ => {}
+            }
+
+            let token_at = self.tokens.next().expect("the token was peeked");
+
+            // the `}` that closes the rule's block
+            if token_at.token == Token::CloseCurlyBracket() && self.tokens.depth() == depth {
+                return;
+            }
+        }
+    }
+
+    /// consume the rest of an invalid declaration that started when `depth` blocks were open
+    ///
+    /// stops after the next semicolon, but never consumes the `}` that closes the surrounding block
+    ///
+    /// adapted from https://www.w3.org/TR/css-syntax-3/#consume-list-of-declarations
+    fn skip_declaration(&mut self, depth: usize) {
+        loop {
+            let is_top_level = self.tokens.depth() <= depth;
+
+            match self.tokens.peek().map(|token_at| &token_at.token) {
+                None => return,
+                Some(Token::CloseCurlyBracket()) if is_top_level => return,
+                Some(Token::Semicolon()) if is_top_level => {
+                    self.tokens.next();
+                    return;
+                }
+                _ => {}
+            }
+
+            self.tokens.next();
+        }
+    }
+
+    /// where the next token starts, or where the last token ended at the end of the file
+    pub(crate) fn position(&mut self) -> Position {
+        let end = self.tokens.end();
+
+        match self.tokens.peek() {
+            Some(token_at) => token_at.span.start,
+            None => end,
+        }
+    }
+
+    /// the span from `start` to the end of the last token that is not whitespace
+    pub(crate) fn span_from(&self, start: Position) -> Span {
+        Span::new(start, self.tokens.end().max(start))
+    }
+
+    /// parse any type that implements Parsable
+    pub fn parse<T: Parsable>(&mut self) -> Result<T, ParsingError> {
         T::parse(self)
     }
 
@@ -113,6 +258,19 @@ impl<I: Iterator<Item = char>> Parser<I> {
         }
     }
 
+    /// expect the `}` at the end of a block
+    ///
+    /// in error recovery mode a block that is missing its `}` at the end of the file is still accepted
+    fn expect_end_of_block(&mut self) -> Result<(), ParsingError> {
+        if self.is_recovering() && self.tokens.peek().is_none() {
+            self.recover(ParsingError::end_of_file(
+                &Token::CloseCurlyBracket().to_string(),
+            ))
+        } else {
+            self.expect(Token::CloseCurlyBracket())
+        }
+    }
+
     /// consume whitespace token if there are any
     fn optional_whitespace(&mut self) {
         while let Some(TokenAt {
@@ -124,9 +282,54 @@ impl<I: Iterator<Item = char>> Parser<I> {
         }
     }
 
+    /// parse a stylesheet into rules and declarations that are kept as component values
+    ///
+    /// this accepts any syntax that follows the general rules of CSS, even rules and properties that
+    /// `into_stylesheet` does not support
+    pub fn into_generic_rules(mut self) -> Result<Vec<GenericRule>, ParsingError> {
+        self.parse()
+    }
+
+    /// parse generic rules and return them along with all the errors that were recovered from
+    ///
+    /// this is intended to be used with a parser created by `with_error_recovery`
+    pub fn into_generic_rules_with_errors(mut self) -> (Vec<GenericRule>, Vec<ParsingError>) {
+        let result = self.parse();
+        let mut errors = self.errors.unwrap_or_default();
+
+        match result {
+            Ok(rules) => (rules, errors),
+            Err(error) => {
+                errors.push(error);
+                (Vec::new(), errors)
+            }
+        }
+    }
+
     pub fn into_stylesheet(mut self) -> Result<Stylesheet, ParsingError> {
         self.parse()
     }
+
+    /// parse a stylesheet and return it along with all the errors that were recovered from
+    ///
+    /// this is intended to be used with a parser created by `with_error_recovery`
+    pub fn into_stylesheet_with_errors(mut self) -> (Stylesheet, Vec<ParsingError>) {
+        let result = self.parse();
+        let mut errors = self.errors.unwrap_or_default();
+
+        match result {
+            Ok(stylesheet) => (stylesheet, errors),
+            Err(error) => {
+                errors.push(error);
+                let stylesheet = Stylesheet {
+                    statements: Vec::new(),
+                    imports: Vec::new(),
+                    rules: Vec::new(),
+                };
+                (stylesheet, errors)
+            }
+        }
+    }
 }
 
 pub trait Parsable: Sized {
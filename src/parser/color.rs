@@ -1,7 +1,7 @@
 use super::{side::CanStart, *};
 use crate::tokenizer::*;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum Color {
     Black,
     Silver,
@@ -56,9 +56,19 @@ pub fn parse_num<I: Iterator<Item = char>>(
 ) -> Result<f64, ParsingError> {
     match parser.tokens.next() {
         Some(token_at) => match token_at.token {
-            Token::Number(val) => {
+            Token::Number(val, _) => {
                 if val >= min && val <= max {
                     Ok(val)
+                } else if max == f64::MAX && min == 0.0 {
+                    Err(ParsingError::wrong_token(
+                        token_at,
+                        "a number that is not negative",
+                    ))
+                } else if max == f64::MAX {
+                    Err(ParsingError::wrong_token(
+                        token_at,
+                        &format!("a number of at least {min}"),
+                    ))
                 } else {
                     Err(ParsingError::wrong_token(
                         token_at,
@@ -226,6 +236,105 @@ impl CanStart for Color {
     }
 }
 
+/// the shortest hex notation like `#f00` or `#ff4500` for whole numbered channels from 0 to 255
+fn hex_digits(channels: &[f64]) -> Option<String> {
+    let channels = channels
+        .iter()
+        .map(|channel| {
+            if channel.fract() == 0.0 && (0.0..=255.0).contains(channel) {
+                Some(*channel as u8)
+            } else {
+                None
+            }
+        })
+        .collect::<Option<Vec<u8>>>()?;
+
+    // each channel can be written with one digit if both of its digits are the same
+    if channels.iter().all(|channel| channel % 17 == 0) {
+        Some(channels.iter().fold("#".to_owned(), |digits, channel| {
+            digits + &format!("{:x}", channel / 17)
+        }))
+    } else {
+        Some(channels.iter().fold("#".to_owned(), |digits, channel| {
+            digits + &format!("{channel:02x}")
+        }))
+    }
+}
+
+impl ToCss for Color {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            Color::Black => printer.write_str("black"),
+            Color::Silver => printer.write_str("silver"),
+            Color::Gray => printer.write_str("gray"),
+            Color::Grey => printer.write_str("grey"),
+            Color::White => printer.write_str("white"),
+            Color::Maroon => printer.write_str("maroon"),
+            Color::Red => printer.write_str("red"),
+            Color::Purple => printer.write_str("purple"),
+            Color::Fuchsia => printer.write_str("fuchsia"),
+            Color::Green => printer.write_str("green"),
+            Color::Lime => printer.write_str("lime"),
+            Color::Olive => printer.write_str("olive"),
+            Color::Yellow => printer.write_str("yellow"),
+            Color::Navy => printer.write_str("navy"),
+            Color::Blue => printer.write_str("blue"),
+            Color::Teal => printer.write_str("teal"),
+            Color::Aqua => printer.write_str("aqua"),
+            Color::Transparent => printer.write_str("transparent"),
+            Color::Rgb { r, g, b, a } if printer.is_minified() => {
+                // an alpha that is a whole number out of 255, like one from `#aabbcc80`, can be hex too
+                let alpha = a * 255.0;
+                let digits = if *a == 1.0 {
+                    hex_digits(&[*r, *g, *b])
+                } else if (alpha - alpha.round()).abs() < 1e-9 {
+                    hex_digits(&[*r, *g, *b, alpha.round()])
+                } else {
+                    None
+                };
+
+                match digits {
+                    Some(digits) => printer.write_str(&digits),
+                    None if *a == 1.0 => write!(printer, "rgb({r},{g},{b})"),
+                    None => write!(
+                        printer,
+                        "rgba({r},{g},{b},{})",
+                        (a * 1000.0).round() / 1000.0
+                    ),
+                }
+            }
+            Color::Rgb { r, g, b, a } => {
+                let separator = if printer.is_minified() { "," } else { ", " };
+                if *a == 1.0 {
+                    write!(printer, "rgb({r}{separator}{g}{separator}{b})")
+                } else {
+                    write!(
+                        printer,
+                        "rgba({r}{separator}{g}{separator}{b}{separator}{a})"
+                    )
+                }
+            }
+            Color::Hsl { h, s, l, a } => {
+                let separator = if printer.is_minified() { "," } else { ", " };
+                if *a == 1.0 {
+                    write!(printer, "hsl({h}{separator}{s}%{separator}{l}%)")
+                } else {
+                    write!(
+                        printer,
+                        "hsla({h}{separator}{s}%{separator}{l}%{separator}{a})"
+                    )
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -602,4 +711,84 @@ mod tests {
         let mut parser = Parser::new("#H8NKNC".chars());
         assert!(parser.parse::<Color>().is_err());
     }
+
+    #[test]
+    fn serialize_keyword() {
+        assert_eq!("fuchsia", Color::Fuchsia.to_string());
+    }
+
+    #[test]
+    fn serialize_rgb() {
+        let mut parser = Parser::new("#FF48FA".chars());
+        assert_eq!(
+            "rgb(255, 72, 250)",
+            parser.parse::<Color>().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn serialize_rgba() {
+        let mut parser = Parser::new("rgba(1, 2, 3, 0.5)".chars());
+        assert_eq!(
+            "rgba(1, 2, 3, 0.5)",
+            parser.parse::<Color>().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn minify_short_hex() {
+        let color = Color::Rgb {
+            r: 255.0,
+            g: 0.0,
+            b: 170.0,
+            a: 1.0,
+        };
+        assert_eq!("#f0a", crate::printer::minify(&color));
+    }
+
+    #[test]
+    fn minify_long_hex() {
+        let color = Color::Rgb {
+            r: 255.0,
+            g: 72.0,
+            b: 250.0,
+            a: 1.0,
+        };
+        assert_eq!("#ff48fa", crate::printer::minify(&color));
+    }
+
+    #[test]
+    fn minify_rgba() {
+        let color = Color::Rgb {
+            r: 1.0,
+            g: 2.0,
+            b: 3.0,
+            a: 0.5,
+        };
+        assert_eq!("rgba(1,2,3,0.5)", crate::printer::minify(&color));
+    }
+
+    #[test]
+    fn minify_hex_with_alpha() {
+        for (input, output) in [
+            ("#aabbcc80", "#aabbcc80"),
+            ("#aabbcc88", "#abc8"),
+            ("#abc0", "#abc0"),
+        ] {
+            let mut parser = Parser::new(input.chars());
+            let color: Color = parser.parse().unwrap();
+            assert_eq!(output, crate::printer::minify(&color));
+        }
+    }
+
+    #[test]
+    fn minify_rgba_with_long_alpha() {
+        let color = Color::Rgb {
+            r: 1.0,
+            g: 2.0,
+            b: 3.0,
+            a: 0.123456,
+        };
+        assert_eq!("rgba(1,2,3,0.123)", crate::printer::minify(&color));
+    }
 }
@@ -0,0 +1,399 @@
+use super::{variable::Variable, *};
+use crate::tokenizer::HashType;
+
+/// A piece of a value that is kept as it was written instead of being parsed into a specific type
+/// Grammar: `<component-value>`
+///
+/// adapted from https://www.w3.org/TR/css-syntax-3/#component-value
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum ComponentValue {
+    Token(Token),
+    Variable(Variable),
+    Function(String, Vec<ComponentValue>),
+    ParenthesisBlock(Vec<ComponentValue>),
+    SquareBracketBlock(Vec<ComponentValue>),
+    CurlyBracketBlock(Vec<ComponentValue>),
+}
+
+impl Parsable for ComponentValue {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Function(name),
+                ..
+            }) if name.eq_ignore_ascii_case("var") => {
+                return Ok(ComponentValue::Variable(parser.parse()?));
+            }
+            None => return Err(ParsingError::end_of_file("a value")),
+            _ => {}
+        }
+
+        let token_at = parser.tokens.next().expect("the token was peeked");
+
+        match token_at.token {
+            Token::Function(name) => Ok(ComponentValue::Function(
+                name,
+                parser.parse_component_values_until(Token::CloseParenthesis())?,
+            )),
+            Token::OpenParenthesis() => Ok(ComponentValue::ParenthesisBlock(
+                parser.parse_component_values_until(Token::CloseParenthesis())?,
+            )),
+            Token::OpenSquareBracket() => Ok(ComponentValue::SquareBracketBlock(
+                parser.parse_component_values_until(Token::CloseSquareBracket())?,
+            )),
+            Token::OpenCurlyBracket() => Ok(ComponentValue::CurlyBracketBlock(
+                parser.parse_component_values_until(Token::CloseCurlyBracket())?,
+            )),
+
+            // these can not be part of a valid value
+            Token::BadComment()
+            | Token::BadString()
+            | Token::BadUrl()
+            | Token::CloseParenthesis()
+            | Token::CloseSquareBracket()
+            | Token::CloseCurlyBracket() => Err(ParsingError::wrong_token(token_at, "a value")),
+
+            token => Ok(ComponentValue::Token(token)),
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// parse the value of a declaration as component values, without the whitespace around it
+    ///
+    /// stops before the `;` or `}` that ends the declaration, or before `!important`
+    pub fn parse_component_values(&mut self) -> Result<Vec<ComponentValue>, ParsingError> {
+        let mut values = Vec::new();
+
+        while !self.is_important_next() {
+            match self.tokens.peek() {
+                None
+                | Some(TokenAt {
+                    token: Token::Semicolon() | Token::CloseCurlyBracket(),
+                    ..
+                }) => break,
+                _ => push_component_value(&mut values, self.parse()?),
+            }
+        }
+
+        trim_whitespace(&mut values);
+        Ok(values)
+    }
+
+    /// parse the contents of a function or block and the token that closes it
+    pub(crate) fn parse_component_values_until(
+        &mut self,
+        close: Token,
+    ) -> Result<Vec<ComponentValue>, ParsingError> {
+        let mut values = Vec::new();
+
+        loop {
+            match self.tokens.peek() {
+                Some(token_at) if token_at.token == close => {
+                    self.tokens.next();
+                    return Ok(values);
+                }
+                Some(TokenAt {
+                    token:
+                        Token::CloseParenthesis()
+                        | Token::CloseSquareBracket()
+                        | Token::CloseCurlyBracket(),
+                    ..
+                }) => {
+                    let token_at = self.tokens.next().expect("the token was peeked");
+                    return Err(ParsingError::wrong_token(token_at, &close.to_string()));
+                }
+                Some(_) => push_component_value(&mut values, self.parse()?),
+                None => return Err(ParsingError::end_of_file(&close.to_string())),
+            }
+        }
+    }
+}
+
+/// add a value to the end of a list, whitespace right after whitespace is left out
+///
+/// a comment between whitespace is not kept, so there can be more than one whitespace token in a row
+pub(crate) fn push_component_value(values: &mut Vec<ComponentValue>, value: ComponentValue) {
+    let is_whitespace =
+        |value: &ComponentValue| *value == ComponentValue::Token(Token::Whitespace());
+
+    if !(is_whitespace(&value) && values.last().is_some_and(is_whitespace)) {
+        values.push(value);
+    }
+}
+
+/// remove the whitespace at the start and end of a list of component values
+pub(crate) fn trim_whitespace(values: &mut Vec<ComponentValue>) {
+    let is_whitespace =
+        |value: &ComponentValue| *value == ComponentValue::Token(Token::Whitespace());
+
+    while values.last().is_some_and(is_whitespace) {
+        values.pop();
+    }
+
+    let leading = values
+        .iter()
+        .take_while(|value| is_whitespace(value))
+        .count();
+    values.drain(..leading);
+}
+
+/// if whitespace next to this component value can be left out without changing what is read back
+fn is_separator(value: Option<&ComponentValue>) -> bool {
+    matches!(
+        value,
+        None | Some(
+            ComponentValue::Token(Token::Comma() | Token::Semicolon())
+                | ComponentValue::CurlyBracketBlock(_)
+        )
+    )
+}
+
+impl Printer<'_> {
+    /// print component values one after the other, they include their own whitespace
+    ///
+    /// when minified the whitespace at the start and end, around commas, semicolons and `{}` blocks, and after
+    /// a colon is left out
+    pub fn print_component_values(&mut self, values: &[ComponentValue]) -> fmt::Result {
+        for (index, value) in values.iter().enumerate() {
+            let previous = index.checked_sub(1).and_then(|index| values.get(index));
+            let next = values.get(index + 1);
+
+            let is_unneeded = *value == ComponentValue::Token(Token::Whitespace())
+                && (is_separator(previous)
+                    || is_separator(next)
+                    || previous == Some(&ComponentValue::Token(Token::Colon())));
+
+            if !(self.is_minified() && is_unneeded) {
+                self.print(value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ToCss for ComponentValue {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            ComponentValue::Token(token) => printer.print(token),
+            ComponentValue::Variable(variable) => printer.print(variable),
+            ComponentValue::Function(name, values) => {
+                printer.print_identifier(name)?;
+                printer.write_char('(')?;
+                printer.print_component_values(values)?;
+                printer.write_char(')')
+            }
+            ComponentValue::ParenthesisBlock(values) => {
+                printer.write_char('(')?;
+                printer.print_component_values(values)?;
+                printer.write_char(')')
+            }
+            ComponentValue::SquareBracketBlock(values) => {
+                printer.write_char('[')?;
+                printer.print_component_values(values)?;
+                printer.write_char(']')
+            }
+            ComponentValue::CurlyBracketBlock(values) => {
+                printer.write_char('{')?;
+                printer.print_component_values(values)?;
+                printer.write_char('}')
+            }
+        }
+    }
+}
+
+impl fmt::Display for ComponentValue {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+/// invalid tokens are never part of a syntax tree so they print nothing
+impl ToCss for Token {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            Token::BadComment() | Token::BadString() | Token::BadUrl() => Ok(()),
+            Token::Identifier(name) => printer.print_identifier(name),
+            Token::Function(name) => {
+                printer.print_identifier(name)?;
+                printer.write_char('(')
+            }
+            Token::AtKeyword(name) => {
+                printer.write_char('@')?;
+                printer.print_identifier(name)
+            }
+            Token::Hash(name, HashType::Id) => {
+                printer.write_char('#')?;
+                printer.print_identifier(name)
+            }
+            Token::Hash(name, HashType::Unrestricted) => {
+                printer.write_char('#')?;
+                printer.print_name(name)
+            }
+            Token::String(string) => printer.print_string(string),
+            Token::Url(url) => {
+                printer.write_str("url(")?;
+
+                // a quoted url would be read back as a `url(` function instead of a url token
+                let is_unquoted = !url.is_empty()
+                    && url.chars().all(|character| {
+                        character.is_ascii_graphic() && !"\"'()\\".contains(character)
+                    });
+                if is_unquoted {
+                    printer.write_str(url)?;
+                } else {
+                    printer.print_string(url)?;
+                }
+
+                printer.write_char(')')
+            }
+            Token::Delimiter(character) => printer.write_char(*character),
+            // the sign is kept since it matters in values like `U+0-FF`
+            Token::Number(value, sign) => write!(printer, "{}{value}", sign.prefix()),
+            Token::Percentage(value) => write!(printer, "{value}%"),
+            Token::Dimension(value, sign, unit) => {
+                write!(printer, "{}{value}", sign.prefix())?;
+                printer.print_identifier(unit)
+            }
+            Token::Whitespace() => printer.write_char(' '),
+            Token::Colon() => printer.write_char(':'),
+            Token::Semicolon() => printer.write_char(';'),
+            Token::Comma() => printer.write_char(','),
+            Token::OpenSquareBracket() => printer.write_char('['),
+            Token::CloseSquareBracket() => printer.write_char(']'),
+            Token::OpenParenthesis() => printer.write_char('('),
+            Token::CloseParenthesis() => printer.write_char(')'),
+            Token::OpenCurlyBracket() => printer.write_char('{'),
+            Token::CloseCurlyBracket() => printer.write_char('}'),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::Sign;
+
+    fn parse_component_values(input: &str) -> Result<Vec<ComponentValue>, ParsingError> {
+        let mut parser = Parser::new(input.chars());
+        parser.parse_component_values()
+    }
+
+    #[test]
+    fn tokens() {
+        assert_eq!(
+            Ok(vec![
+                ComponentValue::Token(Token::Number(1.0, Sign::Signless)),
+                ComponentValue::Token(Token::Whitespace()),
+                ComponentValue::Token(Token::Identifier("solid".to_owned())),
+            ]),
+            parse_component_values("  1 solid  ")
+        );
+    }
+
+    #[test]
+    fn whitespace_around_comment() {
+        assert_eq!(
+            Ok(vec![
+                ComponentValue::Token(Token::Number(1.0, Sign::Signless)),
+                ComponentValue::Token(Token::Whitespace()),
+                ComponentValue::Token(Token::Number(2.0, Sign::Signless)),
+            ]),
+            parse_component_values("1 /* a comment */ 2")
+        );
+    }
+
+    #[test]
+    fn function() {
+        assert_eq!(
+            Ok(vec![ComponentValue::Function(
+                "rgb".to_owned(),
+                vec![
+                    ComponentValue::Token(Token::Number(0.0, Sign::Signless)),
+                    ComponentValue::Token(Token::Comma()),
+                    ComponentValue::Token(Token::Number(1.0, Sign::Signless)),
+                ]
+            )]),
+            parse_component_values("rgb(0,1)")
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        assert_eq!(
+            Ok(vec![ComponentValue::SquareBracketBlock(vec![
+                ComponentValue::CurlyBracketBlock(vec![ComponentValue::ParenthesisBlock(vec![])])
+            ])]),
+            parse_component_values("[{()}]")
+        );
+    }
+
+    #[test]
+    fn stops_at_end_of_declaration() {
+        let mut parser = Parser::new("a b; c".chars());
+        assert_eq!(3, parser.parse_component_values().unwrap().len());
+        assert_eq!(
+            Some(Token::Semicolon()),
+            parser.tokens.next().map(|t| t.token)
+        );
+    }
+
+    #[test]
+    fn semicolon_in_block() {
+        assert_eq!(
+            Ok(vec![ComponentValue::CurlyBracketBlock(vec![
+                ComponentValue::Token(Token::Semicolon())
+            ])]),
+            parse_component_values("{;}")
+        );
+    }
+
+    #[test]
+    fn unclosed_function() {
+        assert_eq!(
+            Err(ParsingError::EndOfFile {
+                expected: ")".to_owned()
+            }),
+            parse_component_values("rgb(0")
+        );
+    }
+
+    #[test]
+    fn mismatched_block() {
+        assert!(parse_component_values("(]").is_err());
+        assert!(parse_component_values("a)").is_err());
+    }
+
+    #[test]
+    fn bad_string() {
+        assert!(parse_component_values("\"abc\n").is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let input = "1px solid #0f0 rgb(0, 0, 0) [a] \"b\" 50% @c";
+        let values = parse_component_values(input).unwrap();
+        let mut output = String::new();
+        Printer::new(&mut output)
+            .print_component_values(&values)
+            .unwrap();
+        assert_eq!(input, output);
+    }
+
+    #[test]
+    fn minify() {
+        for (input, output) in [
+            ("translate( 1px , 2px )", "translate(1px,2px)"),
+            ("a: 1in , b { c }", "a:1in,b{c}"),
+            ("1px solid red", "1px solid red"),
+            ("a :hover and (b)", "a :hover and (b)"),
+        ] {
+            let values = parse_component_values(input).unwrap();
+            let mut minified = String::new();
+            Printer::minified(&mut minified)
+                .print_component_values(&values)
+                .unwrap();
+            assert_eq!(output, minified);
+        }
+    }
+}
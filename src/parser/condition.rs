@@ -0,0 +1,141 @@
+use super::*;
+
+/// `Some(true)` for `and` and `Some(false)` for `or`
+fn operator(token: &Token) -> Option<bool> {
+    match token {
+        Token::Identifier(name) if name.eq_ignore_ascii_case("and") => Some(true),
+        Token::Identifier(name) if name.eq_ignore_ascii_case("or") => Some(false),
+        _ => None,
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// parse a boolean condition like the ones in `@supports` and `@container`, which is
+    /// `not a`, `a`, `a and b and ...`, or `a or b or ...` where each operand is in parentheses
+    ///
+    /// `and` and `or` can not be mixed without parentheses, and `a and b and c` is nested to the
+    /// right as `and(a, and(b, c))`
+    ///
+    /// adapted from https://www.w3.org/TR/css-conditional-3/#typedef-supports-condition
+    pub(crate) fn parse_condition<T>(
+        &mut self,
+        parse_in_parentheses: fn(&mut Self) -> Result<T, ParsingError>,
+        not: fn(Box<T>) -> T,
+        and: fn(Box<T>, Box<T>) -> T,
+        or: fn(Box<T>, Box<T>) -> T,
+    ) -> Result<T, ParsingError> {
+        if let Some(TokenAt {
+            token: Token::Identifier(name),
+            ..
+        }) = self.tokens.peek()
+        {
+            if name.eq_ignore_ascii_case("not") {
+                self.tokens.next();
+                self.optional_whitespace();
+                return Ok(not(Box::new(parse_in_parentheses(self)?)));
+            }
+        }
+
+        let first = parse_in_parentheses(self)?;
+
+        let Some(is_and) = self.peek_condition_operator() else {
+            return Ok(first);
+        };
+
+        let mut operands = vec![first];
+
+        loop {
+            self.optional_whitespace();
+            let token_at = self.tokens.next().expect("the operator was peeked");
+            if operator(&token_at.token) != Some(is_and) {
+                return Err(ParsingError::wrong_token(
+                    token_at,
+                    "parentheses around a mix of and and or",
+                ));
+            }
+
+            self.optional_whitespace();
+            operands.push(parse_in_parentheses(self)?);
+
+            if self.peek_condition_operator().is_none() {
+                break;
+            }
+        }
+
+        let combine = if is_and { and } else { or };
+        let last = operands.pop().expect("there are at least two operands");
+        Ok(operands
+            .into_iter()
+            .rev()
+            .fold(last, |right, left| combine(Box::new(left), Box::new(right))))
+    }
+
+    /// whether whitespace and then `and` or `or` is next, without consuming anything
+    ///
+    /// the whitespace after a condition is only consumed once we know an operator follows it
+    fn peek_condition_operator(&mut self) -> Option<bool> {
+        match self.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Whitespace(),
+                ..
+            }) => self
+                .tokens
+                .peek_nth(1)
+                .and_then(|token_at| operator(&token_at.token)),
+            _ => None,
+        }
+    }
+}
+
+/// The operator a condition is made of along with its operands
+pub(crate) enum Operation<'a, T> {
+    Not(&'a T),
+    And(&'a T, &'a T),
+    Or(&'a T, &'a T),
+}
+
+/// A boolean condition that `parse_condition` builds, so it can be printed the same way
+pub(crate) trait Condition: ToCss + Sized {
+    /// the operator this condition is made of, or `None` if it is a single test like `(width > 10px)`
+    fn operation(&self) -> Option<Operation<'_, Self>>;
+}
+
+/// print a condition made of `not`, `and`, or `or`, a condition that is not is printed as it is
+///
+/// `a and (b and c)` is printed as `a and b and c` since it means the same thing
+pub(crate) fn print_condition<T: Condition>(printer: &mut Printer, condition: &T) -> fmt::Result {
+    match condition.operation() {
+        Some(Operation::Not(operand)) => {
+            printer.write_str("not ")?;
+            print_operand(printer, operand, false)
+        }
+        Some(Operation::And(left, right)) => {
+            print_operand(printer, left, false)?;
+            printer.write_str(" and ")?;
+            let is_and = matches!(right.operation(), Some(Operation::And(_, _)));
+            print_operand(printer, right, is_and)
+        }
+        Some(Operation::Or(left, right)) => {
+            print_operand(printer, left, false)?;
+            printer.write_str(" or ")?;
+            let is_or = matches!(right.operation(), Some(Operation::Or(_, _)));
+            print_operand(printer, right, is_or)
+        }
+        None => printer.print(condition),
+    }
+}
+
+/// print an operand of `not`, `and`, or `or`, adding parentheses if it is also made of an operator
+fn print_operand<T: Condition>(
+    printer: &mut Printer,
+    operand: &T,
+    is_same_operator: bool,
+) -> fmt::Result {
+    if operand.operation().is_some() && !is_same_operator {
+        printer.write_char('(')?;
+        print_condition(printer, operand)?;
+        printer.write_char(')')
+    } else {
+        print_condition(printer, operand)
+    }
+}
@@ -0,0 +1,510 @@
+use super::{
+    condition::{print_condition, Condition, Operation},
+    length::Length,
+    media_query::MediaFeature,
+    *,
+};
+
+/// A test of the size of a container like `(min-width: 400px) and (inline-size < 60em)`
+///
+/// adapted from https://www.w3.org/TR/css-contain-3/#container-rule
+#[derive(Debug, PartialEq, Serialize)]
+pub enum ContainerCondition {
+    SizeFeature(SizeFeature),
+    Not(Box<ContainerCondition>),
+    And(Box<ContainerCondition>, Box<ContainerCondition>),
+    Or(Box<ContainerCondition>, Box<ContainerCondition>),
+}
+
+/// A single test of the size of a container
+///
+/// adapted from https://www.w3.org/TR/css-contain-3/#size-container
+#[derive(Debug, PartialEq, Serialize)]
+pub enum SizeFeature {
+    /// `width`, `min-width`, `max-width`, `height`, `min-height`, or `max-height` which are the
+    /// same as in a media query
+    MediaFeature(MediaFeature),
+    MinInlineSize(Length),
+    InlineSize(Length),
+    MaxInlineSize(Length),
+    MinBlockSize(Length),
+    BlockSize(Length),
+    MaxBlockSize(Length),
+    /// a comparison like `width > 400px` or `400px <= inline-size < 800px`
+    Range(SizeRange),
+}
+
+/// A size compared to one or two lengths, at least one of `start` and `end` is always set
+///
+/// adapted from https://www.w3.org/TR/mediaqueries-4/#mq-range-context
+#[derive(Debug, PartialEq, Serialize)]
+pub struct SizeRange {
+    /// the length and comparison before the name like `400px <=`
+    pub start: Option<(Length, Comparison)>,
+    pub name: SizeFeatureName,
+    /// the comparison and length after the name like `< 800px`
+    pub end: Option<(Comparison, Length)>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum SizeFeatureName {
+    Width,
+    Height,
+    InlineSize,
+    BlockSize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum Comparison {
+    Equal,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+/// the size features that are shared with media queries
+const MEDIA_SIZE_FEATURES: [&str; 6] = [
+    "min-width",
+    "width",
+    "max-width",
+    "min-height",
+    "height",
+    "max-height",
+];
+
+impl Parsable for ContainerCondition {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        parser.parse_condition(
+            Parser::parse_container_in_parentheses,
+            ContainerCondition::Not,
+            ContainerCondition::And,
+            ContainerCondition::Or,
+        )
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// parse a condition or a size feature in parentheses
+    fn parse_container_in_parentheses(&mut self) -> Result<ContainerCondition, ParsingError> {
+        self.expect(Token::OpenParenthesis())?;
+        self.optional_whitespace();
+
+        let condition = match self.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Identifier(name),
+                ..
+            }) if name.eq_ignore_ascii_case("not") => self.parse()?,
+            Some(TokenAt {
+                token: Token::OpenParenthesis(),
+                ..
+            }) => self.parse()?,
+            Some(_) => ContainerCondition::SizeFeature(self.parse()?),
+            None => return Err(ParsingError::end_of_file("a container condition")),
+        };
+
+        self.optional_whitespace();
+        self.expect(Token::CloseParenthesis())?;
+        Ok(condition)
+    }
+
+    /// whether a comparison like `<` comes after the name that is next, without consuming anything
+    fn is_size_range(&mut self) -> bool {
+        let after_name = match self.tokens.peek_nth(1) {
+            Some(TokenAt {
+                token: Token::Whitespace(),
+                ..
+            }) => self.tokens.peek_nth(2),
+            token_at => token_at,
+        };
+
+        matches!(
+            after_name,
+            Some(TokenAt {
+                token: Token::Delimiter('<' | '>' | '='),
+                ..
+            })
+        )
+    }
+}
+
+impl Parsable for SizeFeature {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        const EXPECTED: &str = "a size feature";
+
+        let name = match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Identifier(name),
+                ..
+            }) => name.clone(),
+            Some(_) => return Ok(SizeFeature::Range(parser.parse()?)),
+            None => return Err(ParsingError::end_of_file(EXPECTED)),
+        };
+
+        if parser.is_size_range() {
+            return Ok(SizeFeature::Range(parser.parse()?));
+        }
+
+        if MEDIA_SIZE_FEATURES.contains(&name.as_str()) {
+            return Ok(SizeFeature::MediaFeature(parser.parse()?));
+        }
+
+        let token_at = parser.tokens.next().expect("the name was peeked");
+
+        let size_feature: fn(Length) -> SizeFeature = match name.as_str() {
+            "min-inline-size" => SizeFeature::MinInlineSize,
+            "inline-size" => SizeFeature::InlineSize,
+            "max-inline-size" => SizeFeature::MaxInlineSize,
+            "min-block-size" => SizeFeature::MinBlockSize,
+            "block-size" => SizeFeature::BlockSize,
+            "max-block-size" => SizeFeature::MaxBlockSize,
+            _ => return Err(ParsingError::wrong_token(token_at, EXPECTED)),
+        };
+
+        parser.consume_colon_separator()?;
+        Ok(size_feature(parser.parse()?))
+    }
+}
+
+impl Parsable for SizeRange {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Identifier(_),
+                ..
+            }) => None,
+            _ => {
+                let length = parser.parse()?;
+                parser.optional_whitespace();
+                let comparison: Comparison = parser.parse()?;
+                parser.optional_whitespace();
+                Some((length, comparison))
+            }
+        };
+
+        let name = parser.parse()?;
+
+        parser.optional_whitespace();
+
+        let end = match parser.tokens.peek() {
+            Some(
+                token_at @ TokenAt {
+                    token: Token::Delimiter('<' | '>' | '='),
+                    ..
+                },
+            ) => {
+                let token_at = token_at.clone();
+                let comparison: Comparison = parser.parse()?;
+
+                // `400px < width > 800px` and `400px = width = 800px` can not be true
+                if let Some((_, first)) = start {
+                    if !first.is_same_direction(comparison) {
+                        return Err(ParsingError::wrong_token(
+                            token_at,
+                            "a comparison in the same direction as the first one",
+                        ));
+                    }
+                }
+
+                parser.optional_whitespace();
+                Some((comparison, parser.parse()?))
+            }
+            Some(token_at) if start.is_none() => {
+                return Err(ParsingError::wrong_token(token_at.clone(), "<, >, or ="))
+            }
+            None if start.is_none() => return Err(ParsingError::end_of_file("<, >, or =")),
+            _ => None,
+        };
+
+        Ok(SizeRange { start, name, end })
+    }
+}
+
+impl FromStr for SizeFeatureName {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "width" => Ok(SizeFeatureName::Width),
+            "height" => Ok(SizeFeatureName::Height),
+            "inline-size" => Ok(SizeFeatureName::InlineSize),
+            "block-size" => Ok(SizeFeatureName::BlockSize),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromIdentifier for SizeFeatureName {
+    const EXPECTED: &'static str = "width, height, inline-size, or block-size";
+}
+
+impl Parsable for Comparison {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        const EXPECTED: &str = "<, >, or =";
+
+        let token_at = match parser.tokens.next() {
+            Some(token_at) => token_at,
+            None => return Err(ParsingError::end_of_file(EXPECTED)),
+        };
+
+        let (strict, or_equal) = match token_at.token {
+            Token::Delimiter('=') => return Ok(Comparison::Equal),
+            Token::Delimiter('<') => (Comparison::LessThan, Comparison::LessThanOrEqual),
+            Token::Delimiter('>') => (Comparison::GreaterThan, Comparison::GreaterThanOrEqual),
+            _ => return Err(ParsingError::wrong_token(token_at, EXPECTED)),
+        };
+
+        // there can not be whitespace in `<=` and `>=`
+        match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Delimiter('='),
+                ..
+            }) => {
+                parser.tokens.next();
+                Ok(or_equal)
+            }
+            _ => Ok(strict),
+        }
+    }
+}
+
+impl Comparison {
+    /// whether both comparisons are `<` or `<=`, or both are `>` or `>=`
+    fn is_same_direction(self, other: Comparison) -> bool {
+        use Comparison::*;
+
+        matches!(
+            (self, other),
+            (LessThan | LessThanOrEqual, LessThan | LessThanOrEqual)
+                | (
+                    GreaterThan | GreaterThanOrEqual,
+                    GreaterThan | GreaterThanOrEqual
+                )
+        )
+    }
+}
+
+impl Condition for ContainerCondition {
+    fn operation(&self) -> Option<Operation<'_, Self>> {
+        match self {
+            ContainerCondition::Not(operand) => Some(Operation::Not(operand)),
+            ContainerCondition::And(left, right) => Some(Operation::And(left, right)),
+            ContainerCondition::Or(left, right) => Some(Operation::Or(left, right)),
+            _ => None,
+        }
+    }
+}
+
+impl ToCss for ContainerCondition {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            ContainerCondition::SizeFeature(size_feature) => {
+                printer.write_char('(')?;
+                printer.print(size_feature)?;
+                printer.write_char(')')
+            }
+            ContainerCondition::Not(_)
+            | ContainerCondition::And(_, _)
+            | ContainerCondition::Or(_, _) => print_condition(printer, self),
+        }
+    }
+}
+
+impl ToCss for SizeFeature {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        let (name, length) = match self {
+            SizeFeature::MediaFeature(media_feature) => return printer.print(media_feature),
+            SizeFeature::Range(range) => return printer.print(range),
+            SizeFeature::MinInlineSize(length) => ("min-inline-size", length),
+            SizeFeature::InlineSize(length) => ("inline-size", length),
+            SizeFeature::MaxInlineSize(length) => ("max-inline-size", length),
+            SizeFeature::MinBlockSize(length) => ("min-block-size", length),
+            SizeFeature::BlockSize(length) => ("block-size", length),
+            SizeFeature::MaxBlockSize(length) => ("max-block-size", length),
+        };
+
+        printer.write_str(name)?;
+        printer.write_char(':')?;
+        printer.print_optional_space()?;
+        printer.print(length)
+    }
+}
+
+impl ToCss for SizeRange {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        if let Some((length, comparison)) = &self.start {
+            printer.print(length)?;
+            printer.print_optional_space()?;
+            printer.print(comparison)?;
+            printer.print_optional_space()?;
+        }
+
+        printer.print(&self.name)?;
+
+        if let Some((comparison, length)) = &self.end {
+            printer.print_optional_space()?;
+            printer.print(comparison)?;
+            printer.print_optional_space()?;
+            printer.print(length)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToCss for SizeFeatureName {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            SizeFeatureName::Width => "width",
+            SizeFeatureName::Height => "height",
+            SizeFeatureName::InlineSize => "inline-size",
+            SizeFeatureName::BlockSize => "block-size",
+        })
+    }
+}
+
+impl ToCss for Comparison {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            Comparison::Equal => "=",
+            Comparison::LessThan => "<",
+            Comparison::LessThanOrEqual => "<=",
+            Comparison::GreaterThan => ">",
+            Comparison::GreaterThanOrEqual => ">=",
+        })
+    }
+}
+
+impl fmt::Display for ContainerCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for SizeFeature {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{length::LengthUnit, *};
+
+    const PIXELS_400: Length = Length::Length(400.0, LengthUnit::Pixels);
+    const PIXELS_800: Length = Length::Length(800.0, LengthUnit::Pixels);
+
+    #[test]
+    fn media_feature() {
+        let mut parser = Parser::new("(min-width: 400px)".chars());
+        assert_eq!(
+            Ok(ContainerCondition::SizeFeature(SizeFeature::MediaFeature(
+                MediaFeature::MinWidth(PIXELS_400)
+            ))),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn not_a_size_feature() {
+        let mut parser = Parser::new("(hover: hover)".chars());
+        assert!(parser.parse::<ContainerCondition>().is_err());
+    }
+
+    #[test]
+    fn inline_size() {
+        let mut parser = Parser::new("( inline-size : 400px )".chars());
+        assert_eq!(
+            Ok(ContainerCondition::SizeFeature(SizeFeature::InlineSize(
+                PIXELS_400
+            ))),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn range() {
+        let mut parser = Parser::new("width>=400px".chars());
+        assert_eq!(
+            Ok(SizeFeature::Range(SizeRange {
+                start: None,
+                name: SizeFeatureName::Width,
+                end: Some((Comparison::GreaterThanOrEqual, PIXELS_400)),
+            })),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn range_with_length_first() {
+        let mut parser = Parser::new("400px = block-size".chars());
+        assert_eq!(
+            Ok(SizeFeature::Range(SizeRange {
+                start: Some((PIXELS_400, Comparison::Equal)),
+                name: SizeFeatureName::BlockSize,
+                end: None,
+            })),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn range_between() {
+        let mut parser = Parser::new("400px <= inline-size < 800px".chars());
+        assert_eq!(
+            Ok(SizeFeature::Range(SizeRange {
+                start: Some((PIXELS_400, Comparison::LessThanOrEqual)),
+                name: SizeFeatureName::InlineSize,
+                end: Some((Comparison::LessThan, PIXELS_800)),
+            })),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn range_in_different_directions() {
+        let mut parser = Parser::new("400px < width > 800px".chars());
+        assert!(parser.parse::<SizeFeature>().is_err());
+
+        let mut parser = Parser::new("400px = width = 800px".chars());
+        assert!(parser.parse::<SizeFeature>().is_err());
+    }
+
+    #[test]
+    fn range_with_space_in_comparison() {
+        let mut parser = Parser::new("(width < = 400px)".chars());
+        assert!(parser.parse::<ContainerCondition>().is_err());
+    }
+
+    #[test]
+    fn and_not() {
+        let mut parser = Parser::new("(width > 400px) and (not (height < 800px))".chars());
+        let condition: ContainerCondition = parser.parse().unwrap();
+        assert!(matches!(
+            condition,
+            ContainerCondition::And(_, ref right) if matches!(**right, ContainerCondition::Not(_))
+        ));
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut parser = Parser::new(
+            "(400px<=width<800px) or ((max-inline-size:60em) and (block-size>0))".chars(),
+        );
+        let condition: ContainerCondition = parser.parse().unwrap();
+        assert_eq!(
+            "(400px <= width < 800px) or ((max-inline-size: 60em) and (block-size > 0))",
+            condition.to_string()
+        );
+        assert_eq!(
+            "(400px<=width<800px) or ((max-inline-size:60em) and (block-size>0))",
+            crate::printer::minify(&condition)
+        );
+    }
+}
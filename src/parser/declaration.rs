@@ -1,164 +1,447 @@
+pub mod animation;
+pub mod container;
+pub mod css_wide_keyword;
 pub mod display;
 pub mod position;
 pub mod text_align;
 mod vec;
 
+use std::ops::Deref;
+
+pub(crate) use vec::parse_declaration_list;
+
 use super::{
     color::{parse_num, Color},
+    component_value::ComponentValue,
     font_family::FontName,
     length_or_percentage::LengthOrPercentage,
     side::Sides,
+    time::Time,
     *,
 };
 use crate::tokenizer::*;
+use animation::{
+    AnimationDirection, AnimationFillMode, AnimationName, AnimationPlayState, EasingFunction,
+    IterationCount,
+};
+use container::{ContainerName, ContainerType};
+use css_wide_keyword::CssWideOr;
 use display::Display;
 use position::Position;
 use text_align::TextAlign;
-pub use vec::*;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum Declaration {
-    BackgroundColor(Color),
-    BorderColor(Sides<Color>),
-    Opacity(f64),
-    FontFamily(Vec<FontName>),
-    FontSize(LengthOrPercentage),
-    MinHeight(LengthOrPercentage),
-    Height(LengthOrPercentage),
-    MaxHeight(LengthOrPercentage),
-    MinWidth(LengthOrPercentage),
-    Width(LengthOrPercentage),
-    MaxWidth(LengthOrPercentage),
-    Margin(Sides<LengthOrPercentage>),
-    Padding(Sides<LengthOrPercentage>),
-    BorderWidth(Sides<LengthOrPercentage>),
-    BorderRadius(Sides<LengthOrPercentage>),
-    TextAlign(TextAlign),
-    Color(Color),
-    Display(Display),
-    Position(Position),
-    Top(LengthOrPercentage),
-    Bottom(LengthOrPercentage),
-    Left(LengthOrPercentage),
-    Right(LengthOrPercentage),
+    BackgroundColor(CssWideOr<Color>),
+    BorderColor(CssWideOr<Sides<Color>>),
+    Opacity(CssWideOr<f64>),
+    FontFamily(CssWideOr<Vec<FontName>>),
+    FontSize(CssWideOr<LengthOrPercentage>),
+    MinHeight(CssWideOr<LengthOrPercentage>),
+    Height(CssWideOr<LengthOrPercentage>),
+    MaxHeight(CssWideOr<LengthOrPercentage>),
+    MinWidth(CssWideOr<LengthOrPercentage>),
+    Width(CssWideOr<LengthOrPercentage>),
+    MaxWidth(CssWideOr<LengthOrPercentage>),
+    Margin(CssWideOr<Sides<LengthOrPercentage>>),
+    Padding(CssWideOr<Sides<LengthOrPercentage>>),
+    BorderWidth(CssWideOr<Sides<LengthOrPercentage>>),
+    BorderRadius(CssWideOr<Sides<LengthOrPercentage>>),
+    TextAlign(CssWideOr<TextAlign>),
+    Color(CssWideOr<Color>),
+    Display(CssWideOr<Display>),
+    Position(CssWideOr<Position>),
+    Top(CssWideOr<LengthOrPercentage>),
+    Bottom(CssWideOr<LengthOrPercentage>),
+    Left(CssWideOr<LengthOrPercentage>),
+    Right(CssWideOr<LengthOrPercentage>),
+    AnimationName(CssWideOr<Vec<AnimationName>>),
+    AnimationDuration(CssWideOr<Vec<Time>>),
+    AnimationTimingFunction(CssWideOr<Vec<EasingFunction>>),
+    AnimationDelay(CssWideOr<Vec<Time>>),
+    AnimationIterationCount(CssWideOr<Vec<IterationCount>>),
+    AnimationDirection(CssWideOr<Vec<AnimationDirection>>),
+    AnimationFillMode(CssWideOr<Vec<AnimationFillMode>>),
+    AnimationPlayState(CssWideOr<Vec<AnimationPlayState>>),
+    ContainerType(CssWideOr<ContainerType>),
+    ContainerName(CssWideOr<ContainerName>),
+    /// a custom property like `--brand-color: #f00`, its value is kept as it was written
+    Custom {
+        name: String,
+        value: Vec<ComponentValue>,
+    },
+    /// a property whose value uses `var()`, so it is kept as it was written
+    WithVariables {
+        name: String,
+        value: Vec<ComponentValue>,
+    },
+    /// a property this parser does not know like `transform: rotate(45deg)`, its value is kept as it was written
+    ///
+    /// a strict parser returns an error for these instead
+    Unknown {
+        name: String,
+        value: Vec<ComponentValue>,
+    },
 }
 
 impl Parsable for Declaration {
     fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
-        match parser.tokens.next() {
-            Some(token_at) => match &token_at.token {
-                Token::Identifier(key) => match key.as_str() {
-                    "background-color" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::BackgroundColor(parser.parse()?))
-                    }
-                    "border-color" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::BorderColor(parser.parse()?))
-                    }
-                    "opacity" => {
-                        parser.consume_colon_separator()?;
-                        let opacity = parse_num(parser, 0.0, 1.0)?;
-                        Ok(Declaration::Opacity(opacity))
-                    }
-                    "font-family" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::FontFamily(parser.parse()?))
-                    }
-                    "font-size" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::FontSize(parser.parse()?))
-                    }
-                    "min-height" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::MinHeight(parser.parse()?))
-                    }
-                    "height" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Height(parser.parse()?))
-                    }
-                    "max-height" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::MaxHeight(parser.parse()?))
-                    }
-                    "min-width" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::MinWidth(parser.parse()?))
-                    }
-                    "width" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Width(parser.parse()?))
-                    }
-                    "max-width" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::MaxWidth(parser.parse()?))
-                    }
-                    "margin" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Margin(parser.parse()?))
-                    }
-                    "padding" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Padding(parser.parse()?))
-                    }
-                    "border-width" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::BorderWidth(parser.parse()?))
-                    }
-                    "border-radius" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::BorderRadius(parser.parse()?))
-                    }
-                    "text-align" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::TextAlign(parser.parse()?))
-                    }
-                    "color" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Color(parser.parse()?))
-                    }
-                    "display" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Display(parser.parse()?))
-                    }
-                    "position" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Position(parser.parse()?))
-                    }
-                    "top" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Top(parser.parse()?))
-                    }
-                    "bottom" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Bottom(parser.parse()?))
-                    }
-                    "left" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Left(parser.parse()?))
-                    }
-                    "right" => {
-                        parser.consume_colon_separator()?;
-                        Ok(Declaration::Right(parser.parse()?))
-                    }
+        let token_at = match parser.tokens.next() {
+            Some(token_at) => token_at,
+            None => return Err(ParsingError::end_of_file("a valid property name")),
+        };
+
+        let name = match &token_at.token {
+            Token::Identifier(name) => name,
+            _ => return Err(ParsingError::wrong_token(token_at, "a valid property name")),
+        };
+
+        // custom properties can hold any value, so it is kept as it was written
+        if name.starts_with("--") {
+            let name = name.clone();
+            parser.consume_colon_separator()?;
+            return Ok(Declaration::Custom {
+                name,
+                value: parser.parse_component_values()?,
+            });
+        }
 
-                    _ => Err(ParsingError::wrong_token(token_at, "a valid property name")),
-                },
-                _ => Err(ParsingError::wrong_token(token_at, "a valid property name")),
+        let parse_value: fn(&mut Parser<I>) -> Result<Declaration, ParsingError> = match name
+            .as_str()
+        {
+            "background-color" => |parser| Ok(Declaration::BackgroundColor(parser.parse()?)),
+            "border-color" => |parser| Ok(Declaration::BorderColor(parser.parse()?)),
+            "opacity" => |parser| {
+                Ok(Declaration::Opacity(CssWideOr::parse_with(
+                    parser,
+                    |parser| parse_num(parser, 0.0, 1.0),
+                )?))
             },
-            _ => Err(ParsingError::end_of_file("a valid property name")),
+            "font-family" => |parser| Ok(Declaration::FontFamily(parser.parse()?)),
+            "font-size" => |parser| Ok(Declaration::FontSize(parser.parse()?)),
+            "min-height" => |parser| Ok(Declaration::MinHeight(parser.parse()?)),
+            "height" => |parser| Ok(Declaration::Height(parser.parse()?)),
+            "max-height" => |parser| Ok(Declaration::MaxHeight(parser.parse()?)),
+            "min-width" => |parser| Ok(Declaration::MinWidth(parser.parse()?)),
+            "width" => |parser| Ok(Declaration::Width(parser.parse()?)),
+            "max-width" => |parser| Ok(Declaration::MaxWidth(parser.parse()?)),
+            "margin" => |parser| Ok(Declaration::Margin(parser.parse()?)),
+            "padding" => |parser| Ok(Declaration::Padding(parser.parse()?)),
+            "border-width" => |parser| Ok(Declaration::BorderWidth(parser.parse()?)),
+            "border-radius" => |parser| Ok(Declaration::BorderRadius(parser.parse()?)),
+            "text-align" => |parser| Ok(Declaration::TextAlign(parser.parse()?)),
+            "color" => |parser| Ok(Declaration::Color(parser.parse()?)),
+            "display" => |parser| Ok(Declaration::Display(parser.parse()?)),
+            "position" => |parser| Ok(Declaration::Position(parser.parse()?)),
+            "top" => |parser| Ok(Declaration::Top(parser.parse()?)),
+            "bottom" => |parser| Ok(Declaration::Bottom(parser.parse()?)),
+            "left" => |parser| Ok(Declaration::Left(parser.parse()?)),
+            "right" => |parser| Ok(Declaration::Right(parser.parse()?)),
+            "animation-name" => |parser| Ok(Declaration::AnimationName(parser.parse()?)),
+            "animation-duration" => |parser| {
+                Ok(Declaration::AnimationDuration(CssWideOr::parse_with(
+                    parser,
+                    animation::parse_durations,
+                )?))
+            },
+            "animation-timing-function" => {
+                |parser| Ok(Declaration::AnimationTimingFunction(parser.parse()?))
+            }
+            "animation-delay" => |parser| Ok(Declaration::AnimationDelay(parser.parse()?)),
+            "animation-iteration-count" => {
+                |parser| Ok(Declaration::AnimationIterationCount(parser.parse()?))
+            }
+            "animation-direction" => |parser| Ok(Declaration::AnimationDirection(parser.parse()?)),
+            "animation-fill-mode" => |parser| Ok(Declaration::AnimationFillMode(parser.parse()?)),
+            "animation-play-state" => |parser| Ok(Declaration::AnimationPlayState(parser.parse()?)),
+            "container-type" => |parser| Ok(Declaration::ContainerType(parser.parse()?)),
+            "container-name" => |parser| Ok(Declaration::ContainerName(parser.parse()?)),
+
+            _ if parser.is_strict => {
+                return Err(ParsingError::wrong_token(token_at, "a valid property name"))
+            }
+            _ => {
+                let name = name.clone();
+                parser.consume_colon_separator()?;
+                return Ok(Declaration::Unknown {
+                    name,
+                    value: parser.parse_unknown_value()?,
+                });
+            }
+        };
+
+        parser.consume_colon_separator()?;
+
+        // the value can only be checked once the variables are known
+        if parser.is_value_with_variables() {
+            return Ok(Declaration::WithVariables {
+                name: name.clone(),
+                value: parser.parse_component_values()?,
+            });
         }
+
+        parse_value(parser)
+    }
+}
+
+impl Declaration {
+    /// the name of the property this declaration sets
+    pub fn property_name(&self) -> &str {
+        match self {
+            Declaration::BackgroundColor(_) => "background-color",
+            Declaration::BorderColor(_) => "border-color",
+            Declaration::Opacity(_) => "opacity",
+            Declaration::FontFamily(_) => "font-family",
+            Declaration::FontSize(_) => "font-size",
+            Declaration::MinHeight(_) => "min-height",
+            Declaration::Height(_) => "height",
+            Declaration::MaxHeight(_) => "max-height",
+            Declaration::MinWidth(_) => "min-width",
+            Declaration::Width(_) => "width",
+            Declaration::MaxWidth(_) => "max-width",
+            Declaration::Margin(_) => "margin",
+            Declaration::Padding(_) => "padding",
+            Declaration::BorderWidth(_) => "border-width",
+            Declaration::BorderRadius(_) => "border-radius",
+            Declaration::TextAlign(_) => "text-align",
+            Declaration::Color(_) => "color",
+            Declaration::Display(_) => "display",
+            Declaration::Position(_) => "position",
+            Declaration::Top(_) => "top",
+            Declaration::Bottom(_) => "bottom",
+            Declaration::Left(_) => "left",
+            Declaration::Right(_) => "right",
+            Declaration::AnimationName(_) => "animation-name",
+            Declaration::AnimationDuration(_) => "animation-duration",
+            Declaration::AnimationTimingFunction(_) => "animation-timing-function",
+            Declaration::AnimationDelay(_) => "animation-delay",
+            Declaration::AnimationIterationCount(_) => "animation-iteration-count",
+            Declaration::AnimationDirection(_) => "animation-direction",
+            Declaration::AnimationFillMode(_) => "animation-fill-mode",
+            Declaration::AnimationPlayState(_) => "animation-play-state",
+            Declaration::ContainerType(_) => "container-type",
+            Declaration::ContainerName(_) => "container-name",
+            Declaration::Custom { name, .. }
+            | Declaration::WithVariables { name, .. }
+            | Declaration::Unknown { name, .. } => name,
+        }
+    }
+}
+
+impl ToCss for Declaration {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.print_identifier(self.property_name())?;
+        printer.write_char(':')?;
+        printer.print_optional_space()?;
+
+        match self {
+            Declaration::BackgroundColor(value) => printer.print(value),
+            Declaration::BorderColor(value) => printer.print(value),
+            Declaration::Opacity(value) => {
+                value.print_with(printer, |printer, value| write!(printer, "{value}"))
+            }
+            Declaration::FontFamily(font_names) => font_names
+                .print_with(printer, |printer, values| {
+                    printer.print_comma_separated(values)
+                }),
+            Declaration::FontSize(value) => printer.print(value),
+            Declaration::MinHeight(value) => printer.print(value),
+            Declaration::Height(value) => printer.print(value),
+            Declaration::MaxHeight(value) => printer.print(value),
+            Declaration::MinWidth(value) => printer.print(value),
+            Declaration::Width(value) => printer.print(value),
+            Declaration::MaxWidth(value) => printer.print(value),
+            Declaration::Margin(value) => printer.print(value),
+            Declaration::Padding(value) => printer.print(value),
+            Declaration::BorderWidth(value) => printer.print(value),
+            Declaration::BorderRadius(value) => printer.print(value),
+            Declaration::TextAlign(value) => printer.print(value),
+            Declaration::Color(value) => printer.print(value),
+            Declaration::Display(value) => printer.print(value),
+            Declaration::Position(value) => printer.print(value),
+            Declaration::Top(value) => printer.print(value),
+            Declaration::Bottom(value) => printer.print(value),
+            Declaration::Left(value) => printer.print(value),
+            Declaration::Right(value) => printer.print(value),
+            Declaration::AnimationName(names) => names.print_with(printer, |printer, values| {
+                printer.print_comma_separated(values)
+            }),
+            Declaration::AnimationDuration(times) => times
+                .print_with(printer, |printer, values| {
+                    printer.print_comma_separated(values)
+                }),
+            Declaration::AnimationTimingFunction(easing_functions) => easing_functions
+                .print_with(printer, |printer, values| {
+                    printer.print_comma_separated(values)
+                }),
+            Declaration::AnimationDelay(times) => times.print_with(printer, |printer, values| {
+                printer.print_comma_separated(values)
+            }),
+            Declaration::AnimationIterationCount(counts) => counts
+                .print_with(printer, |printer, values| {
+                    printer.print_comma_separated(values)
+                }),
+            Declaration::AnimationDirection(directions) => directions
+                .print_with(printer, |printer, values| {
+                    printer.print_comma_separated(values)
+                }),
+            Declaration::AnimationFillMode(fill_modes) => fill_modes
+                .print_with(printer, |printer, values| {
+                    printer.print_comma_separated(values)
+                }),
+            Declaration::AnimationPlayState(play_states) => play_states
+                .print_with(printer, |printer, values| {
+                    printer.print_comma_separated(values)
+                }),
+            Declaration::ContainerType(value) => printer.print(value),
+            Declaration::ContainerName(value) => printer.print(value),
+            Declaration::Custom { value, .. }
+            | Declaration::WithVariables { value, .. }
+            | Declaration::Unknown { value, .. } => printer.print_component_values(value),
+        }
+    }
+}
+
+impl fmt::Display for Declaration {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+/// A declaration in a block along with whether it is marked `!important`
+/// Grammar: `<declaration> | <declaration> "!important"`
+///
+/// this is separate from `Declaration` so that every property can be important without a flag in each variant
+#[derive(Debug, PartialEq, Serialize)]
+pub struct DeclarationItem {
+    pub declaration: Declaration,
+    pub is_important: bool,
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// parse the value of a declaration that is not supported, it is kept as it was written but can not be empty
+    pub(crate) fn parse_unknown_value(&mut self) -> Result<Vec<ComponentValue>, ParsingError> {
+        let value = self.parse_component_values()?;
+
+        if value.is_empty() {
+            return match self.tokens.peek() {
+                Some(token_at) => Err(ParsingError::wrong_token(token_at.clone(), "a value")),
+                None => Err(ParsingError::end_of_file("a value")),
+            };
+        }
+
+        Ok(value)
+    }
+
+    /// whether the next tokens are `!important`, there can be whitespace after the `!`
+    ///
+    /// adapted from https://www.w3.org/TR/css-syntax-3/#consume-declaration
+    pub(crate) fn is_important_next(&mut self) -> bool {
+        let Some(TokenAt {
+            token: Token::Delimiter('!'),
+            ..
+        }) = self.tokens.peek()
+        else {
+            return false;
+        };
+
+        let n = match self.tokens.peek_nth(1) {
+            Some(TokenAt {
+                token: Token::Whitespace(),
+                ..
+            }) => 2,
+            _ => 1,
+        };
+
+        matches!(
+            self.tokens.peek_nth(n),
+            Some(TokenAt { token: Token::Identifier(name), .. })
+                if name.eq_ignore_ascii_case("important")
+        )
+    }
+
+    /// consume `!important` if it is next, returns whether it was there
+    pub(crate) fn parse_important(&mut self) -> bool {
+        let is_important = self.is_important_next();
+
+        if is_important {
+            self.tokens.next();
+            self.optional_whitespace();
+            self.tokens.next();
+        }
+
+        is_important
+    }
+}
+
+impl Parsable for DeclarationItem {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let declaration = parser.parse()?;
+        parser.optional_whitespace();
+
+        Ok(DeclarationItem {
+            declaration,
+            is_important: parser.parse_important(),
+        })
+    }
+}
+
+/// a declaration that is not important
+impl From<Declaration> for DeclarationItem {
+    fn from(declaration: Declaration) -> Self {
+        Self {
+            declaration,
+            is_important: false,
+        }
+    }
+}
+
+/// wraps a declaration that is not important with `Span::EMPTY`
+impl From<Declaration> for Spanned<DeclarationItem> {
+    fn from(declaration: Declaration) -> Self {
+        Spanned::from(DeclarationItem::from(declaration))
+    }
+}
+
+impl Deref for DeclarationItem {
+    type Target = Declaration;
+
+    fn deref(&self) -> &Declaration {
+        &self.declaration
+    }
+}
+
+impl ToCss for DeclarationItem {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.print(&self.declaration)?;
+
+        if self.is_important {
+            printer.print_optional_space()?;
+            printer.write_str("!important")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for DeclarationItem {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{length::Length, length::LengthUnit, percentage::Percentage, *};
+    use css_wide_keyword::CssWideKeyword;
 
     #[test]
     fn background_color() {
         let mut parser = Parser::new("background-color: red".chars());
-        assert_eq!(Ok(Declaration::BackgroundColor(Color::Red)), parser.parse());
+        assert_eq!(
+            Ok(Declaration::BackgroundColor(Color::Red.into())),
+            parser.parse()
+        );
         assert_eq!(None, parser.tokens.next());
     }
 
@@ -172,7 +455,7 @@ mod tests {
     fn border_color() {
         let mut parser = Parser::new("border-color: red".chars());
         assert_eq!(
-            Ok(Declaration::BorderColor(Sides::Single(Color::Red))),
+            Ok(Declaration::BorderColor(Sides::Single(Color::Red).into())),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -188,17 +471,20 @@ mod tests {
     fn quad_border_color() {
         let mut parser = Parser::new("border-color: red green blue rgb(10, 20, 30)".chars());
         assert_eq!(
-            Ok(Declaration::BorderColor(Sides::Quad(
-                Color::Red,
-                Color::Green,
-                Color::Blue,
-                Color::Rgb {
-                    r: 10.0,
-                    g: 20.0,
-                    b: 30.0,
-                    a: 1.0
-                }
-            ))),
+            Ok(Declaration::BorderColor(
+                Sides::Quad(
+                    Color::Red,
+                    Color::Green,
+                    Color::Blue,
+                    Color::Rgb {
+                        r: 10.0,
+                        g: 20.0,
+                        b: 30.0,
+                        a: 1.0
+                    }
+                )
+                .into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -207,7 +493,7 @@ mod tests {
     #[test]
     fn opacity() {
         let mut parser = Parser::new("opacity: 0.3".chars());
-        assert_eq!(Ok(Declaration::Opacity(0.3)), parser.parse());
+        assert_eq!(Ok(Declaration::Opacity(0.3.into())), parser.parse());
         assert_eq!(None, parser.tokens.next());
     }
 
@@ -221,17 +507,30 @@ mod tests {
     fn font_family() {
         let mut parser = Parser::new("font-family: Arial".chars());
         assert_eq!(
-            Ok(Declaration::FontFamily(vec![FontName("Arial".to_owned())])),
+            Ok(Declaration::FontFamily(
+                vec![FontName::Family("Arial".to_owned())].into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
     }
 
+    #[test]
+    fn quoted_font_family_keyword() {
+        let mut parser = Parser::new("font-family: \"inherit\"".chars());
+        let declaration: Declaration = parser.parse().unwrap();
+        assert_eq!(
+            Declaration::FontFamily(vec![FontName::Family("inherit".to_owned())].into()),
+            declaration
+        );
+        assert_eq!("font-family: \"inherit\"", declaration.to_string());
+    }
+
     #[test]
     fn text_align() {
         let mut parser = Parser::new("text-align: center".chars());
         assert_eq!(
-            Ok(Declaration::TextAlign(TextAlign::Center)),
+            Ok(Declaration::TextAlign(TextAlign::Center.into())),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -241,9 +540,9 @@ mod tests {
     fn font_size() {
         let mut parser = Parser::new("font-size: 3px".chars());
         assert_eq!(
-            Ok(Declaration::FontSize(LengthOrPercentage::Length(
-                Length::Length(3.0, LengthUnit::Pixels)
-            ))),
+            Ok(Declaration::FontSize(
+                LengthOrPercentage::Length(Length::Length(3.0, LengthUnit::Pixels)).into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -253,9 +552,9 @@ mod tests {
     fn height() {
         let mut parser = Parser::new("height: 3px".chars());
         assert_eq!(
-            Ok(Declaration::Height(LengthOrPercentage::Length(
-                Length::Length(3.0, LengthUnit::Pixels)
-            ))),
+            Ok(Declaration::Height(
+                LengthOrPercentage::Length(Length::Length(3.0, LengthUnit::Pixels)).into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -265,21 +564,37 @@ mod tests {
     fn width() {
         let mut parser = Parser::new("width: 3px".chars());
         assert_eq!(
-            Ok(Declaration::Width(LengthOrPercentage::Length(
-                Length::Length(3.0, LengthUnit::Pixels)
-            ))),
+            Ok(Declaration::Width(
+                LengthOrPercentage::Length(Length::Length(3.0, LengthUnit::Pixels)).into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
     }
 
+    #[test]
+    fn width_calc() {
+        let mut parser = Parser::new("width: calc(100% - 2 * 8px)".chars());
+        let declaration: Declaration = parser.parse().unwrap();
+        assert!(matches!(
+            declaration,
+            Declaration::Width(CssWideOr::Value(LengthOrPercentage::MathFunction(_)))
+        ));
+        assert_eq!("width: calc(100% - 2 * 8px)", declaration.to_string());
+        assert_eq!(None, parser.tokens.next());
+    }
+
     #[test]
     fn margin() {
         let mut parser = Parser::new("margin: 3px".chars());
         assert_eq!(
-            Ok(Declaration::Margin(Sides::Single(
-                LengthOrPercentage::Length(Length::Length(3.0, LengthUnit::Pixels))
-            ))),
+            Ok(Declaration::Margin(
+                Sides::Single(LengthOrPercentage::Length(Length::Length(
+                    3.0,
+                    LengthUnit::Pixels
+                )))
+                .into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -289,12 +604,15 @@ mod tests {
     fn quad_margin() {
         let mut parser = Parser::new("margin: 3px 20% 700rem -100pt".chars());
         assert_eq!(
-            Ok(Declaration::Margin(Sides::Quad(
-                LengthOrPercentage::Length(Length::Length(3.0, LengthUnit::Pixels)),
-                LengthOrPercentage::Percentage(Percentage(20.0)),
-                LengthOrPercentage::Length(Length::Length(700.0, LengthUnit::RootFontSize)),
-                LengthOrPercentage::Length(Length::Length(-100.0, LengthUnit::Points)),
-            ))),
+            Ok(Declaration::Margin(
+                Sides::Quad(
+                    LengthOrPercentage::Length(Length::Length(3.0, LengthUnit::Pixels)),
+                    LengthOrPercentage::Percentage(Percentage(20.0)),
+                    LengthOrPercentage::Length(Length::Length(700.0, LengthUnit::RootFontSize)),
+                    LengthOrPercentage::Length(Length::Length(-100.0, LengthUnit::Points)),
+                )
+                .into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -304,9 +622,13 @@ mod tests {
     fn padding() {
         let mut parser = Parser::new("padding: 3px".chars());
         assert_eq!(
-            Ok(Declaration::Padding(Sides::Single(
-                LengthOrPercentage::Length(Length::Length(3.0, LengthUnit::Pixels))
-            ))),
+            Ok(Declaration::Padding(
+                Sides::Single(LengthOrPercentage::Length(Length::Length(
+                    3.0,
+                    LengthUnit::Pixels
+                )))
+                .into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -316,9 +638,13 @@ mod tests {
     fn border_width() {
         let mut parser = Parser::new("border-width: 3px".chars());
         assert_eq!(
-            Ok(Declaration::BorderWidth(Sides::Single(
-                LengthOrPercentage::Length(Length::Length(3.0, LengthUnit::Pixels))
-            ))),
+            Ok(Declaration::BorderWidth(
+                Sides::Single(LengthOrPercentage::Length(Length::Length(
+                    3.0,
+                    LengthUnit::Pixels
+                )))
+                .into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -328,9 +654,13 @@ mod tests {
     fn border_radius() {
         let mut parser = Parser::new("border-radius: 3px".chars());
         assert_eq!(
-            Ok(Declaration::BorderRadius(Sides::Single(
-                LengthOrPercentage::Length(Length::Length(3.0, LengthUnit::Pixels))
-            ))),
+            Ok(Declaration::BorderRadius(
+                Sides::Single(LengthOrPercentage::Length(Length::Length(
+                    3.0,
+                    LengthUnit::Pixels
+                )))
+                .into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -339,7 +669,10 @@ mod tests {
     #[test]
     fn display() {
         let mut parser = Parser::new("display: block".chars());
-        assert_eq!(Ok(Declaration::Display(Display::Block)), parser.parse());
+        assert_eq!(
+            Ok(Declaration::Display(Display::Block.into())),
+            parser.parse()
+        );
         assert_eq!(None, parser.tokens.next());
     }
 
@@ -347,7 +680,7 @@ mod tests {
     fn position() {
         let mut parser = Parser::new("position: absolute".chars());
         assert_eq!(
-            Ok(Declaration::Position(Position::Absolute)),
+            Ok(Declaration::Position(Position::Absolute.into())),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -357,7 +690,9 @@ mod tests {
     fn top() {
         let mut parser = Parser::new("top: 0".chars());
         assert_eq!(
-            Ok(Declaration::Top(LengthOrPercentage::Length(Length::Zero()))),
+            Ok(Declaration::Top(
+                LengthOrPercentage::Length(Length::Zero()).into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -367,9 +702,9 @@ mod tests {
     fn bottom() {
         let mut parser = Parser::new("bottom: 0".chars());
         assert_eq!(
-            Ok(Declaration::Bottom(LengthOrPercentage::Length(
-                Length::Zero()
-            ))),
+            Ok(Declaration::Bottom(
+                LengthOrPercentage::Length(Length::Zero()).into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -380,7 +715,7 @@ mod tests {
         let mut parser = Parser::new("left: 0".chars());
         assert_eq!(
             Ok(Declaration::Left(
-                LengthOrPercentage::Length(Length::Zero())
+                LengthOrPercentage::Length(Length::Zero()).into()
             )),
             parser.parse()
         );
@@ -391,9 +726,9 @@ mod tests {
     fn right() {
         let mut parser = Parser::new("right: 0".chars());
         assert_eq!(
-            Ok(Declaration::Right(LengthOrPercentage::Length(
-                Length::Zero()
-            ))),
+            Ok(Declaration::Right(
+                LengthOrPercentage::Length(Length::Zero()).into()
+            )),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -401,14 +736,14 @@ mod tests {
 
     #[test]
     fn bad_declaration() {
-        let mut parser = Parser::new("band-color: red".chars());
+        let mut parser = Parser::new("band-color: red".chars()).strict();
         assert!(parser.parse::<Declaration>().is_err());
     }
 
     #[test]
     fn color() {
         let mut parser = Parser::new("color: red".chars());
-        assert_eq!(Ok(Declaration::Color(Color::Red)), parser.parse());
+        assert_eq!(Ok(Declaration::Color(Color::Red.into())), parser.parse());
         assert_eq!(None, parser.tokens.next());
     }
 
@@ -417,4 +752,319 @@ mod tests {
         let mut parser = Parser::new("color: awrrvads".chars());
         assert!(parser.parse::<Declaration>().is_err());
     }
+
+    #[test]
+    fn serialize() {
+        let mut parser = Parser::new("margin:0 10px".chars());
+        assert_eq!(
+            "margin: 0 10px",
+            parser.parse::<Declaration>().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn custom_property() {
+        let mut parser = Parser::new("--brand-color: #f00".chars());
+        assert_eq!(
+            Ok(Declaration::Custom {
+                name: "--brand-color".to_owned(),
+                value: vec![ComponentValue::Token(Token::Hash(
+                    "f00".to_owned(),
+                    HashType::Id
+                ))],
+            }),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn custom_property_keeps_any_value() {
+        let mut parser = Parser::new("--spacing: { a: b } [1, 2]  ;".chars());
+        let declaration: Declaration = parser.parse().unwrap();
+        assert_eq!("--spacing: { a: b } [1, 2]", declaration.to_string());
+        assert_eq!(
+            Some(Token::Semicolon()),
+            parser.tokens.next().map(|t| t.token)
+        );
+    }
+
+    #[test]
+    fn empty_custom_property() {
+        let mut parser = Parser::new("--empty:;".chars());
+        assert_eq!(
+            Ok(Declaration::Custom {
+                name: "--empty".to_owned(),
+                value: vec![],
+            }),
+            parser.parse()
+        );
+    }
+
+    #[test]
+    fn variable() {
+        let mut parser = Parser::new("color: var(--brand-color, red)".chars());
+        let declaration: Declaration = parser.parse().unwrap();
+        assert!(matches!(
+            &declaration,
+            Declaration::WithVariables { name, value }
+                if name == "color" && matches!(value.as_slice(), [ComponentValue::Variable(_)])
+        ));
+        assert_eq!("color: var(--brand-color, red)", declaration.to_string());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn variable_in_part_of_value() {
+        let mut parser = Parser::new("margin: 0 calc(var(--gap) * 2);".chars());
+        let declaration: Declaration = parser.parse().unwrap();
+        assert_eq!("margin", declaration.property_name());
+        assert_eq!("margin: 0 calc(var(--gap) * 2)", declaration.to_string());
+    }
+
+    #[test]
+    fn variable_in_unknown_property() {
+        let mut parser = Parser::new("colour: var(--brand-color)".chars());
+        assert!(matches!(
+            parser.parse(),
+            Ok(Declaration::Unknown { name, .. }) if name == "colour"
+        ));
+
+        let mut parser = Parser::new("colour: var(--brand-color)".chars()).strict();
+        assert!(parser.parse::<Declaration>().is_err());
+    }
+
+    #[test]
+    fn unknown_property() {
+        let mut parser = Parser::new("transform : rotate(45deg) scale(2) ;".chars());
+        let declaration: Declaration = parser.parse().unwrap();
+        assert!(matches!(
+            &declaration,
+            Declaration::Unknown { name, value } if name == "transform" && value.len() == 3
+        ));
+        assert_eq!("transform: rotate(45deg) scale(2)", declaration.to_string());
+        assert_eq!(
+            Some(Token::Semicolon()),
+            parser.tokens.next().map(|token_at| token_at.token)
+        );
+    }
+
+    #[test]
+    fn unknown_property_without_value() {
+        let mut parser = Parser::new("z-index: ;".chars());
+        assert!(parser.parse::<Declaration>().is_err());
+    }
+
+    #[test]
+    fn unknown_property_in_strict_mode() {
+        let mut parser = Parser::new("z-index: 1".chars()).strict();
+        assert!(parser.parse::<Declaration>().is_err());
+    }
+
+    #[test]
+    fn animation_name() {
+        let mut parser = Parser::new("animation-name: fade, spin".chars());
+        assert_eq!(
+            Ok(Declaration::AnimationName(
+                vec![
+                    AnimationName::Keyframes("fade".to_owned()),
+                    AnimationName::Keyframes("spin".to_owned())
+                ]
+                .into()
+            )),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn quoted_animation_name_keyword() {
+        let mut parser = Parser::new("animation-name: \"initial\", \"none\", fade".chars());
+        let declaration: Declaration = parser.parse().unwrap();
+        assert_eq!(
+            Declaration::AnimationName(
+                vec![
+                    AnimationName::Keyframes("initial".to_owned()),
+                    AnimationName::Keyframes("none".to_owned()),
+                    AnimationName::Keyframes("fade".to_owned()),
+                ]
+                .into()
+            ),
+            declaration
+        );
+        assert_eq!(
+            "animation-name: \"initial\", \"none\", fade",
+            declaration.to_string()
+        );
+    }
+
+    #[test]
+    fn animation_duration() {
+        let mut parser = Parser::new("animation-duration: 1s, 250ms".chars());
+        assert_eq!(
+            Ok(Declaration::AnimationDuration(
+                vec![Time::Seconds(1.0), Time::Milliseconds(250.0)].into()
+            )),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn negative_animation_duration() {
+        let mut parser = Parser::new("animation-duration: -1s".chars());
+        assert!(parser.parse::<Declaration>().is_err());
+
+        let mut parser = Parser::new("animation-delay: -1s".chars());
+        assert_eq!(
+            Ok(Declaration::AnimationDelay(
+                vec![Time::Seconds(-1.0)].into()
+            )),
+            parser.parse()
+        );
+    }
+
+    #[test]
+    fn animation_timing_function() {
+        let mut parser = Parser::new("animation-timing-function: steps(3, end)".chars());
+        let declaration: Declaration = parser.parse().unwrap();
+        assert_eq!(
+            "animation-timing-function: steps(3, end)",
+            declaration.to_string()
+        );
+    }
+
+    #[test]
+    fn container_type() {
+        let mut parser = Parser::new("container-type: inline-size".chars());
+        assert_eq!(
+            Ok(Declaration::ContainerType(ContainerType::InlineSize.into())),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn container_name() {
+        let mut parser = Parser::new("container-name: sidebar card".chars());
+        let declaration: Declaration = parser.parse().unwrap();
+        assert_eq!("container-name: sidebar card", declaration.to_string());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn important() {
+        let mut parser = Parser::new("color: red !important".chars());
+        assert_eq!(
+            Ok(DeclarationItem {
+                declaration: Declaration::Color(Color::Red.into()),
+                is_important: true,
+            }),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn important_with_whitespace() {
+        let mut parser = Parser::new("margin: 0 1px ! IMPORTANT".chars());
+        let item: DeclarationItem = parser.parse().unwrap();
+        assert!(item.is_important);
+        assert_eq!("margin: 0 1px !important", item.to_string());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn not_important() {
+        let mut parser = Parser::new("opacity: 1".chars());
+        assert_eq!(
+            Ok(DeclarationItem::from(Declaration::Opacity(1.0.into()))),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn important_raw_values() {
+        for input in [
+            "--gap: 1px !important",
+            "width: var(--width) !important",
+            "z-index: 1 !important",
+        ] {
+            let mut parser = Parser::new(input.chars());
+            let item: DeclarationItem = parser.parse().unwrap();
+            assert!(item.is_important);
+            assert_eq!(input, item.to_string());
+            assert_eq!(None, parser.tokens.next());
+        }
+    }
+
+    #[test]
+    fn minified_important() {
+        let mut parser = Parser::new("color: red !important".chars());
+        let item: DeclarationItem = parser.parse().unwrap();
+        assert_eq!("color:red!important", crate::printer::minify(&item));
+    }
+
+    #[test]
+    fn exclamation_mark_without_important() {
+        let mut parser = Parser::new("color: red !imp".chars());
+        assert_eq!(
+            Ok(DeclarationItem::from(Declaration::Color(Color::Red.into()))),
+            parser.parse()
+        );
+        assert_eq!(
+            Some(Token::Delimiter('!')),
+            parser.tokens.next().map(|token_at| token_at.token)
+        );
+    }
+
+    #[test]
+    fn css_wide_keywords() {
+        for (input, declaration) in [
+            (
+                "color: inherit",
+                Declaration::Color(CssWideOr::Keyword(CssWideKeyword::Inherit)),
+            ),
+            (
+                "margin: initial",
+                Declaration::Margin(CssWideOr::Keyword(CssWideKeyword::Initial)),
+            ),
+            (
+                "display: unset",
+                Declaration::Display(CssWideOr::Keyword(CssWideKeyword::Unset)),
+            ),
+            (
+                "font-family: revert",
+                Declaration::FontFamily(CssWideOr::Keyword(CssWideKeyword::Revert)),
+            ),
+            (
+                "opacity: revert-layer",
+                Declaration::Opacity(CssWideOr::Keyword(CssWideKeyword::RevertLayer)),
+            ),
+            (
+                "animation-duration: inherit",
+                Declaration::AnimationDuration(CssWideOr::Keyword(CssWideKeyword::Inherit)),
+            ),
+        ] {
+            let mut parser = Parser::new(input.chars());
+            assert_eq!(Ok(&declaration), parser.parse().as_ref());
+            assert_eq!(input, declaration.to_string());
+            assert_eq!(None, parser.tokens.next());
+        }
+    }
+
+    #[test]
+    fn important_css_wide_keyword() {
+        let mut parser = Parser::new("width: inherit !important".chars());
+        let item: DeclarationItem = parser.parse().unwrap();
+        assert!(item.is_important);
+        assert_eq!("width: inherit !important", item.to_string());
+    }
+
+    #[test]
+    fn css_wide_keyword_with_other_values() {
+        let mut parser = Parser::new("color: inherit red".chars());
+        assert!(parser.parse::<Declaration>().is_err());
+    }
 }
@@ -0,0 +1,552 @@
+use super::*;
+
+/// The keyframes an animation uses, or `none`
+///
+/// adapted from https://www.w3.org/TR/css-animations-1/#animation-name
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub enum AnimationName {
+    None,
+    /// the name of an `@keyframes` rule
+    Keyframes(String),
+}
+
+impl CommaSeparated for AnimationName {}
+
+impl Parsable for AnimationName {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        match parser.tokens.next() {
+            Some(token_at) => match token_at.token {
+                Token::Identifier(name) if name.eq_ignore_ascii_case("none") => {
+                    Ok(AnimationName::None)
+                }
+                Token::Identifier(name) | Token::String(name) => Ok(AnimationName::Keyframes(name)),
+                _ => Err(ParsingError::wrong_token(
+                    token_at,
+                    "none or a keyframes name",
+                )),
+            },
+            None => Err(ParsingError::end_of_file("none or a keyframes name")),
+        }
+    }
+}
+
+impl ToCss for AnimationName {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            AnimationName::None => printer.write_str("none"),
+            AnimationName::Keyframes(name) => printer.print_custom_identifier(name, &["none"]),
+        }
+    }
+}
+
+/// How the progress of an animation is spread out over its duration
+/// Grammar: `<easing-function>`
+///
+/// adapted from https://www.w3.org/TR/css-easing-1/#easing-functions
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+pub enum EasingFunction {
+    Linear,
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    StepStart,
+    StepEnd,
+    /// `cubic-bezier(x1, y1, x2, y2)` where the x values are between 0 and 1
+    CubicBezier(f64, f64, f64, f64),
+    /// `steps(count, position)`
+    Steps(u32, Option<StepPosition>),
+}
+
+/// When the jumps of `steps()` happen
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum StepPosition {
+    JumpStart,
+    JumpEnd,
+    JumpNone,
+    JumpBoth,
+    Start,
+    End,
+}
+
+impl CommaSeparated for EasingFunction {}
+
+impl Parsable for EasingFunction {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        const EXPECTED: &str = "an easing function";
+
+        let Some(token_at) = parser.tokens.next() else {
+            return Err(ParsingError::end_of_file(EXPECTED));
+        };
+
+        match &token_at.token {
+            Token::Identifier(name) => match name.as_str() {
+                "linear" => Ok(EasingFunction::Linear),
+                "ease" => Ok(EasingFunction::Ease),
+                "ease-in" => Ok(EasingFunction::EaseIn),
+                "ease-out" => Ok(EasingFunction::EaseOut),
+                "ease-in-out" => Ok(EasingFunction::EaseInOut),
+                "step-start" => Ok(EasingFunction::StepStart),
+                "step-end" => Ok(EasingFunction::StepEnd),
+                _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+            },
+            Token::Function(name) if name == "cubic-bezier" => {
+                parser.optional_whitespace();
+                let x1 = parse_num(parser, 0.0, 1.0)?;
+                parser.consume_comma_separator()?;
+                let y1 = parse_num(parser, f64::MIN, f64::MAX)?;
+                parser.consume_comma_separator()?;
+                let x2 = parse_num(parser, 0.0, 1.0)?;
+                parser.consume_comma_separator()?;
+                let y2 = parse_num(parser, f64::MIN, f64::MAX)?;
+                parser.optional_whitespace();
+                parser.expect(Token::CloseParenthesis())?;
+                Ok(EasingFunction::CubicBezier(x1, y1, x2, y2))
+            }
+            Token::Function(name) if name == "steps" => {
+                parser.optional_whitespace();
+                let steps_token = parser.tokens.peek().cloned();
+                let steps = parse_num(parser, 1.0, u32::MAX as f64)?;
+                if steps.fract() != 0.0 {
+                    let token_at = steps_token.expect("a number was parsed");
+                    return Err(ParsingError::wrong_token(
+                        token_at,
+                        "a whole number of steps",
+                    ));
+                }
+                parser.optional_whitespace();
+
+                let position = match parser.tokens.peek() {
+                    Some(TokenAt {
+                        token: Token::Comma(),
+                        ..
+                    }) => {
+                        parser.tokens.next();
+                        parser.optional_whitespace();
+                        Some(parser.parse()?)
+                    }
+                    _ => None,
+                };
+
+                parser.optional_whitespace();
+                parser.expect(Token::CloseParenthesis())?;
+                Ok(EasingFunction::Steps(steps as u32, position))
+            }
+            _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// consume a comma between function arguments along with the whitespace around it
+    fn consume_comma_separator(&mut self) -> Result<(), ParsingError> {
+        self.optional_whitespace();
+        self.expect(Token::Comma())?;
+        self.optional_whitespace();
+        Ok(())
+    }
+}
+
+impl ToCss for EasingFunction {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            EasingFunction::Linear => printer.write_str("linear"),
+            EasingFunction::Ease => printer.write_str("ease"),
+            EasingFunction::EaseIn => printer.write_str("ease-in"),
+            EasingFunction::EaseOut => printer.write_str("ease-out"),
+            EasingFunction::EaseInOut => printer.write_str("ease-in-out"),
+            EasingFunction::StepStart => printer.write_str("step-start"),
+            EasingFunction::StepEnd => printer.write_str("step-end"),
+            EasingFunction::CubicBezier(x1, y1, x2, y2) => {
+                printer.write_str("cubic-bezier(")?;
+                for (index, value) in [x1, y1, x2, y2].into_iter().enumerate() {
+                    if index > 0 {
+                        printer.write_char(',')?;
+                        printer.print_optional_space()?;
+                    }
+                    write!(printer, "{value}")?;
+                }
+                printer.write_char(')')
+            }
+            EasingFunction::Steps(steps, position) => {
+                write!(printer, "steps({steps}")?;
+                if let Some(position) = position {
+                    printer.write_char(',')?;
+                    printer.print_optional_space()?;
+                    printer.print(position)?;
+                }
+                printer.write_char(')')
+            }
+        }
+    }
+}
+
+impl FromStr for StepPosition {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "jump-start" => Ok(StepPosition::JumpStart),
+            "jump-end" => Ok(StepPosition::JumpEnd),
+            "jump-none" => Ok(StepPosition::JumpNone),
+            "jump-both" => Ok(StepPosition::JumpBoth),
+            "start" => Ok(StepPosition::Start),
+            "end" => Ok(StepPosition::End),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromIdentifier for StepPosition {
+    const EXPECTED: &'static str = "jump-start, jump-end, jump-none, jump-both, start, or end";
+}
+
+impl ToCss for StepPosition {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            StepPosition::JumpStart => "jump-start",
+            StepPosition::JumpEnd => "jump-end",
+            StepPosition::JumpNone => "jump-none",
+            StepPosition::JumpBoth => "jump-both",
+            StepPosition::Start => "start",
+            StepPosition::End => "end",
+        })
+    }
+}
+
+/// A time an animation takes to play once, unlike a delay it can not be negative
+///
+/// adapted from https://www.w3.org/TR/css-animations-1/#animation-duration
+struct Duration(Time);
+
+impl CommaSeparated for Duration {}
+
+impl Parsable for Duration {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let token_at = parser.tokens.peek().cloned();
+        match parser.parse()? {
+            Time::Seconds(value) | Time::Milliseconds(value) if value < 0.0 => {
+                let token_at = token_at.expect("a time was parsed");
+                Err(ParsingError::wrong_token(
+                    token_at,
+                    "a time that is not negative",
+                ))
+            }
+            time => Ok(Duration(time)),
+        }
+    }
+}
+
+/// parse the comma separated durations of `animation-duration`
+pub(super) fn parse_durations<I: Iterator<Item = char>>(
+    parser: &mut Parser<I>,
+) -> Result<Vec<Time>, ParsingError> {
+    let durations: Vec<Duration> = parser.parse()?;
+    Ok(durations.into_iter().map(|Duration(time)| time).collect())
+}
+
+/// How many times an animation plays
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+pub enum IterationCount {
+    Infinite,
+    Number(f64),
+}
+
+impl CommaSeparated for IterationCount {}
+
+impl Parsable for IterationCount {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Identifier(name),
+                ..
+            }) if name == "infinite" => {
+                parser.tokens.next();
+                Ok(IterationCount::Infinite)
+            }
+            Some(TokenAt {
+                token: Token::Number(_, _),
+                ..
+            }) => Ok(IterationCount::Number(parse_num(parser, 0.0, f64::MAX)?)),
+            Some(token_at) => Err(ParsingError::wrong_token(
+                token_at.clone(),
+                "infinite or a number",
+            )),
+            None => Err(ParsingError::end_of_file("infinite or a number")),
+        }
+    }
+}
+
+impl ToCss for IterationCount {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            IterationCount::Infinite => printer.write_str("infinite"),
+            IterationCount::Number(count) => write!(printer, "{count}"),
+        }
+    }
+}
+
+/// Whether an animation plays forwards, backwards, or alternates between them
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum AnimationDirection {
+    Normal,
+    Reverse,
+    Alternate,
+    AlternateReverse,
+}
+
+impl CommaSeparated for AnimationDirection {}
+
+impl FromStr for AnimationDirection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(AnimationDirection::Normal),
+            "reverse" => Ok(AnimationDirection::Reverse),
+            "alternate" => Ok(AnimationDirection::Alternate),
+            "alternate-reverse" => Ok(AnimationDirection::AlternateReverse),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromIdentifier for AnimationDirection {
+    const EXPECTED: &'static str = "normal, reverse, alternate, or alternate-reverse";
+}
+
+impl ToCss for AnimationDirection {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            AnimationDirection::Normal => "normal",
+            AnimationDirection::Reverse => "reverse",
+            AnimationDirection::Alternate => "alternate",
+            AnimationDirection::AlternateReverse => "alternate-reverse",
+        })
+    }
+}
+
+/// Which styles an animation applies before it starts and after it ends
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum AnimationFillMode {
+    None,
+    Forwards,
+    Backwards,
+    Both,
+}
+
+impl CommaSeparated for AnimationFillMode {}
+
+impl FromStr for AnimationFillMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(AnimationFillMode::None),
+            "forwards" => Ok(AnimationFillMode::Forwards),
+            "backwards" => Ok(AnimationFillMode::Backwards),
+            "both" => Ok(AnimationFillMode::Both),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromIdentifier for AnimationFillMode {
+    const EXPECTED: &'static str = "none, forwards, backwards, or both";
+}
+
+impl ToCss for AnimationFillMode {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            AnimationFillMode::None => "none",
+            AnimationFillMode::Forwards => "forwards",
+            AnimationFillMode::Backwards => "backwards",
+            AnimationFillMode::Both => "both",
+        })
+    }
+}
+
+/// Whether an animation is running or paused
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum AnimationPlayState {
+    Running,
+    Paused,
+}
+
+impl CommaSeparated for AnimationPlayState {}
+
+impl FromStr for AnimationPlayState {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(AnimationPlayState::Running),
+            "paused" => Ok(AnimationPlayState::Paused),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromIdentifier for AnimationPlayState {
+    const EXPECTED: &'static str = "running or paused";
+}
+
+impl ToCss for AnimationPlayState {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            AnimationPlayState::Running => "running",
+            AnimationPlayState::Paused => "paused",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn animation_names() {
+        let mut parser = Parser::new("fade, none, \"slide in\"".chars());
+        assert_eq!(
+            Ok(vec![
+                AnimationName::Keyframes("fade".to_owned()),
+                AnimationName::None,
+                AnimationName::Keyframes("slide in".to_owned()),
+            ]),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn serialize_animation_name() {
+        let mut output = String::new();
+        Printer::new(&mut output)
+            .print_comma_separated(&[
+                AnimationName::Keyframes("slide in".to_owned()),
+                AnimationName::Keyframes("none".to_owned()),
+            ])
+            .unwrap();
+        assert_eq!("\"slide in\", \"none\"", output);
+    }
+
+    #[test]
+    fn easing_keywords() {
+        let mut parser = Parser::new("ease-in-out, step-end".chars());
+        assert_eq!(
+            Ok(vec![EasingFunction::EaseInOut, EasingFunction::StepEnd]),
+            parser.parse()
+        );
+    }
+
+    #[test]
+    fn cubic_bezier() {
+        let mut parser = Parser::new("cubic-bezier(0.1, -0.6, 0.2 ,1.5)".chars());
+        assert_eq!(
+            Ok(EasingFunction::CubicBezier(0.1, -0.6, 0.2, 1.5)),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn cubic_bezier_x_out_of_range() {
+        let mut parser = Parser::new("cubic-bezier(2, 0, 0, 1)".chars());
+        assert!(parser.parse::<EasingFunction>().is_err());
+    }
+
+    #[test]
+    fn steps() {
+        let mut parser = Parser::new("steps(4), steps( 2 , jump-both )".chars());
+        assert_eq!(
+            Ok(vec![
+                EasingFunction::Steps(4, None),
+                EasingFunction::Steps(2, Some(StepPosition::JumpBoth)),
+            ]),
+            parser.parse()
+        );
+    }
+
+    #[test]
+    fn fractional_steps() {
+        let mut parser = Parser::new("steps(2.5)".chars());
+        assert!(parser.parse::<EasingFunction>().is_err());
+    }
+
+    #[test]
+    fn zero_steps() {
+        let mut parser = Parser::new("steps(0)".chars());
+        assert!(parser.parse::<EasingFunction>().is_err());
+    }
+
+    #[test]
+    fn serialize_easing_functions() {
+        let mut output = String::new();
+        Printer::new(&mut output)
+            .print_comma_separated(&[
+                EasingFunction::CubicBezier(0.1, -0.6, 0.2, 1.5),
+                EasingFunction::Steps(2, Some(StepPosition::JumpBoth)),
+            ])
+            .unwrap();
+        assert_eq!(
+            "cubic-bezier(0.1, -0.6, 0.2, 1.5), steps(2, jump-both)",
+            output
+        );
+    }
+
+    #[test]
+    fn iteration_count() {
+        let mut parser = Parser::new("infinite, 2.5".chars());
+        assert_eq!(
+            Ok(vec![IterationCount::Infinite, IterationCount::Number(2.5)]),
+            parser.parse()
+        );
+    }
+
+    #[test]
+    fn negative_iteration_count() {
+        let mut parser = Parser::new("-1".chars());
+        assert!(parser.parse::<IterationCount>().is_err());
+    }
+
+    #[test]
+    fn negative_duration() {
+        let mut parser = Parser::new("1s, -250ms".chars());
+        match parse_durations(&mut parser) {
+            Err(ParsingError::WrongToken {
+                column,
+                expected,
+                found,
+                ..
+            }) => {
+                assert_eq!(4, column);
+                assert_eq!("a time that is not negative", expected);
+                assert_eq!("the dimension -250ms", found);
+            }
+            result => panic!("expected an error, found {result:?}"),
+        }
+    }
+
+    #[test]
+    fn negative_iteration_count_message() {
+        let mut parser = Parser::new("-1".chars());
+        match parser.parse::<IterationCount>() {
+            Err(ParsingError::WrongToken { expected, .. }) => {
+                assert_eq!("a number that is not negative", expected)
+            }
+            result => panic!("expected an error, found {result:?}"),
+        }
+    }
+
+    #[test]
+    fn keywords() {
+        let mut parser = Parser::new("alternate-reverse".chars());
+        assert_eq!(Ok(AnimationDirection::AlternateReverse), parser.parse());
+
+        let mut parser = Parser::new("both".chars());
+        assert_eq!(Ok(AnimationFillMode::Both), parser.parse());
+
+        let mut parser = Parser::new("paused".chars());
+        assert_eq!(Ok(AnimationPlayState::Paused), parser.parse());
+    }
+}
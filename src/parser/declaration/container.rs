@@ -0,0 +1,143 @@
+use super::*;
+
+/// Which sizes of an element can be tested with `@container`
+///
+/// adapted from https://www.w3.org/TR/css-contain-3/#container-type
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum ContainerType {
+    Normal,
+    Size,
+    InlineSize,
+}
+
+/// The names `@container` can use to refer to an element like `sidebar card`
+///
+/// adapted from https://www.w3.org/TR/css-contain-3/#container-name
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum ContainerName {
+    None,
+    Names(Vec<String>),
+}
+
+impl FromStr for ContainerType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(ContainerType::Normal),
+            "size" => Ok(ContainerType::Size),
+            "inline-size" => Ok(ContainerType::InlineSize),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromIdentifier for ContainerType {
+    const EXPECTED: &'static str = "normal, size, or inline-size";
+}
+
+impl Parsable for ContainerName {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        const EXPECTED: &str = "none or a container name";
+
+        let mut names = Vec::new();
+
+        loop {
+            match parser.tokens.next() {
+                Some(TokenAt {
+                    token: Token::Identifier(name),
+                    ..
+                }) if name == "none" && names.is_empty() => return Ok(ContainerName::None),
+                Some(token_at) => match token_at.token {
+                    Token::Identifier(ref name)
+                        if !["none", "and", "or", "not"].contains(&name.as_str()) =>
+                    {
+                        names.push(name.clone())
+                    }
+                    _ => return Err(ParsingError::wrong_token(token_at, EXPECTED)),
+                },
+                None => return Err(ParsingError::end_of_file(EXPECTED)),
+            }
+
+            parser.optional_whitespace();
+
+            match parser.tokens.peek() {
+                Some(TokenAt {
+                    token: Token::Identifier(_),
+                    ..
+                }) => {}
+                _ => return Ok(ContainerName::Names(names)),
+            }
+        }
+    }
+}
+
+impl ToCss for ContainerType {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            ContainerType::Normal => "normal",
+            ContainerType::Size => "size",
+            ContainerType::InlineSize => "inline-size",
+        })
+    }
+}
+
+impl ToCss for ContainerName {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            ContainerName::None => printer.write_str("none"),
+            ContainerName::Names(names) => {
+                for (index, name) in names.iter().enumerate() {
+                    if index > 0 {
+                        printer.write_char(' ')?;
+                    }
+                    printer.print_identifier(name)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for ContainerType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for ContainerName {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        let mut parser = Parser::new("sidebar  card".chars());
+        assert_eq!(
+            Ok(ContainerName::Names(vec![
+                "sidebar".to_owned(),
+                "card".to_owned()
+            ])),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn none() {
+        let mut parser = Parser::new("none".chars());
+        assert_eq!(Ok(ContainerName::None), parser.parse());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn none_with_names() {
+        let mut parser = Parser::new("card none".chars());
+        assert!(parser.parse::<ContainerName>().is_err());
+    }
+}
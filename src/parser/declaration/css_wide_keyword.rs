@@ -0,0 +1,208 @@
+use super::*;
+
+/// A keyword that is a valid value for every property like `inherit`
+///
+/// adapted from https://www.w3.org/TR/css-values-4/#common-keywords
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum CssWideKeyword {
+    Initial,
+    Inherit,
+    Unset,
+    Revert,
+    RevertLayer,
+}
+
+impl FromStr for CssWideKeyword {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "initial" => Ok(CssWideKeyword::Initial),
+            "inherit" => Ok(CssWideKeyword::Inherit),
+            "unset" => Ok(CssWideKeyword::Unset),
+            "revert" => Ok(CssWideKeyword::Revert),
+            "revert-layer" => Ok(CssWideKeyword::RevertLayer),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromIdentifier for CssWideKeyword {
+    const EXPECTED: &'static str = "initial, inherit, unset, revert, or revert-layer";
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// parse a CSS-wide keyword if it is the whole value of a declaration
+    ///
+    /// nothing is consumed if the value is anything else, such as `inherit` as one of several values
+    pub(crate) fn parse_css_wide_keyword(&mut self) -> Option<CssWideKeyword> {
+        let keyword = match self.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Identifier(name),
+                ..
+            }) => name.parse().ok()?,
+            _ => return None,
+        };
+
+        let n = match self.tokens.peek_nth(1) {
+            Some(TokenAt {
+                token: Token::Whitespace(),
+                ..
+            }) => 2,
+            _ => 1,
+        };
+
+        let is_whole_value = matches!(
+            self.tokens.peek_nth(n).map(|token_at| &token_at.token),
+            None | Some(Token::Semicolon() | Token::CloseCurlyBracket() | Token::Delimiter('!'))
+        );
+
+        if is_whole_value {
+            self.tokens.next();
+            Some(keyword)
+        } else {
+            None
+        }
+    }
+}
+
+impl ToCss for CssWideKeyword {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            CssWideKeyword::Initial => "initial",
+            CssWideKeyword::Inherit => "inherit",
+            CssWideKeyword::Unset => "unset",
+            CssWideKeyword::Revert => "revert",
+            CssWideKeyword::RevertLayer => "revert-layer",
+        })
+    }
+}
+
+impl fmt::Display for CssWideKeyword {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+/// The value of a property, or a CSS-wide keyword in its place
+///
+/// adapted from https://www.w3.org/TR/css-values-4/#common-keywords
+#[derive(Debug, PartialEq, Serialize)]
+pub enum CssWideOr<T> {
+    Keyword(CssWideKeyword),
+    Value(T),
+}
+
+impl<T> CssWideOr<T> {
+    /// parse a CSS-wide keyword if it is the whole value, or else the value with `parse_value`
+    pub(crate) fn parse_with<I: Iterator<Item = char>>(
+        parser: &mut Parser<I>,
+        parse_value: impl FnOnce(&mut Parser<I>) -> Result<T, ParsingError>,
+    ) -> Result<Self, ParsingError> {
+        match parser.parse_css_wide_keyword() {
+            Some(keyword) => Ok(CssWideOr::Keyword(keyword)),
+            None => Ok(CssWideOr::Value(parse_value(parser)?)),
+        }
+    }
+
+    /// print the keyword, or else the value with `print_value`
+    pub(crate) fn print_with(
+        &self,
+        printer: &mut Printer,
+        print_value: impl FnOnce(&mut Printer, &T) -> fmt::Result,
+    ) -> fmt::Result {
+        match self {
+            CssWideOr::Keyword(keyword) => printer.print(keyword),
+            CssWideOr::Value(value) => print_value(printer, value),
+        }
+    }
+}
+
+impl<T> From<T> for CssWideOr<T> {
+    fn from(value: T) -> Self {
+        CssWideOr::Value(value)
+    }
+}
+
+impl<T: Parsable> Parsable for CssWideOr<T> {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        CssWideOr::parse_with(parser, T::parse)
+    }
+}
+
+impl<T: ToCss> ToCss for CssWideOr<T> {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        self.print_with(printer, |printer, value| printer.print(value))
+    }
+}
+
+impl<T: ToCss> fmt::Display for CssWideOr<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords() {
+        for (input, keyword) in [
+            ("initial", CssWideKeyword::Initial),
+            ("inherit", CssWideKeyword::Inherit),
+            ("UNSET", CssWideKeyword::Unset),
+            ("revert", CssWideKeyword::Revert),
+            ("revert-layer", CssWideKeyword::RevertLayer),
+        ] {
+            let mut parser = Parser::new(input.chars());
+            assert_eq!(Ok(keyword), parser.parse());
+            assert_eq!(None, parser.tokens.next());
+        }
+    }
+
+    #[test]
+    fn whole_value() {
+        let mut parser = Parser::new("inherit ;".chars());
+        assert_eq!(
+            Some(CssWideKeyword::Inherit),
+            parser.parse_css_wide_keyword()
+        );
+    }
+
+    #[test]
+    fn part_of_value() {
+        let mut parser = Parser::new("inherit serif".chars());
+        assert_eq!(None, parser.parse_css_wide_keyword());
+        assert_eq!(
+            Some(Token::Identifier("inherit".to_owned())),
+            parser.tokens.next().map(|token_at| token_at.token)
+        );
+    }
+
+    #[test]
+    fn print() {
+        assert_eq!("revert-layer", CssWideKeyword::RevertLayer.to_string());
+    }
+
+    #[test]
+    fn keyword_or_value() {
+        let mut parser = Parser::new("inherit".chars());
+        assert_eq!(
+            Ok(CssWideOr::<Color>::Keyword(CssWideKeyword::Inherit)),
+            parser.parse()
+        );
+
+        let mut parser = Parser::new("red".chars());
+        assert_eq!(Ok(CssWideOr::Value(Color::Red)), parser.parse());
+    }
+
+    #[test]
+    fn print_keyword_or_value() {
+        assert_eq!(
+            "unset",
+            CssWideOr::<Color>::Keyword(CssWideKeyword::Unset).to_string()
+        );
+        assert_eq!("red", CssWideOr::Value(Color::Red).to_string());
+    }
+}
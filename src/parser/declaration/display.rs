@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum Display {
     Block,
     Inline,
@@ -23,3 +23,19 @@ impl FromStr for Display {
 impl FromIdentifier for Display {
     const EXPECTED: &'static str = "block, inline, or inline-block";
 }
+
+impl ToCss for Display {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            Display::Block => "block",
+            Display::Inline => "inline",
+            Display::InlineBlock => "inline-block",
+        })
+    }
+}
+
+impl fmt::Display for Display {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
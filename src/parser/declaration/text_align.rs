@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum TextAlign {
     Left,
     Right,
@@ -25,3 +25,20 @@ impl FromStr for TextAlign {
 impl FromIdentifier for TextAlign {
     const EXPECTED: &'static str = "left, right, center, or justify";
 }
+
+impl ToCss for TextAlign {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            TextAlign::Left => "left",
+            TextAlign::Right => "right",
+            TextAlign::Center => "center",
+            TextAlign::Justify => "justify",
+        })
+    }
+}
+
+impl fmt::Display for TextAlign {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
@@ -1,49 +1,121 @@
 use super::*;
 
-impl Parsable for Vec<Declaration> {
+impl Parsable for Vec<Spanned<DeclarationItem>> {
     fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
-        let mut declarations = Vec::new();
-        loop {
-            parser.optional_whitespace();
-
-            match parser.tokens.peek() {
-                Some(TokenAt {
-                    token: Token::Identifier(_),
-                    ..
-                }) => {
-                    let dec: Declaration = parser.parse()?;
-                    declarations.push(dec);
-                }
-                _ => break,
+        parse_declaration_list(parser)
+    }
+}
+
+/// parse a list of declarations separated by semicolons
+///
+/// this is shared with blocks of declaration like things, such as the descriptors in `@font-face`
+pub(crate) fn parse_declaration_list<T: Parsable, I: Iterator<Item = char>>(
+    parser: &mut Parser<I>,
+) -> Result<Vec<Spanned<T>>, ParsingError> {
+    if parser.is_recovering() {
+        return parse_with_recovery(parser);
+    }
+
+    let mut declarations = Vec::new();
+    loop {
+        parser.optional_whitespace();
+
+        match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Identifier(_),
+                ..
+            }) => {
+                let dec: Spanned<T> = parser.parse()?;
+                declarations.push(dec);
+            }
+            _ => break,
+        }
+
+        parser.optional_whitespace();
+
+        match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Semicolon(),
+                ..
+            }) => {
+                parser.tokens.next();
+                continue;
+            }
+            _ => break,
+        }
+    }
+    Ok(declarations)
+}
+
+/// parse declarations until the end of the block skipping over any invalid declarations
+fn parse_with_recovery<T: Parsable, I: Iterator<Item = char>>(
+    parser: &mut Parser<I>,
+) -> Result<Vec<Spanned<T>>, ParsingError> {
+    let mut declarations = Vec::new();
+
+    loop {
+        parser.optional_whitespace();
+
+        match parser.tokens.peek() {
+            None
+            | Some(TokenAt {
+                token: Token::CloseCurlyBracket(),
+                ..
+            }) => break,
+            Some(TokenAt {
+                token: Token::Semicolon(),
+                ..
+            }) => {
+                parser.tokens.next();
+                continue;
             }
+            _ => {}
+        }
+
+        let depth = parser.tokens.depth();
 
-            parser.optional_whitespace();
-
-            match parser.tokens.peek() {
-                Some(TokenAt {
-                    token: Token::Semicolon(),
-                    ..
-                }) => {
-                    parser.tokens.next();
-                    continue;
-                }
-                _ => break,
+        let declaration = match parser.parse() {
+            Ok(declaration) => declaration,
+            Err(error) => {
+                parser.recover(error)?;
+                parser.skip_declaration(depth);
+                continue;
+            }
+        };
+
+        parser.optional_whitespace();
+
+        // a declaration followed by anything else is invalid as a whole
+        match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Semicolon() | Token::CloseCurlyBracket(),
+                ..
+            })
+            | None => declarations.push(declaration),
+            Some(token_at) => {
+                let error = ParsingError::wrong_token(token_at.clone(), "; or }");
+                parser.recover(error)?;
+                parser.skip_declaration(depth);
             }
         }
-        Ok(declarations)
     }
+
+    Ok(declarations)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::span::assert_eq_ignoring_spans;
 
     #[test]
     fn one_dec() {
         let mut parser = Parser::new("background-color: red".chars());
-        assert_eq!(
-            Ok(vec![Declaration::BackgroundColor(Color::Red)]),
-            parser.parse()
+        assert_eq_ignoring_spans(
+            Ok(vec![Spanned::<DeclarationItem>::from(
+                Declaration::BackgroundColor(Color::Red.into()),
+            )]),
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -51,12 +123,14 @@ mod tests {
     #[test]
     fn two_dec() {
         let mut parser = Parser::new("background-color: red; border-color: red;".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(vec![
-                Declaration::BackgroundColor(Color::Red),
-                Declaration::BorderColor(Sides::Single(Color::Red))
+                Spanned::<DeclarationItem>::from(Declaration::BackgroundColor(Color::Red.into())),
+                Spanned::<DeclarationItem>::from(Declaration::BorderColor(
+                    Sides::Single(Color::Red).into(),
+                )),
             ]),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -64,12 +138,14 @@ mod tests {
     #[test]
     fn two_dec_no_ending_semicolon() {
         let mut parser = Parser::new("background-color: red; border-color: red".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(vec![
-                Declaration::BackgroundColor(Color::Red),
-                Declaration::BorderColor(Sides::Single(Color::Red))
+                Spanned::<DeclarationItem>::from(Declaration::BackgroundColor(Color::Red.into())),
+                Spanned::<DeclarationItem>::from(Declaration::BorderColor(
+                    Sides::Single(Color::Red).into(),
+                )),
             ]),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -77,10 +153,87 @@ mod tests {
     #[test]
     fn missing_semicolon() {
         let mut parser = Parser::new("background-color: red border-color: red".chars());
-        assert_eq!(
-            Ok(vec![Declaration::BackgroundColor(Color::Red)]),
-            parser.parse()
+        assert_eq_ignoring_spans(
+            Ok(vec![Spanned::<DeclarationItem>::from(
+                Declaration::BackgroundColor(Color::Red.into()),
+            )]),
+            parser.parse(),
         );
         assert_ne!(None, parser.tokens.next());
     }
+
+    #[test]
+    fn recover_from_invalid_declaration() {
+        let mut parser =
+            Parser::with_error_recovery("color: red; band-color: red; opacity: 1".chars()).strict();
+        assert_eq_ignoring_spans(
+            Ok(vec![
+                Spanned::<DeclarationItem>::from(Declaration::Color(Color::Red.into())),
+                Spanned::<DeclarationItem>::from(Declaration::Opacity(1.0.into())),
+            ]),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+        assert_eq_ignoring_spans(1, parser.errors.unwrap().len());
+    }
+
+    #[test]
+    fn recover_from_invalid_value() {
+        let mut parser = Parser::with_error_recovery(
+            "color: rgb(1; 2); opacity: 2; background-color: blue".chars(),
+        );
+        assert_eq_ignoring_spans(
+            Ok(vec![Spanned::<DeclarationItem>::from(
+                Declaration::BackgroundColor(Color::Blue.into()),
+            )]),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+        assert_eq_ignoring_spans(2, parser.errors.unwrap().len());
+    }
+
+    #[test]
+    fn recover_from_missing_semicolon() {
+        let mut parser = Parser::with_error_recovery(
+            "background-color: red border-color: red; color: red".chars(),
+        );
+        assert_eq_ignoring_spans(
+            Ok(vec![Spanned::<DeclarationItem>::from(Declaration::Color(
+                Color::Red.into(),
+            ))]),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+        assert_eq_ignoring_spans(1, parser.errors.unwrap().len());
+    }
+
+    #[test]
+    fn recover_from_junk_after_value() {
+        let mut parser =
+            Parser::with_error_recovery("color: red !imp; opacity: 1 (a; b); opacity: 0.5".chars());
+        assert_eq_ignoring_spans(
+            Ok(vec![Spanned::<DeclarationItem>::from(
+                Declaration::Opacity(0.5.into()),
+            )]),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+        assert_eq_ignoring_spans(2, parser.errors.unwrap().len());
+    }
+
+    #[test]
+    fn recover_stops_at_end_of_block() {
+        let mut parser = Parser::with_error_recovery("color: red; 123 } color: red".chars());
+        assert_eq_ignoring_spans(
+            Ok(vec![Spanned::<DeclarationItem>::from(Declaration::Color(
+                Color::Red.into(),
+            ))]),
+            parser.parse(),
+        );
+        assert_eq_ignoring_spans(
+            Some(Token::CloseCurlyBracket()),
+            parser.tokens.next().map(|token_at| token_at.token),
+        );
+        assert_eq_ignoring_spans(1, parser.errors.unwrap().len());
+    }
 }
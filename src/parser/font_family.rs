@@ -1,7 +1,78 @@
+use crate::printer::is_custom_identifier;
+
 use super::*;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct FontName(pub String);
+/// A font in a list of fonts to try like `Arial` or `"Times New Roman"` or a generic family like `serif`
+///
+/// adapted from https://www.w3.org/TR/css-fonts-4/#font-family-prop
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub enum FontName {
+    /// the name of a font family, written as a string or as identifiers separated by spaces
+    Family(String),
+    /// a font chosen by the browser, these are only keywords when they are not in quotes
+    Generic(GenericFamily),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum GenericFamily {
+    Serif,
+    SansSerif,
+    Monospace,
+    Cursive,
+    Fantasy,
+    SystemUi,
+    UiSerif,
+    UiSansSerif,
+    UiMonospace,
+    UiRounded,
+    Math,
+    Emoji,
+    Fangsong,
+}
+
+/// the keywords of the generic families, a family name with one of these in it has to be a string
+const GENERIC_FAMILIES: [&str; 13] = [
+    "serif",
+    "sans-serif",
+    "monospace",
+    "cursive",
+    "fantasy",
+    "system-ui",
+    "ui-serif",
+    "ui-sans-serif",
+    "ui-monospace",
+    "ui-rounded",
+    "math",
+    "emoji",
+    "fangsong",
+];
+
+impl FromStr for GenericFamily {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "serif" => Ok(GenericFamily::Serif),
+            "sans-serif" => Ok(GenericFamily::SansSerif),
+            "monospace" => Ok(GenericFamily::Monospace),
+            "cursive" => Ok(GenericFamily::Cursive),
+            "fantasy" => Ok(GenericFamily::Fantasy),
+            "system-ui" => Ok(GenericFamily::SystemUi),
+            "ui-serif" => Ok(GenericFamily::UiSerif),
+            "ui-sans-serif" => Ok(GenericFamily::UiSansSerif),
+            "ui-monospace" => Ok(GenericFamily::UiMonospace),
+            "ui-rounded" => Ok(GenericFamily::UiRounded),
+            "math" => Ok(GenericFamily::Math),
+            "emoji" => Ok(GenericFamily::Emoji),
+            "fangsong" => Ok(GenericFamily::Fangsong),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromIdentifier for GenericFamily {
+    const EXPECTED: &'static str = "a generic font family";
+}
 
 impl CommaSeparated for FontName {}
 
@@ -10,6 +81,8 @@ impl Parsable for FontName {
         match parser.tokens.next() {
             Some(token_at) => match token_at.token {
                 Token::Identifier(mut name) => {
+                    let mut is_one_word = true;
+
                     loop {
                         parser.optional_whitespace();
                         match parser.tokens.peek() {
@@ -18,6 +91,7 @@ impl Parsable for FontName {
                                     name.push(' ');
                                     name.push_str(part);
                                     parser.tokens.next();
+                                    is_one_word = false;
                                 }
                                 _ => break,
                             },
@@ -25,9 +99,12 @@ impl Parsable for FontName {
                         }
                     }
 
-                    Ok(FontName(name))
+                    match name.parse() {
+                        Ok(generic_family) if is_one_word => Ok(FontName::Generic(generic_family)),
+                        _ => Ok(FontName::Family(name)),
+                    }
                 }
-                Token::String(name) => Ok(FontName(name)),
+                Token::String(name) => Ok(FontName::Family(name)),
                 _ => Err(ParsingError::wrong_token(token_at, "identifier or string")),
             },
             None => Err(ParsingError::end_of_file("identifier or string")),
@@ -35,6 +112,58 @@ impl Parsable for FontName {
     }
 }
 
+impl FontName {
+    /// if this font name can be written as a list of identifiers instead of a string
+    ///
+    /// a family name that has a keyword in it like `"serif"` or `"inherit"` keeps its quotes so it is not read as the keyword
+    fn is_identifiers(name: &str) -> bool {
+        name.split(' ')
+            .all(|word| is_custom_identifier(word, &GENERIC_FAMILIES))
+    }
+}
+
+impl ToCss for FontName {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            FontName::Family(name) if FontName::is_identifiers(name) => printer.write_str(name),
+            FontName::Family(name) => printer.print_string(name),
+            FontName::Generic(generic_family) => printer.print(generic_family),
+        }
+    }
+}
+
+impl ToCss for GenericFamily {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            GenericFamily::Serif => "serif",
+            GenericFamily::SansSerif => "sans-serif",
+            GenericFamily::Monospace => "monospace",
+            GenericFamily::Cursive => "cursive",
+            GenericFamily::Fantasy => "fantasy",
+            GenericFamily::SystemUi => "system-ui",
+            GenericFamily::UiSerif => "ui-serif",
+            GenericFamily::UiSansSerif => "ui-sans-serif",
+            GenericFamily::UiMonospace => "ui-monospace",
+            GenericFamily::UiRounded => "ui-rounded",
+            GenericFamily::Math => "math",
+            GenericFamily::Emoji => "emoji",
+            GenericFamily::Fangsong => "fangsong",
+        })
+    }
+}
+
+impl fmt::Display for FontName {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for GenericFamily {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,7 +171,10 @@ mod tests {
     #[test]
     fn one_word() {
         let mut parser = Parser::new("Arial".chars());
-        assert_eq!(Ok(vec![FontName("Arial".to_owned())]), parser.parse());
+        assert_eq!(
+            Ok(vec![FontName::Family("Arial".to_owned())]),
+            parser.parse()
+        );
         assert_eq!(None, parser.tokens.next());
     }
 
@@ -50,7 +182,7 @@ mod tests {
     fn quotes() {
         let mut parser = Parser::new("\"Times New Roman\"".chars());
         assert_eq!(
-            Ok(vec![FontName("Times New Roman".to_owned())]),
+            Ok(vec![FontName::Family("Times New Roman".to_owned())]),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -60,7 +192,7 @@ mod tests {
     fn no_quotes() {
         let mut parser = Parser::new("Times New Roman".chars());
         assert_eq!(
-            Ok(vec![FontName("Times New Roman".to_owned())]),
+            Ok(vec![FontName::Family("Times New Roman".to_owned())]),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
@@ -71,9 +203,9 @@ mod tests {
         let mut parser = Parser::new("Arial, \"Times New Roman\", serif".chars());
         assert_eq!(
             Ok(vec![
-                FontName("Arial".to_owned()),
-                FontName("Times New Roman".to_owned()),
-                FontName("serif".to_owned())
+                FontName::Family("Arial".to_owned()),
+                FontName::Family("Times New Roman".to_owned()),
+                FontName::Generic(GenericFamily::Serif)
             ]),
             parser.parse()
         );
@@ -86,7 +218,7 @@ mod tests {
     fn spaces() {
         let mut parser = Parser::new("Goudy Bookletter 1911, sans-serif".chars());
         assert_eq!(
-            Ok(vec![FontName("Goudy Bookletter".to_owned())]),
+            Ok(vec![FontName::Family("Goudy Bookletter".to_owned())]),
             parser.parse()
         );
         assert_ne!(None, parser.tokens.next());
@@ -95,14 +227,68 @@ mod tests {
     #[test]
     fn slash() {
         let mut parser = Parser::new("Red/Black, sans-serif".chars());
-        assert_eq!(Ok(vec![FontName("Red".to_owned())]), parser.parse());
+        assert_eq!(Ok(vec![FontName::Family("Red".to_owned())]), parser.parse());
         assert_ne!(None, parser.tokens.next());
     }
 
     #[test]
     fn missing_comma() {
         let mut parser = Parser::new("\"Lucida\" Grande, sans-serif".chars());
-        assert_eq!(Ok(vec![FontName("Lucida".to_owned())]), parser.parse());
+        assert_eq!(
+            Ok(vec![FontName::Family("Lucida".to_owned())]),
+            parser.parse()
+        );
         assert_ne!(None, parser.tokens.next());
     }
+
+    #[test]
+    fn serialize_identifiers() {
+        assert_eq!(
+            "Times New Roman",
+            FontName::Family("Times New Roman".to_owned()).to_string()
+        );
+    }
+
+    #[test]
+    fn generic_family() {
+        let mut parser = Parser::new("\"serif\", serif, SANS-SERIF".chars());
+        assert_eq!(
+            Ok(vec![
+                FontName::Family("serif".to_owned()),
+                FontName::Generic(GenericFamily::Serif),
+                FontName::Generic(GenericFamily::SansSerif)
+            ]),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn keywords_keep_their_quotes() {
+        for input in [
+            "\"serif\"",
+            "\"Sans-Serif\"",
+            "\"inherit\"",
+            "\"default\"",
+            "\"My serif\"",
+            "serif",
+            "Times New Roman",
+        ] {
+            let mut parser = Parser::new(input.chars());
+            let font_name: FontName = parser.parse().unwrap();
+            let output = font_name.to_string();
+            assert_eq!(input, output);
+
+            let mut parser = Parser::new(output.chars());
+            assert_eq!(Ok(font_name), parser.parse());
+        }
+    }
+
+    #[test]
+    fn serialize_quoted() {
+        assert_eq!(
+            "\"Font 2\"",
+            FontName::Family("Font 2".to_owned()).to_string()
+        );
+    }
 }
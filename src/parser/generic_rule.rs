@@ -0,0 +1,616 @@
+use super::component_value::{push_component_value, trim_whitespace, ComponentValue};
+use super::*;
+
+/// A rule that has not been interpreted yet, its prelude and values are kept as component values
+///
+/// this is what a stylesheet looks like before any at-rule, selector or property is understood,
+/// so it can hold any syntax even when the typed `Rule` can not
+///
+/// adapted from https://www.w3.org/TR/css-syntax-3/#parsing
+#[derive(Debug, PartialEq, Serialize)]
+pub enum GenericRule {
+    AtRule(GenericAtRule),
+    QualifiedRule(GenericQualifiedRule),
+}
+
+/// An at-rule like `@media screen { a { color: red } }` or `@charset "utf-8";`
+///
+/// adapted from https://www.w3.org/TR/css-syntax-3/#consume-at-rule
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GenericAtRule {
+    /// the name without the `@` like `media`
+    pub name: String,
+    /// everything between the name and the block or `;`, without the whitespace around it
+    pub prelude: Vec<ComponentValue>,
+    /// the contents of the block, or `None` if the rule ends with a `;`
+    pub block: Option<Vec<GenericBlockItem>>,
+    pub span: Span,
+}
+
+/// A rule with a prelude and a block like `a:hover { color: red }`, the prelude is usually a selector list
+///
+/// adapted from https://www.w3.org/TR/css-syntax-3/#consume-qualified-rule
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GenericQualifiedRule {
+    /// everything before the block, without the whitespace around it
+    pub prelude: Vec<ComponentValue>,
+    pub block: Vec<GenericBlockItem>,
+    pub span: Span,
+}
+
+/// One of the things inside the block of a generic rule
+///
+/// adapted from https://www.w3.org/TR/css-syntax-3/#consume-block-contents
+#[derive(Debug, PartialEq, Serialize)]
+pub enum GenericBlockItem {
+    Declaration(GenericDeclaration),
+    Rule(GenericRule),
+}
+
+/// A declaration like `color: red !important` with any property name and value
+///
+/// adapted from https://www.w3.org/TR/css-syntax-3/#consume-declaration
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GenericDeclaration {
+    pub name: String,
+    /// the value without the whitespace around it or `!important`
+    pub value: Vec<ComponentValue>,
+    pub is_important: bool,
+    pub span: Span,
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// whether a declaration is next instead of a rule
+    ///
+    /// a name and a colon can also start a rule like `a:hover { }`, so a `{}` block in the value
+    /// means it is a rule unless it is a custom property
+    fn is_generic_declaration_next(&mut self) -> bool {
+        let is_custom_property = match self.tokens.peek().map(|token_at| &token_at.token) {
+            Some(Token::Identifier(name)) => name.starts_with("--"),
+            _ => return false,
+        };
+
+        let mut n = 1;
+        while let Some(Token::Whitespace()) =
+            self.tokens.peek_nth(n).map(|token_at| &token_at.token)
+        {
+            n += 1;
+        }
+
+        if !matches!(
+            self.tokens.peek_nth(n).map(|token_at| &token_at.token),
+            Some(Token::Colon())
+        ) {
+            return false;
+        }
+
+        if is_custom_property {
+            return true;
+        }
+
+        let mut depth: usize = 0;
+
+        for n in n + 1.. {
+            match self.tokens.peek_nth(n).map(|token_at| &token_at.token) {
+                None => return true,
+                Some(Token::Semicolon() | Token::CloseCurlyBracket()) if depth == 0 => return true,
+                Some(Token::OpenCurlyBracket()) if depth == 0 => return false,
+                Some(
+                    Token::OpenCurlyBracket()
+                    | Token::OpenParenthesis()
+                    | Token::OpenSquareBracket()
+                    | Token::Function(_),
+                ) => depth += 1,
+                Some(
+                    Token::CloseCurlyBracket()
+                    | Token::CloseParenthesis()
+                    | Token::CloseSquareBracket(),
+                ) => depth = depth.saturating_sub(1),
+                Some(_) => {}
+            }
+        }
+
+        unreachable!("the loop only ends by returning")
+    }
+}
+
+impl Parsable for Vec<GenericRule> {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let mut rules = Vec::new();
+
+        loop {
+            parser.optional_whitespace();
+
+            match parser.tokens.peek() {
+                Some(TokenAt {
+                    token: Token::AtKeyword(_),
+                    ..
+                }) => {
+                    if let Some(at_rule) = parser.parse_rule(true)? {
+                        rules.push(GenericRule::AtRule(at_rule));
+                    }
+                }
+                Some(TokenAt {
+                    token: Token::CloseCurlyBracket(),
+                    ..
+                }) => {
+                    let token_at = parser.tokens.next().expect("the token was peeked");
+                    parser.recover(ParsingError::wrong_token(token_at, "a rule"))?;
+                }
+                // an invalid qualified rule is skipped like an at-rule since it can end at a `;`
+                Some(_) => {
+                    if let Some(qualified_rule) = parser.parse_rule(true)? {
+                        rules.push(GenericRule::QualifiedRule(qualified_rule));
+                    }
+                }
+                None => break,
+            }
+        }
+
+        Ok(rules)
+    }
+}
+
+impl Parsable for Vec<GenericBlockItem> {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let mut items = Vec::new();
+
+        loop {
+            parser.optional_whitespace();
+
+            let is_declaration = parser.is_generic_declaration_next();
+
+            match parser.tokens.peek() {
+                None
+                | Some(TokenAt {
+                    token: Token::CloseCurlyBracket(),
+                    ..
+                }) => break,
+                Some(TokenAt {
+                    token: Token::Semicolon(),
+                    ..
+                }) => {
+                    parser.tokens.next();
+                }
+                Some(TokenAt {
+                    token: Token::AtKeyword(_),
+                    ..
+                }) => {
+                    if let Some(at_rule) = parser.parse_rule(true)? {
+                        items.push(GenericBlockItem::Rule(GenericRule::AtRule(at_rule)));
+                    }
+                }
+                Some(_) if is_declaration => {
+                    let depth = parser.tokens.depth();
+
+                    let declaration = match parser.parse() {
+                        Ok(declaration) => declaration,
+                        Err(error) => {
+                            parser.recover(error)?;
+                            parser.skip_declaration(depth);
+                            continue;
+                        }
+                    };
+
+                    parser.optional_whitespace();
+
+                    // a declaration followed by anything else is invalid as a whole
+                    match parser.tokens.peek() {
+                        Some(TokenAt {
+                            token: Token::Semicolon() | Token::CloseCurlyBracket(),
+                            ..
+                        })
+                        | None => items.push(GenericBlockItem::Declaration(declaration)),
+                        Some(token_at) => {
+                            let error = ParsingError::wrong_token(token_at.clone(), "; or }");
+                            parser.recover(error)?;
+                            parser.skip_declaration(depth);
+                        }
+                    }
+                }
+                Some(_) => {
+                    if let Some(qualified_rule) = parser.parse_rule(true)? {
+                        items.push(GenericBlockItem::Rule(GenericRule::QualifiedRule(
+                            qualified_rule,
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(items)
+    }
+}
+
+impl Parsable for GenericAtRule {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
+        let name = match parser.tokens.next() {
+            Some(TokenAt {
+                token: Token::AtKeyword(name),
+                ..
+            }) => name,
+            Some(token_at) => return Err(ParsingError::wrong_token(token_at, "an at-rule")),
+            None => return Err(ParsingError::end_of_file("an at-rule")),
+        };
+
+        let mut prelude = Vec::new();
+
+        let block = loop {
+            match parser.tokens.peek() {
+                Some(TokenAt {
+                    token: Token::Semicolon(),
+                    ..
+                }) => {
+                    parser.tokens.next();
+                    break None;
+                }
+                Some(TokenAt {
+                    token: Token::OpenCurlyBracket(),
+                    ..
+                }) => {
+                    parser.tokens.next();
+                    let block = parser.parse()?;
+                    parser.expect_end_of_block()?;
+                    break Some(block);
+                }
+                // the `}` of the surrounding block or the end of the file also ends the rule
+                Some(TokenAt {
+                    token: Token::CloseCurlyBracket(),
+                    ..
+                })
+                | None => break None,
+                Some(_) => push_component_value(&mut prelude, parser.parse()?),
+            }
+        };
+
+        trim_whitespace(&mut prelude);
+
+        Ok(GenericAtRule {
+            name,
+            prelude,
+            block,
+            span: parser.span_from(start),
+        })
+    }
+}
+
+impl Parsable for GenericQualifiedRule {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+        let mut prelude = Vec::new();
+
+        loop {
+            match parser.tokens.peek() {
+                Some(TokenAt {
+                    token: Token::OpenCurlyBracket(),
+                    ..
+                }) => break,
+                Some(TokenAt {
+                    token: Token::Semicolon() | Token::CloseCurlyBracket(),
+                    ..
+                }) => {
+                    let token_at = parser.tokens.peek().expect("the token was peeked");
+                    return Err(ParsingError::wrong_token(token_at.clone(), "{"));
+                }
+                None => return Err(ParsingError::end_of_file("{")),
+                Some(_) => push_component_value(&mut prelude, parser.parse()?),
+            }
+        }
+
+        trim_whitespace(&mut prelude);
+
+        parser.expect(Token::OpenCurlyBracket())?;
+        let block = parser.parse()?;
+        parser.expect_end_of_block()?;
+
+        Ok(GenericQualifiedRule {
+            prelude,
+            block,
+            span: parser.span_from(start),
+        })
+    }
+}
+
+impl Parsable for GenericDeclaration {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
+        let name = match parser.tokens.next() {
+            Some(TokenAt {
+                token: Token::Identifier(name),
+                ..
+            }) => name,
+            Some(token_at) => return Err(ParsingError::wrong_token(token_at, "a property name")),
+            None => return Err(ParsingError::end_of_file("a property name")),
+        };
+
+        parser.consume_colon_separator()?;
+        let value = parser.parse_component_values()?;
+        parser.optional_whitespace();
+        let is_important = parser.parse_important();
+
+        Ok(GenericDeclaration {
+            name,
+            value,
+            is_important,
+            span: parser.span_from(start),
+        })
+    }
+}
+
+impl ToCss for GenericRule {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            GenericRule::AtRule(at_rule) => printer.print(at_rule),
+            GenericRule::QualifiedRule(qualified_rule) => printer.print(qualified_rule),
+        }
+    }
+}
+
+impl ToCss for GenericAtRule {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_char('@')?;
+        printer.print_identifier(&self.name)?;
+
+        if !self.prelude.is_empty() {
+            printer.write_char(' ')?;
+            printer.print_component_values(&self.prelude)?;
+        }
+
+        match &self.block {
+            Some(block) => printer.print_block_with(block, GenericBlockItem::terminator),
+            None => printer.write_char(';'),
+        }
+    }
+}
+
+impl ToCss for GenericQualifiedRule {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.print_component_values(&self.prelude)?;
+        printer.print_block_with(&self.block, GenericBlockItem::terminator)
+    }
+}
+
+impl ToCss for GenericBlockItem {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            GenericBlockItem::Declaration(declaration) => printer.print(declaration),
+            GenericBlockItem::Rule(rule) => printer.print(rule),
+        }
+    }
+}
+
+impl GenericBlockItem {
+    /// declarations end with a `;` in a block but rules do not
+    fn terminator(&self) -> &'static str {
+        match self {
+            GenericBlockItem::Declaration(_) => ";",
+            GenericBlockItem::Rule(_) => "",
+        }
+    }
+}
+
+impl ToCss for GenericDeclaration {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.print_identifier(&self.name)?;
+        printer.write_char(':')?;
+        printer.print_optional_space()?;
+        printer.print_component_values(&self.value)?;
+
+        if self.is_important {
+            printer.print_optional_space()?;
+            printer.write_str("!important")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for GenericRule {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for GenericAtRule {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for GenericQualifiedRule {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for GenericBlockItem {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for GenericDeclaration {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+    use crate::span::assert_eq_ignoring_spans;
+
+    fn parse(input: &str) -> Result<Vec<GenericRule>, ParsingError> {
+        Parser::new(input.chars()).into_generic_rules()
+    }
+
+    fn identifier(name: &str) -> ComponentValue {
+        ComponentValue::Token(Token::Identifier(name.to_owned()))
+    }
+
+    #[test]
+    fn qualified_rule() {
+        assert_eq_ignoring_spans(
+            Ok(vec![GenericRule::QualifiedRule(GenericQualifiedRule {
+                prelude: vec![identifier("a")],
+                block: vec![GenericBlockItem::Declaration(GenericDeclaration {
+                    name: "color".to_owned(),
+                    value: vec![identifier("red")],
+                    is_important: true,
+                    span: Span::EMPTY,
+                })],
+                span: Span::EMPTY,
+            })]),
+            parse("a { color: red !important; }"),
+        );
+    }
+
+    #[test]
+    fn at_rules() {
+        let rules = parse("@charset \"utf-8\"; @media screen { a { } }").unwrap();
+        assert!(matches!(
+            rules.as_slice(),
+            [
+                GenericRule::AtRule(GenericAtRule { name, block: None, .. }),
+                GenericRule::AtRule(GenericAtRule { block: Some(block), .. }),
+            ] if name == "charset"
+                && matches!(block.as_slice(), [GenericBlockItem::Rule(GenericRule::QualifiedRule(_))])
+        ));
+    }
+
+    #[test]
+    fn nested_rule_with_colon() {
+        let rules = parse("a { color: red; b:hover { color: blue } --x: { c }; }").unwrap();
+        let GenericRule::QualifiedRule(rule) = &rules[0] else {
+            panic!("expected a qualified rule");
+        };
+        assert!(matches!(
+            rule.block.as_slice(),
+            [
+                GenericBlockItem::Declaration(_),
+                GenericBlockItem::Rule(GenericRule::QualifiedRule(_)),
+                GenericBlockItem::Declaration(GenericDeclaration { name, .. }),
+            ] if name == "--x"
+        ));
+    }
+
+    #[test]
+    fn syntax_the_typed_parser_does_not_know() {
+        let rules = parse("a::part(label) > b { transform: rotate(45deg) }").unwrap();
+        assert_eq!(
+            "a::part(label) > b { transform: rotate(45deg); }",
+            rules[0].to_string()
+        );
+    }
+
+    #[test]
+    fn missing_block() {
+        assert!(parse("a b;").is_err());
+        assert!(parse("a b").is_err());
+    }
+
+    #[test]
+    fn recover_from_invalid_rule() {
+        let (rules, errors) = Parser::with_error_recovery("} a b; c { d: e f }".chars())
+            .into_generic_rules_with_errors();
+        assert_eq!(1, rules.len());
+        assert_eq!(2, errors.len());
+    }
+
+    #[test]
+    fn invalid_declaration_is_dropped() {
+        let (rules, errors) = Parser::with_error_recovery("a { b: c !important d; e: f }".chars())
+            .into_generic_rules_with_errors();
+        assert_eq!(1, errors.len());
+        assert_eq!("a { e: f; }", rules[0].to_string());
+    }
+
+    #[test]
+    fn at_rule_statement() {
+        let mut parser = Parser::new("@charset \"utf-8\";".chars());
+        assert_eq_ignoring_spans(
+            Ok(GenericAtRule {
+                name: "charset".to_owned(),
+                prelude: vec![ComponentValue::Token(Token::String("utf-8".to_owned()))],
+                block: None,
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn at_rule_block() {
+        let mut parser = Parser::new("@page :first { margin: 1in; }".chars());
+        let rule: GenericAtRule = parser.parse().unwrap();
+        assert_eq!("page", rule.name);
+        assert_eq!(2, rule.prelude.len());
+        assert!(matches!(
+            rule.block.as_deref(),
+            Some([GenericBlockItem::Declaration(_)])
+        ));
+        assert_eq!("@page :first { margin: 1in; }", rule.to_string());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn at_rule_ends_at_end_of_file() {
+        let mut parser = Parser::new("@namespace svg url(http://www.w3.org/2000/svg)".chars());
+        let rule: GenericAtRule = parser.parse().unwrap();
+        assert_eq!(None, rule.block);
+        assert_eq!(
+            "@namespace svg url(http://www.w3.org/2000/svg);",
+            rule.to_string()
+        );
+    }
+
+    #[test]
+    fn at_rule_unclosed_block() {
+        let mut parser = Parser::new("@page { margin: 0".chars());
+        assert!(parser.parse::<GenericAtRule>().is_err());
+    }
+
+    #[test]
+    fn minify_at_rule() {
+        let mut parser = Parser::new("@property --x { syntax: '<length>' }".chars());
+        let rule: GenericAtRule = parser.parse().unwrap();
+        assert_eq!(
+            "@property --x{syntax:\"<length>\"}",
+            crate::printer::minify(&rule)
+        );
+    }
+
+    #[test]
+    fn test_files() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_files/good");
+
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            let rules = parse(&fs::read_to_string(&path).unwrap()).unwrap();
+
+            for rule in rules {
+                let output = rule.to_string();
+                let reparsed = parse(&output).unwrap_or_else(|error| {
+                    panic!("{} did not round trip: {error}", path.display())
+                });
+                assert_eq_ignoring_spans(vec![rule], reparsed);
+            }
+        }
+    }
+
+    #[test]
+    fn print_minified() {
+        let rules = parse("@media screen { a { color : red ; b { } } }").unwrap();
+        assert_eq!(
+            "@media screen{a{color:red;b{}}}",
+            crate::printer::minify(&rules[0])
+        );
+    }
+}
@@ -1,15 +1,28 @@
+use super::layer_name::LayerName;
 use super::media_query::*;
 use super::url::*;
 use super::*;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct Import {
     pub url: Url,
-    pub media_queries: Vec<MediaQuery>,
+    /// the cascade layer the imported rules are put in
+    pub layer: Option<ImportLayer>,
+    pub media_queries: Vec<Spanned<MediaQuery>>,
+    pub span: Span,
+}
+
+/// The layer of an import, either `layer` for a new anonymous layer or `layer(name)`
+#[derive(Debug, PartialEq, Serialize)]
+pub enum ImportLayer {
+    Anonymous,
+    Named(LayerName),
 }
 
 impl Parsable for Import {
     fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
         match parser.tokens.next() {
             Some(token_at) => match token_at.token {
                 Token::AtKeyword(keyword) if keyword == "import" => {
@@ -19,7 +32,31 @@ impl Parsable for Import {
 
                     parser.optional_whitespace();
 
-                    let media_queries: Vec<MediaQuery> = match parser.tokens.peek() {
+                    let layer = match parser.tokens.peek() {
+                        Some(TokenAt {
+                            token: Token::Identifier(name),
+                            ..
+                        }) if name == "layer" => {
+                            parser.tokens.next();
+                            Some(ImportLayer::Anonymous)
+                        }
+                        Some(TokenAt {
+                            token: Token::Function(name),
+                            ..
+                        }) if name == "layer" => {
+                            parser.tokens.next();
+                            parser.optional_whitespace();
+                            let name = parser.parse()?;
+                            parser.optional_whitespace();
+                            parser.expect(Token::CloseParenthesis())?;
+                            Some(ImportLayer::Named(name))
+                        }
+                        _ => None,
+                    };
+
+                    parser.optional_whitespace();
+
+                    let media_queries: Vec<Spanned<MediaQuery>> = match parser.tokens.peek() {
                         Some(token_at) => match token_at.token {
                             Token::Identifier(_) | Token::OpenParenthesis() => parser.parse()?,
                             Token::Semicolon() => Vec::new(),
@@ -35,7 +72,12 @@ impl Parsable for Import {
 
                     parser.expect(Token::Semicolon())?;
 
-                    Ok(Import { url, media_queries })
+                    Ok(Import {
+                        url,
+                        layer,
+                        media_queries,
+                        span: parser.span_from(start),
+                    })
                 }
                 _ => Err(ParsingError::wrong_token(token_at, "@import")),
             },
@@ -45,19 +87,52 @@ impl Parsable for Import {
     }
 }
 
+impl ToCss for Import {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("@import ")?;
+        printer.print(&self.url)?;
+
+        match &self.layer {
+            Some(ImportLayer::Anonymous) => printer.write_str(" layer")?,
+            Some(ImportLayer::Named(name)) => {
+                printer.write_str(" layer(")?;
+                printer.print(name)?;
+                printer.write_char(')')?;
+            }
+            None => {}
+        }
+
+        if !self.media_queries.is_empty() {
+            printer.write_char(' ')?;
+            printer.print_comma_separated(&self.media_queries)?;
+        }
+
+        printer.write_char(';')
+    }
+}
+
+impl fmt::Display for Import {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::span::assert_eq_ignoring_spans;
 
     #[test]
     fn simple_url() {
         let mut parser = Parser::new("@import url(example.com);".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Import {
                 url: Url("example.com".to_owned()),
-                media_queries: vec![]
+                layer: None,
+                media_queries: vec![],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -66,12 +141,14 @@ mod tests {
     fn extra_whitespace() {
         let mut parser = Parser::new("@import    url(example.com)  ;".chars());
 
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Import {
                 url: Url("example.com".to_owned()),
-                media_queries: vec![]
+                layer: None,
+                media_queries: vec![],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -79,12 +156,14 @@ mod tests {
     #[test]
     fn string_url() {
         let mut parser = Parser::new("@import 'example.com';".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Import {
                 url: Url("example.com".to_owned()),
-                media_queries: vec![]
+                layer: None,
+                media_queries: vec![],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -92,12 +171,14 @@ mod tests {
     #[test]
     fn media_query() {
         let mut parser = Parser::new("@import url(example.com) screen;".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Import {
                 url: Url("example.com".to_owned()),
-                media_queries: vec![MediaQuery::MediaType(MediaType::Screen)]
+                layer: None,
+                media_queries: vec![MediaQuery::MediaType(MediaType::Screen).into()],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -107,21 +188,50 @@ mod tests {
         let mut parser = Parser::new(
             "@import url(example.com) screen and (orientation: landscape), (color);".chars(),
         );
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Import {
                 url: Url("example.com".to_owned()),
+                layer: None,
                 media_queries: vec![
                     MediaQuery::And(
                         Box::new(MediaQuery::MediaType(MediaType::Screen)),
                         Box::new(MediaQuery::MediaFeature(MediaFeature::Orientation(
-                            Orientation::Landscape
-                        )))
-                    ),
-                    MediaQuery::MediaFeature(MediaFeature::Color)
-                ]
+                            Orientation::Landscape,
+                        ))),
+                    )
+                    .into(),
+                    MediaQuery::MediaFeature(MediaFeature::Color).into(),
+                ],
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn layer() {
+        let mut parser = Parser::new("@import url(example.com) layer(base.reset) screen;".chars());
+        assert_eq_ignoring_spans(
+            Ok(Import {
+                url: Url("example.com".to_owned()),
+                layer: Some(ImportLayer::Named(LayerName(vec![
+                    "base".to_owned(),
+                    "reset".to_owned(),
+                ]))),
+                media_queries: vec![MediaQuery::MediaType(MediaType::Screen).into()],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
+
+    #[test]
+    fn anonymous_layer() {
+        let mut parser = Parser::new("@import 'example.com' layer;".chars());
+        let import: Import = parser.parse().unwrap();
+        assert_eq!(Some(ImportLayer::Anonymous), import.layer);
+        assert_eq!("@import url(\"example.com\") layer;", import.to_string());
+    }
 }
@@ -0,0 +1,115 @@
+use super::*;
+
+/// The name of a cascade layer like `components` or `framework.base` for a sub-layer
+///
+/// adapted from https://www.w3.org/TR/css-cascade-5/#layer-names
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct LayerName(pub Vec<String>);
+
+impl CommaSeparated for LayerName {}
+
+impl Parsable for LayerName {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let mut names = Vec::new();
+
+        loop {
+            match parser.tokens.next() {
+                Some(TokenAt {
+                    token: Token::Identifier(name),
+                    ..
+                }) => names.push(name),
+                Some(token_at) => return Err(ParsingError::wrong_token(token_at, "a layer name")),
+                None => return Err(ParsingError::end_of_file("a layer name")),
+            }
+
+            // there can not be whitespace around the `.`
+            match parser.tokens.peek() {
+                Some(TokenAt {
+                    token: Token::Delimiter('.'),
+                    ..
+                }) => {
+                    parser.tokens.next();
+                }
+                _ => return Ok(LayerName(names)),
+            }
+        }
+    }
+}
+
+impl LayerName {
+    /// the layers this one is nested in followed by this layer, so `a.b.c` gives `a`, `a.b`, and `a.b.c`
+    pub fn ancestors_and_self(&self) -> impl Iterator<Item = LayerName> + '_ {
+        (1..=self.0.len()).map(|length| LayerName(self.0[..length].to_vec()))
+    }
+}
+
+impl ToCss for LayerName {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        for (index, name) in self.0.iter().enumerate() {
+            if index > 0 {
+                printer.write_char('.')?;
+            }
+            printer.print_identifier(name)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for LayerName {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layer_name() {
+        let mut parser = Parser::new("base".chars());
+        assert_eq!(Ok(LayerName(vec!["base".to_owned()])), parser.parse());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn sub_layer() {
+        let mut parser = Parser::new("framework.base.reset".chars());
+        assert_eq!(
+            Ok(LayerName(vec![
+                "framework".to_owned(),
+                "base".to_owned(),
+                "reset".to_owned()
+            ])),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn trailing_dot() {
+        let mut parser = Parser::new("framework.".chars());
+        assert!(parser.parse::<LayerName>().is_err());
+    }
+
+    #[test]
+    fn whitespace_around_dot() {
+        let mut parser = Parser::new("framework .base".chars());
+        assert_eq!(Ok(LayerName(vec!["framework".to_owned()])), parser.parse());
+        assert_eq!(
+            Some(Token::Whitespace()),
+            parser.tokens.next().map(|token_at| token_at.token)
+        );
+    }
+
+    #[test]
+    fn ancestors_and_self() {
+        let name = LayerName(vec!["a".to_owned(), "b".to_owned()]);
+        assert_eq!(
+            vec!["a".to_owned(), "a.b".to_owned()],
+            name.ancestors_and_self()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+}
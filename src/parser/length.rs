@@ -9,7 +9,7 @@ mod tests;
 /// Represents a single length value.
 /// There are two types: zero (with no unit), and a number with a unit
 /// Grammar: `<length>`
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
 pub enum Length {
     Zero(),
     Length(f64, LengthUnit),
@@ -17,7 +17,7 @@ pub enum Length {
 
 /// Represents a length unit.
 /// Grammar: `<length-unit>`
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
 pub enum LengthUnit {
     Pixels,             // px
     Centimeters,        // cm
@@ -63,8 +63,8 @@ impl Parsable for Length {
     fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
         match parser.tokens.next() {
             Some(token_at) => match &token_at.token {
-                Token::Number(value) if *value == 0.0 => Ok(Length::Zero()),
-                Token::Dimension(value, unit) => match unit.parse::<LengthUnit>() {
+                Token::Number(value, _) if *value == 0.0 => Ok(Length::Zero()),
+                Token::Dimension(value, _, unit) => match unit.parse::<LengthUnit>() {
                     Ok(unit) => Ok(Length::Length(*value, unit)),
                     Err(()) => Err(ParsingError::wrong_token(
                         token_at,
@@ -77,3 +77,53 @@ impl Parsable for Length {
         }
     }
 }
+
+impl ToCss for LengthUnit {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        use LengthUnit::*;
+
+        printer.write_str(match self {
+            Pixels => "px",
+            Centimeters => "cm",
+            Inches => "in",
+            Points => "pt",
+            FontSize => "em",
+            RootFontSize => "rem",
+            ViewportHeight => "vh",
+            ViewportWidth => "vw",
+            ViewportBlockSize => "vb",
+            ViewportInlineSize => "vi",
+            ViewportMinimum => "vmin",
+            ViewportMaximum => "vmax",
+            ZeroCharacterWidth => "ch",
+        })
+    }
+}
+
+impl fmt::Display for LengthUnit {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl ToCss for Length {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            Length::Zero() => printer.write_str("0"),
+            // a length of zero does not need a unit
+            Length::Length(value, _) if *value == 0.0 && printer.is_minified() => {
+                printer.write_str("0")
+            }
+            Length::Length(value, unit) => {
+                write!(printer, "{value}")?;
+                printer.print(unit)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
@@ -7,14 +7,14 @@ mod side_length {
 
     fn parse_side_length(input: &str) -> Result<Sides<LengthOrPercentage>, ParsingError> {
         let mut parser = Parser::new(input.chars());
-        return parser.parse();
+        parser.parse()
     }
 
     mod unit {
         use super::*;
 
         fn parse_unit(input: &str) -> Result<LengthUnit, ()> {
-            return input.parse::<LengthUnit>();
+            input.parse::<LengthUnit>()
         }
 
         #[test]
@@ -98,7 +98,7 @@ mod side_length {
 
         fn parse_single_length(input: &str) -> Result<Length, ParsingError> {
             let mut parser = Parser::new(input.chars());
-            return parser.parse::<Length>();
+            parser.parse::<Length>()
         }
 
         #[test]
@@ -159,6 +159,7 @@ mod side_length {
         }
 
         #[test]
+        #[allow(clippy::approx_constant)]
         fn positive_float_with_unit() {
             let mut parser = Parser::new("3.14rem".chars());
             let result = parser.parse::<Length>().unwrap();
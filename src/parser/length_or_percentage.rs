@@ -1,11 +1,24 @@
-use super::{length::Length, percentage::Percentage, side::CanStart, *};
+use super::{
+    length::Length,
+    math_function::{CalculationType, MathFunction},
+    percentage::Percentage,
+    side::CanStart,
+    *,
+};
 
 /// Wraps a length or percentage together
-/// Grammar: `<length-or-percentage>
-#[derive(Debug, PartialEq, Clone, Copy)]
+/// Grammar: `<length-or-percentage>`
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub enum LengthOrPercentage {
     Length(Length),
     Percentage(Percentage),
+    /// a math function like `calc(100% - 64px)` that results in a length or percentage
+    MathFunction(Box<MathFunction>),
+}
+
+/// whether a function token starts a math function
+fn is_math_function(name: &str) -> bool {
+    matches!(name, "calc" | "min" | "max" | "clamp")
 }
 
 impl Parsable for LengthOrPercentage {
@@ -13,13 +26,25 @@ impl Parsable for LengthOrPercentage {
         match parser.tokens.peek() {
             Some(token_at) => match token_at.token {
                 // parse length
-                Token::Number(_) | Token::Dimension(_, _) => {
+                Token::Number(_, _) | Token::Dimension(_, _, _) => {
                     Ok(LengthOrPercentage::Length(parser.parse()?))
                 }
 
                 // parse percentage
                 Token::Percentage(_) => Ok(LengthOrPercentage::Percentage(parser.parse()?)),
 
+                // parse math function, it can not result in a plain number
+                Token::Function(ref name) if is_math_function(name) => {
+                    let token_at = token_at.clone();
+                    let function: MathFunction = parser.parse()?;
+
+                    if function.value_type() == Some(CalculationType::Number) {
+                        Err(ParsingError::wrong_token(token_at, "length or percentage"))
+                    } else {
+                        Ok(LengthOrPercentage::MathFunction(Box::new(function)))
+                    }
+                }
+
                 // neither
                 _ => Err(ParsingError::wrong_token(
                     token_at.clone(),
@@ -35,8 +60,24 @@ impl CanStart for LengthOrPercentage {
     fn can_start(token: &Token) -> bool {
         matches!(
             token,
-            Token::Number(_) | Token::Dimension(_, _) | Token::Percentage(_)
-        )
+            Token::Number(_, _) | Token::Dimension(_, _, _) | Token::Percentage(_)
+        ) || matches!(token, Token::Function(name) if is_math_function(name))
+    }
+}
+
+impl ToCss for LengthOrPercentage {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            LengthOrPercentage::Length(length) => printer.print(length),
+            LengthOrPercentage::Percentage(percentage) => printer.print(percentage),
+            LengthOrPercentage::MathFunction(function) => printer.print(function),
+        }
+    }
+}
+
+impl fmt::Display for LengthOrPercentage {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
     }
 }
 
@@ -46,7 +87,7 @@ mod tests {
 
     fn parse_length_or_percentage(input: &str) -> Result<LengthOrPercentage, ParsingError> {
         let mut parser = Parser::new(input.chars());
-        return parser.parse::<LengthOrPercentage>();
+        parser.parse::<LengthOrPercentage>()
     }
 
     #[test]
@@ -75,4 +116,29 @@ mod tests {
             )))
         );
     }
+
+    #[test]
+    fn math_function() {
+        let mut parser = Parser::new("calc(100% - 64px)".chars());
+        let result = parser.parse::<LengthOrPercentage>();
+
+        assert!(matches!(result, Ok(LengthOrPercentage::MathFunction(_))));
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn math_function_resulting_in_number() {
+        assert!(parse_length_or_percentage("calc(2 * 3)").is_err());
+    }
+
+    #[test]
+    fn other_function() {
+        assert!(parse_length_or_percentage("rgb(0, 0, 0)").is_err());
+    }
+
+    #[test]
+    fn serialize_math_function() {
+        let result = parse_length_or_percentage("min( 10px , 5% )").unwrap();
+        assert_eq!("min(10px, 5%)", result.to_string());
+    }
 }
@@ -0,0 +1,653 @@
+use super::{length::Length, percentage::Percentage, *};
+
+/// A math function that calculates a length or percentage
+/// Grammar: `<math-function>`
+///
+/// adapted from https://www.w3.org/TR/css-values-4/#math
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum MathFunction {
+    Calc(Calculation),
+    Min(Vec<Calculation>),
+    Max(Vec<Calculation>),
+    Clamp(Calculation, Calculation, Calculation),
+}
+
+/// An expression inside of a math function
+/// Grammar: `<calc-sum>`
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum Calculation {
+    Number(f64),
+    Length(Length),
+    Percentage(Percentage),
+    MathFunction(Box<MathFunction>),
+    Sum(Box<Calculation>, Box<Calculation>),
+    Difference(Box<Calculation>, Box<Calculation>),
+    Product(Box<Calculation>, Box<Calculation>),
+    Quotient(Box<Calculation>, Box<Calculation>),
+}
+
+/// The type of value a calculation results in
+///
+/// adapted from https://www.w3.org/TR/css-values-4/#calc-type-checking
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum CalculationType {
+    Number,
+    Length,
+    Percentage,
+    /// a mix of lengths and percentages like `100% - 10px`
+    LengthPercentage,
+}
+
+impl CalculationType {
+    /// the type of adding or subtracting two values, or of picking between them with `min()` or `max()`
+    fn add(self, other: Self) -> Option<Self> {
+        use CalculationType::*;
+
+        match (self, other) {
+            _ if self == other => Some(self),
+            (Number, _) | (_, Number) => None,
+            _ => Some(LengthPercentage),
+        }
+    }
+
+    /// the type of multiplying two values, at least one of them has to be a number
+    fn multiply(self, other: Self) -> Option<Self> {
+        match (self, other) {
+            (CalculationType::Number, _) => Some(other),
+            (_, CalculationType::Number) => Some(self),
+            _ => None,
+        }
+    }
+
+    /// the type of dividing two values, the divisor has to be a number
+    fn divide(self, divisor: Self) -> Option<Self> {
+        match divisor {
+            CalculationType::Number => Some(self),
+            _ => None,
+        }
+    }
+
+    /// what the other side of an operator with this type on the left needs to be
+    fn expected_operand(self) -> &'static str {
+        match self {
+            CalculationType::Number => "a number",
+            _ => "a length or percentage",
+        }
+    }
+}
+
+impl MathFunction {
+    /// the type this function results in, `None` if it mixes types that can not be combined
+    pub fn value_type(&self) -> Option<CalculationType> {
+        match self {
+            MathFunction::Calc(calculation) => calculation.value_type(),
+            MathFunction::Min(calculations) | MathFunction::Max(calculations) => {
+                let mut calculations = calculations.iter();
+                let first = calculations.next()?.value_type()?;
+                calculations.try_fold(first, |value_type, calculation| {
+                    value_type.add(calculation.value_type()?)
+                })
+            }
+            MathFunction::Clamp(minimum, value, maximum) => minimum
+                .value_type()?
+                .add(value.value_type()?)?
+                .add(maximum.value_type()?),
+        }
+    }
+}
+
+impl Calculation {
+    /// the type this calculation results in, `None` if it mixes types that can not be combined
+    pub fn value_type(&self) -> Option<CalculationType> {
+        match self {
+            Calculation::Number(_) => Some(CalculationType::Number),
+            Calculation::Length(_) => Some(CalculationType::Length),
+            Calculation::Percentage(_) => Some(CalculationType::Percentage),
+            Calculation::MathFunction(function) => function.value_type(),
+            Calculation::Sum(left, right) | Calculation::Difference(left, right) => {
+                left.value_type()?.add(right.value_type()?)
+            }
+            Calculation::Product(left, right) => left.value_type()?.multiply(right.value_type()?),
+            Calculation::Quotient(left, right) => left.value_type()?.divide(right.value_type()?),
+        }
+    }
+
+    /// how tightly an operator binds, values are never split up so they bind the tightest
+    fn precedence(&self) -> u8 {
+        match self {
+            Calculation::Sum(_, _) | Calculation::Difference(_, _) => 1,
+            Calculation::Product(_, _) | Calculation::Quotient(_, _) => 2,
+            _ => 3,
+        }
+    }
+
+    /// print a calculation that is an operand, adding parentheses if it would otherwise be parsed differently
+    fn operand_to_css(&self, printer: &mut Printer, needs_parentheses: bool) -> fmt::Result {
+        if needs_parentheses {
+            printer.write_char('(')?;
+            printer.print(self)?;
+            printer.write_char(')')
+        } else {
+            printer.print(self)
+        }
+    }
+}
+
+/// parse a calculation and make sure its type can be combined with the calculations before it
+///
+/// `combine` is given the type of the calculation, the error points at where the calculation starts
+fn parse_operand<I: Iterator<Item = char>>(
+    parser: &mut Parser<I>,
+    parse: fn(&mut Parser<I>) -> Result<Calculation, ParsingError>,
+    combine: impl FnOnce(CalculationType) -> Option<CalculationType>,
+    expected: &str,
+) -> Result<(Calculation, CalculationType), ParsingError> {
+    let start = parser.tokens.peek().cloned();
+    let calculation = parse(parser)?;
+
+    match calculation.value_type().and_then(combine) {
+        Some(value_type) => Ok((calculation, value_type)),
+        None => match start {
+            Some(token_at) => Err(ParsingError::wrong_token(token_at, expected)),
+            None => Err(ParsingError::end_of_file(expected)),
+        },
+    }
+}
+
+/// parse a `<calc-sum>` which is products separated by `+` or `-`
+fn parse_sum<I: Iterator<Item = char>>(
+    parser: &mut Parser<I>,
+) -> Result<Calculation, ParsingError> {
+    let (mut sum, mut value_type) = parse_operand(parser, parse_product, Some, "a calculation")?;
+
+    loop {
+        parser.optional_whitespace();
+
+        let operator = match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Delimiter(operator @ ('+' | '-')),
+                ..
+            }) => *operator,
+            _ => break,
+        };
+
+        // `+` and `-` need whitespace on both sides so that they are not confused with the sign of a number
+        let has_whitespace_before = parser.tokens.is_after_whitespace();
+        let token_at = parser.tokens.next().expect("the operator was peeked");
+        if !has_whitespace_before || !is_whitespace_next(parser) {
+            return Err(ParsingError::wrong_token(
+                token_at,
+                &format!("whitespace around {operator}"),
+            ));
+        }
+        parser.optional_whitespace();
+
+        let (right, right_type) = parse_operand(
+            parser,
+            parse_product,
+            |right_type| value_type.add(right_type),
+            value_type.expected_operand(),
+        )?;
+        value_type = right_type;
+
+        sum = match operator {
+            '+' => Calculation::Sum(Box::new(sum), Box::new(right)),
+            _ => Calculation::Difference(Box::new(sum), Box::new(right)),
+        };
+    }
+
+    Ok(sum)
+}
+
+/// parse a `<calc-product>` which is values separated by `*` or `/`
+fn parse_product<I: Iterator<Item = char>>(
+    parser: &mut Parser<I>,
+) -> Result<Calculation, ParsingError> {
+    let (mut product, mut value_type) = parse_operand(parser, parse_value, Some, "a calculation")?;
+
+    loop {
+        parser.optional_whitespace();
+
+        let operator = match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Delimiter(operator @ ('*' | '/')),
+                ..
+            }) => *operator,
+            _ => break,
+        };
+
+        parser.tokens.next();
+        parser.optional_whitespace();
+
+        let (right, right_type) = if operator == '*' {
+            parse_operand(
+                parser,
+                parse_value,
+                |right_type| value_type.multiply(right_type),
+                "a number",
+            )?
+        } else {
+            parse_operand(
+                parser,
+                parse_value,
+                |right_type| value_type.divide(right_type),
+                "a number",
+            )?
+        };
+        value_type = right_type;
+
+        product = match operator {
+            '*' => Calculation::Product(Box::new(product), Box::new(right)),
+            _ => Calculation::Quotient(Box::new(product), Box::new(right)),
+        };
+    }
+
+    Ok(product)
+}
+
+fn is_whitespace_next<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> bool {
+    matches!(
+        parser.tokens.peek(),
+        Some(TokenAt {
+            token: Token::Whitespace(),
+            ..
+        })
+    )
+}
+
+/// parse a `<calc-value>` which is a number, length, percentage, math function, or a sum in parentheses
+fn parse_value<I: Iterator<Item = char>>(
+    parser: &mut Parser<I>,
+) -> Result<Calculation, ParsingError> {
+    const EXPECTED: &str = "a number, length, percentage, math function, or (";
+
+    let Some(token_at) = parser.tokens.peek() else {
+        return Err(ParsingError::end_of_file(EXPECTED));
+    };
+
+    match &token_at.token {
+        Token::Number(value, _) => {
+            let value = *value;
+            parser.tokens.next();
+            Ok(Calculation::Number(value))
+        }
+        Token::Dimension(_, _, _) => Ok(Calculation::Length(parser.parse()?)),
+        Token::Percentage(_) => Ok(Calculation::Percentage(parser.parse()?)),
+        Token::Function(_) => Ok(Calculation::MathFunction(Box::new(parser.parse()?))),
+        Token::OpenParenthesis() => {
+            parser.tokens.next();
+            parser.optional_whitespace();
+            let sum = parse_sum(parser)?;
+            parser.optional_whitespace();
+            parser.expect(Token::CloseParenthesis())?;
+            Ok(sum)
+        }
+        _ => Err(ParsingError::wrong_token(token_at.clone(), EXPECTED)),
+    }
+}
+
+/// parse the comma separated arguments of `min()`, `max()`, or `clamp()` which all need to have compatible types
+fn parse_arguments<I: Iterator<Item = char>>(
+    parser: &mut Parser<I>,
+) -> Result<Vec<Calculation>, ParsingError> {
+    parser.optional_whitespace();
+    let (first, mut value_type) = parse_operand(parser, parse_sum, Some, "a calculation")?;
+    let mut arguments = vec![first];
+
+    loop {
+        parser.optional_whitespace();
+
+        match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Comma(),
+                ..
+            }) => {
+                parser.tokens.next();
+                parser.optional_whitespace();
+            }
+            _ => break,
+        }
+
+        let (argument, argument_type) = parse_operand(
+            parser,
+            parse_sum,
+            |argument_type| value_type.add(argument_type),
+            value_type.expected_operand(),
+        )?;
+        value_type = argument_type;
+        arguments.push(argument);
+    }
+
+    Ok(arguments)
+}
+
+impl Parsable for MathFunction {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        const EXPECTED: &str = "calc(, min(, max(, or clamp(";
+
+        let Some(token_at) = parser.tokens.next() else {
+            return Err(ParsingError::end_of_file(EXPECTED));
+        };
+
+        let function = match &token_at.token {
+            Token::Function(name) if name == "calc" => {
+                parser.optional_whitespace();
+                MathFunction::Calc(parse_sum(parser)?)
+            }
+            Token::Function(name) if name == "min" => MathFunction::Min(parse_arguments(parser)?),
+            Token::Function(name) if name == "max" => MathFunction::Max(parse_arguments(parser)?),
+            Token::Function(name) if name == "clamp" => {
+                let mut arguments = parse_arguments(parser)?.into_iter();
+                match (
+                    arguments.next(),
+                    arguments.next(),
+                    arguments.next(),
+                    arguments.next(),
+                ) {
+                    (Some(minimum), Some(value), Some(maximum), None) => {
+                        MathFunction::Clamp(minimum, value, maximum)
+                    }
+                    _ => return Err(ParsingError::wrong_token(token_at, "three arguments")),
+                }
+            }
+            _ => return Err(ParsingError::wrong_token(token_at, EXPECTED)),
+        };
+
+        parser.optional_whitespace();
+        parser.expect(Token::CloseParenthesis())?;
+
+        Ok(function)
+    }
+}
+
+impl ToCss for MathFunction {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            MathFunction::Calc(calculation) => {
+                printer.write_str("calc(")?;
+                printer.print(calculation)?;
+            }
+            MathFunction::Min(calculations) => {
+                printer.write_str("min(")?;
+                printer.print_comma_separated(calculations)?;
+            }
+            MathFunction::Max(calculations) => {
+                printer.write_str("max(")?;
+                printer.print_comma_separated(calculations)?;
+            }
+            MathFunction::Clamp(minimum, value, maximum) => {
+                printer.write_str("clamp(")?;
+                printer.print(minimum)?;
+                printer.write_char(',')?;
+                printer.print_optional_space()?;
+                printer.print(value)?;
+                printer.write_char(',')?;
+                printer.print_optional_space()?;
+                printer.print(maximum)?;
+            }
+        }
+
+        printer.write_char(')')
+    }
+}
+
+impl fmt::Display for MathFunction {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl ToCss for Calculation {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        let (left, operator, right) = match self {
+            Calculation::Number(value) => return write!(printer, "{value}"),
+            // a zero without a unit would be a number in a calculation
+            Calculation::Length(Length::Length(value, unit)) => {
+                write!(printer, "{value}")?;
+                return printer.print(unit);
+            }
+            Calculation::Length(length) => return printer.print(length),
+            Calculation::Percentage(percentage) => return printer.print(percentage),
+            Calculation::MathFunction(function) => return printer.print(function),
+            Calculation::Sum(left, right) => (left, '+', right),
+            Calculation::Difference(left, right) => (left, '-', right),
+            Calculation::Product(left, right) => (left, '*', right),
+            Calculation::Quotient(left, right) => (left, '/', right),
+        };
+
+        // operators are parsed left to right, so an operand on the right needs
+        // parentheses even if it has the same precedence
+        left.operand_to_css(printer, left.precedence() < self.precedence())?;
+
+        if operator == '+' || operator == '-' {
+            // whitespace around `+` and `-` is required
+            write!(printer, " {operator} ")?;
+        } else {
+            printer.print_optional_space()?;
+            printer.write_char(operator)?;
+            printer.print_optional_space()?;
+        }
+
+        right.operand_to_css(printer, right.precedence() <= self.precedence())
+    }
+}
+
+impl fmt::Display for Calculation {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{length::LengthUnit, *};
+    use crate::span::assert_eq_ignoring_spans;
+
+    fn pixels(value: f64) -> Calculation {
+        Calculation::Length(Length::Length(value, LengthUnit::Pixels))
+    }
+
+    fn percentage(value: f64) -> Calculation {
+        Calculation::Percentage(Percentage(value))
+    }
+
+    #[test]
+    fn calc_length() {
+        let mut parser = Parser::new("calc(10px)".chars());
+        assert_eq!(Ok(MathFunction::Calc(pixels(10.0))), parser.parse());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn calc_difference() {
+        let mut parser = Parser::new("calc(100% - 64px)".chars());
+        assert_eq!(
+            Ok(MathFunction::Calc(Calculation::Difference(
+                Box::new(percentage(100.0)),
+                Box::new(pixels(64.0))
+            ))),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn precedence() {
+        let mut parser = Parser::new("calc(1px + 2px * 3)".chars());
+        assert_eq!(
+            Ok(MathFunction::Calc(Calculation::Sum(
+                Box::new(pixels(1.0)),
+                Box::new(Calculation::Product(
+                    Box::new(pixels(2.0)),
+                    Box::new(Calculation::Number(3.0))
+                ))
+            ))),
+            parser.parse()
+        );
+    }
+
+    #[test]
+    fn left_associative() {
+        let mut parser = Parser::new("calc(1px - 2px - 3px)".chars());
+        assert_eq!(
+            Ok(MathFunction::Calc(Calculation::Difference(
+                Box::new(Calculation::Difference(
+                    Box::new(pixels(1.0)),
+                    Box::new(pixels(2.0))
+                )),
+                Box::new(pixels(3.0))
+            ))),
+            parser.parse()
+        );
+    }
+
+    #[test]
+    fn parentheses() {
+        let mut parser = Parser::new("calc( ( 1px + 2px ) / 2 )".chars());
+        assert_eq!(
+            Ok(MathFunction::Calc(Calculation::Quotient(
+                Box::new(Calculation::Sum(
+                    Box::new(pixels(1.0)),
+                    Box::new(pixels(2.0))
+                )),
+                Box::new(Calculation::Number(2.0))
+            ))),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn nested_functions() {
+        let mut parser = Parser::new("calc(max(100vh, 100%) - 64px)".chars());
+        assert_eq!(
+            Ok(MathFunction::Calc(Calculation::Difference(
+                Box::new(Calculation::MathFunction(Box::new(MathFunction::Max(
+                    vec![
+                        Calculation::Length(Length::Length(100.0, LengthUnit::ViewportHeight)),
+                        percentage(100.0)
+                    ]
+                )))),
+                Box::new(pixels(64.0))
+            ))),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn min() {
+        let mut parser = Parser::new("min(10px,5%)".chars());
+        assert_eq!(
+            Ok(MathFunction::Min(vec![pixels(10.0), percentage(5.0)])),
+            parser.parse()
+        );
+    }
+
+    #[test]
+    fn clamp() {
+        let mut parser = Parser::new("clamp(1rem, 2.5vw, 2rem)".chars());
+        assert_eq!(
+            Ok(MathFunction::Clamp(
+                Calculation::Length(Length::Length(1.0, LengthUnit::RootFontSize)),
+                Calculation::Length(Length::Length(2.5, LengthUnit::ViewportWidth)),
+                Calculation::Length(Length::Length(2.0, LengthUnit::RootFontSize))
+            )),
+            parser.parse()
+        );
+    }
+
+    #[test]
+    fn clamp_with_two_arguments() {
+        let mut parser = Parser::new("clamp(1rem, 2rem)".chars());
+        assert!(parser.parse::<MathFunction>().is_err());
+    }
+
+    #[test]
+    fn operator_without_whitespace() {
+        let mut parser = Parser::new("calc(1px -2px)".chars());
+        assert!(parser.parse::<MathFunction>().is_err());
+    }
+
+    #[test]
+    fn operator_without_whitespace_after() {
+        let mut parser = Parser::new("calc(1px +(2px))".chars());
+        assert!(parser.parse::<MathFunction>().is_err());
+    }
+
+    #[test]
+    fn adding_number_to_length() {
+        let mut parser = Parser::new("calc(10px + 5)".chars());
+        assert_eq_ignoring_spans(
+            Err(ParsingError::WrongToken {
+                line: 0,
+                column: 12,
+                expected: "a length or percentage".to_owned(),
+                found: "the number 5".to_owned(),
+                span: Span::EMPTY,
+            }),
+            parser.parse::<MathFunction>(),
+        );
+    }
+
+    #[test]
+    fn multiplying_lengths() {
+        let mut parser = Parser::new("calc(10px * 5px)".chars());
+        assert!(parser.parse::<MathFunction>().is_err());
+    }
+
+    #[test]
+    fn dividing_by_length() {
+        let mut parser = Parser::new("calc(10 / 5px)".chars());
+        assert!(parser.parse::<MathFunction>().is_err());
+    }
+
+    #[test]
+    fn mixed_min_arguments() {
+        let mut parser = Parser::new("min(10px, 2)".chars());
+        assert!(parser.parse::<MathFunction>().is_err());
+    }
+
+    #[test]
+    fn unknown_function() {
+        let mut parser = Parser::new("sin(10px)".chars());
+        assert!(parser.parse::<MathFunction>().is_err());
+    }
+
+    #[test]
+    fn value_type() {
+        let mut parser = Parser::new("calc((100% - 10px) * 2)".chars());
+        let function: MathFunction = parser.parse().unwrap();
+        assert_eq!(
+            Some(CalculationType::LengthPercentage),
+            function.value_type()
+        );
+
+        let mut parser = Parser::new("calc(2 * 3)".chars());
+        let function: MathFunction = parser.parse().unwrap();
+        assert_eq!(Some(CalculationType::Number), function.value_type());
+    }
+
+    #[test]
+    fn serialize_parentheses() {
+        for input in [
+            "calc((1px + 2px) * 3)",
+            "calc(1px - (2px - 3px))",
+            "calc(1px - 2px - 3px)",
+            "calc(10px / (2 * 3))",
+            "calc(1px + 2px * 3)",
+            "min(10px, max(5%, 1em))",
+            "clamp(1rem, 2.5vw + 1px, 2rem)",
+        ] {
+            let mut parser = Parser::new(input.chars());
+            assert_eq!(input, parser.parse::<MathFunction>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn minify() {
+        let mut parser = Parser::new("calc( (1px + 2px) * 3 )".chars());
+        let function: MathFunction = parser.parse().unwrap();
+        assert_eq!("calc((1px + 2px)*3)", crate::printer::minify(&function));
+    }
+}
@@ -7,9 +7,8 @@ mod vec;
 
 pub use media_feature::*;
 pub use media_type::*;
-pub use vec::*;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum MediaQuery {
     MediaType(MediaType),
     MediaFeature(MediaFeature),
@@ -83,6 +82,55 @@ impl Parsable for MediaQuery {
     }
 }
 
+impl MediaQuery {
+    /// print a query that is on the left side of `and` or `or`
+    ///
+    /// since `and`, `or`, and `not` take everything to their right the left side needs parentheses if it is one of them
+    fn left_operand_to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            MediaQuery::Not(_) | MediaQuery::And(_, _) | MediaQuery::Or(_, _) => {
+                printer.write_char('(')?;
+                printer.print(self)?;
+                printer.write_char(')')
+            }
+            _ => printer.print(self),
+        }
+    }
+}
+
+impl ToCss for MediaQuery {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            MediaQuery::MediaType(media_type) => printer.print(media_type),
+            MediaQuery::MediaFeature(media_feature) => {
+                printer.write_char('(')?;
+                printer.print(media_feature)?;
+                printer.write_char(')')
+            }
+            MediaQuery::Not(query) => {
+                printer.write_str("not ")?;
+                printer.print(query)
+            }
+            MediaQuery::And(left, right) => {
+                left.left_operand_to_css(printer)?;
+                printer.write_str(" and ")?;
+                printer.print(right)
+            }
+            MediaQuery::Or(left, right) => {
+                left.left_operand_to_css(printer)?;
+                printer.write_str(" or ")?;
+                printer.print(right)
+            }
+        }
+    }
+}
+
+impl fmt::Display for MediaQuery {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -228,4 +276,25 @@ mod tests {
         );
         assert_eq!(None, parser.tokens.next());
     }
+
+    #[test]
+    fn serialize_and() {
+        let mut parser = Parser::new("screen and (min-width:100px)".chars());
+        assert_eq!(
+            "screen and (min-width: 100px)",
+            parser.parse::<MediaQuery>().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn serialize_nested_left_operand() {
+        let query = MediaQuery::Or(
+            Box::new(MediaQuery::And(
+                Box::new(MediaQuery::MediaType(MediaType::Screen)),
+                Box::new(MediaQuery::MediaFeature(MediaFeature::Color)),
+            )),
+            Box::new(MediaQuery::MediaType(MediaType::Print)),
+        );
+        assert_eq!("(screen and (color)) or print", query.to_string());
+    }
 }
@@ -12,7 +12,7 @@ pub use hover::Hover;
 pub use orientation::Orientation;
 pub use pointer::Pointer;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum MediaFeature {
     Color,
     Monochrome,
@@ -117,6 +117,40 @@ impl Parsable for MediaFeature {
     }
 }
 
+impl ToCss for MediaFeature {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        let (name, value): (&str, &dyn ToCss) = match self {
+            MediaFeature::Color => return printer.write_str("color"),
+            MediaFeature::Monochrome => return printer.write_str("monochrome"),
+            MediaFeature::MinWidth(length) => ("min-width", length),
+            MediaFeature::Width(length) => ("width", length),
+            MediaFeature::MaxWidth(length) => ("max-width", length),
+            MediaFeature::MinHeight(length) => ("min-height", length),
+            MediaFeature::Height(length) => ("height", length),
+            MediaFeature::MaxHeight(length) => ("max-height", length),
+            MediaFeature::Orientation(orientation) => ("orientation", orientation),
+            MediaFeature::Hover(hover) => ("hover", hover),
+            MediaFeature::AnyHover(hover) => ("any-hover", hover),
+            MediaFeature::Pointer(pointer) => ("pointer", pointer),
+            MediaFeature::AnyPointer(pointer) => ("any-pointer", pointer),
+            MediaFeature::PrefersColorScheme(color_scheme) => {
+                ("prefers-color-scheme", color_scheme)
+            }
+        };
+
+        printer.write_str(name)?;
+        printer.write_char(':')?;
+        printer.print_optional_space()?;
+        printer.print(value)
+    }
+}
+
+impl fmt::Display for MediaFeature {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parser::length::LengthUnit;
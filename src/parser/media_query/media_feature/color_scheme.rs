@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum ColorScheme {
     Light,
     Dark,
@@ -22,6 +22,21 @@ impl FromIdentifier for ColorScheme {
     const EXPECTED: &'static str = "light or dark";
 }
 
+impl ToCss for ColorScheme {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            ColorScheme::Light => "light",
+            ColorScheme::Dark => "dark",
+        })
+    }
+}
+
+impl fmt::Display for ColorScheme {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum Hover {
     Hover,
     None,
@@ -22,6 +22,21 @@ impl FromIdentifier for Hover {
     const EXPECTED: &'static str = "hover or none";
 }
 
+impl ToCss for Hover {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            Hover::Hover => "hover",
+            Hover::None => "none",
+        })
+    }
+}
+
+impl fmt::Display for Hover {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
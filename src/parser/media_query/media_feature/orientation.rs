@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum Orientation {
     Portrait,
     Landscape,
@@ -22,6 +22,21 @@ impl FromIdentifier for Orientation {
     const EXPECTED: &'static str = "portrait or landscape";
 }
 
+impl ToCss for Orientation {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            Orientation::Portrait => "portrait",
+            Orientation::Landscape => "landscape",
+        })
+    }
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
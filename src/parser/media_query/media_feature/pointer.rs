@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum Pointer {
     Fine,
     Coarse,
@@ -24,6 +24,22 @@ impl FromIdentifier for Pointer {
     const EXPECTED: &'static str = "fine, coarse, or none";
 }
 
+impl ToCss for Pointer {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            Pointer::Fine => "fine",
+            Pointer::Coarse => "coarse",
+            Pointer::None => "none",
+        })
+    }
+}
+
+impl fmt::Display for Pointer {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum MediaType {
     All,
     Print,
@@ -24,6 +24,22 @@ impl FromIdentifier for MediaType {
     const EXPECTED: &'static str = "all, print, or screen";
 }
 
+impl ToCss for MediaType {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            MediaType::All => "all",
+            MediaType::Print => "print",
+            MediaType::Screen => "screen",
+        })
+    }
+}
+
+impl fmt::Display for MediaType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
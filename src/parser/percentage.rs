@@ -2,7 +2,7 @@ use super::*;
 
 /// Represents a percentage value
 /// Grammar: `<percentage>`
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
 pub struct Percentage(pub f64);
 
 impl Parsable for Percentage {
@@ -17,6 +17,18 @@ impl Parsable for Percentage {
     }
 }
 
+impl ToCss for Percentage {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        write!(printer, "{}%", self.0)
+    }
+}
+
+impl fmt::Display for Percentage {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,15 +1,33 @@
-use super::{media_query::*, *};
+use super::{generic_rule::GenericAtRule, media_query::*, *};
 
+pub mod container_rule;
+pub mod font_face;
+pub mod keyframes;
+pub mod layer;
 pub mod media_rule;
 pub mod ruleset;
+pub mod supports_rule;
 
+use container_rule::ContainerRule;
+use font_face::FontFace;
+use keyframes::Keyframes;
+use layer::{LayerBlock, LayerStatement};
 use media_rule::MediaRule;
 use ruleset::Ruleset;
+use supports_rule::SupportsRule;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum Rule {
     Ruleset(Ruleset),
     MediaRule(MediaRule),
+    Keyframes(Keyframes),
+    FontFace(FontFace),
+    SupportsRule(SupportsRule),
+    ContainerRule(ContainerRule),
+    LayerStatement(LayerStatement),
+    LayerBlock(LayerBlock),
+    /// any other at-rule, kept as a generic rule
+    Unknown(GenericAtRule),
 }
 
 impl Parsable for Vec<Rule> {
@@ -19,17 +37,64 @@ impl Parsable for Vec<Rule> {
         loop {
             parser.optional_whitespace();
 
+            let is_recovering = parser.is_recovering();
+
             match parser.tokens.peek() {
                 Some(token_at) => match &token_at.token {
                     Token::AtKeyword(keyword) if keyword == "media" => {
-                        rules.push(Rule::MediaRule(parser.parse()?));
+                        if let Some(media_rule) = parser.parse_rule(true)? {
+                            rules.push(Rule::MediaRule(media_rule));
+                        }
+                    }
+                    Token::AtKeyword(keyword) if keyword == "supports" => {
+                        if let Some(supports_rule) = parser.parse_rule(true)? {
+                            rules.push(Rule::SupportsRule(supports_rule));
+                        }
+                    }
+                    Token::AtKeyword(keyword) if keyword == "container" => {
+                        if let Some(container_rule) = parser.parse_rule(true)? {
+                            rules.push(Rule::ContainerRule(container_rule));
+                        }
+                    }
+                    Token::AtKeyword(keyword) if keyword == "layer" => {
+                        if parser.is_layer_statement() {
+                            if let Some(statement) = parser.parse_rule(true)? {
+                                rules.push(Rule::LayerStatement(statement));
+                            }
+                        } else if let Some(block) = parser.parse_rule(true)? {
+                            rules.push(Rule::LayerBlock(block));
+                        }
+                    }
+                    Token::AtKeyword(keyword) if keyword == "keyframes" => {
+                        if let Some(keyframes) = parser.parse_rule(true)? {
+                            rules.push(Rule::Keyframes(keyframes));
+                        }
+                    }
+                    Token::AtKeyword(keyword) if keyword == "font-face" => {
+                        if let Some(font_face) = parser.parse_rule(true)? {
+                            rules.push(Rule::FontFace(font_face));
+                        }
+                    }
+                    Token::AtKeyword(_) => {
+                        if let Some(unknown) = parser.parse_rule(true)? {
+                            rules.push(Rule::Unknown(unknown));
+                        }
                     }
                     Token::Identifier(_)
                     | Token::Hash(_, _)
                     | Token::Delimiter('.' | ':' | '*')
                     | Token::OpenSquareBracket()
                     | Token::Colon() => {
-                        rules.push(Rule::Ruleset(parser.parse()?));
+                        if let Some(ruleset) = parser.parse_rule(false)? {
+                            rules.push(Rule::Ruleset(ruleset));
+                        }
+                    }
+                    Token::CloseCurlyBracket() => break,
+                    _ if is_recovering => {
+                        let error = ParsingError::wrong_token(token_at.clone(), "a rule");
+                        parser.recover(error)?;
+                        let depth = parser.tokens.depth();
+                        parser.skip_rule(false, depth);
                     }
                     _ => break,
                 },
@@ -41,9 +106,54 @@ impl Parsable for Vec<Rule> {
     }
 }
 
+impl Rule {
+    /// whether this rule has no declarations in it, such as `a {}` or `@media print { a {} }`
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Rule::Ruleset(ruleset) => ruleset.declarations.is_empty(),
+            Rule::MediaRule(media_rule) => media_rule.rules.iter().all(Rule::is_empty),
+            Rule::SupportsRule(supports_rule) => supports_rule.rules.iter().all(Rule::is_empty),
+            Rule::ContainerRule(container_rule) => container_rule.rules.iter().all(Rule::is_empty),
+            // a named layer sets the order of layers even if nothing is in it
+            Rule::LayerStatement(_) => false,
+            Rule::LayerBlock(block) => {
+                block.name.is_none() && block.rules.iter().all(Rule::is_empty)
+            }
+            // an animation with no keyframes still exists, so it is never left out
+            Rule::Keyframes(_) => false,
+            Rule::FontFace(font_face) => font_face.descriptors.is_empty(),
+            // what an unknown at-rule does is not known, so it is never left out
+            Rule::Unknown(_) => false,
+        }
+    }
+}
+
+impl ToCss for Rule {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            Rule::Ruleset(ruleset) => printer.print(ruleset),
+            Rule::MediaRule(media_rule) => printer.print(media_rule),
+            Rule::SupportsRule(supports_rule) => printer.print(supports_rule),
+            Rule::ContainerRule(container_rule) => printer.print(container_rule),
+            Rule::LayerStatement(statement) => printer.print(statement),
+            Rule::LayerBlock(block) => printer.print(block),
+            Rule::Unknown(unknown) => printer.print(unknown),
+            Rule::Keyframes(keyframes) => printer.print(keyframes),
+            Rule::FontFace(font_face) => printer.print(font_face),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::span::assert_eq_ignoring_spans;
 
     #[test]
     fn empty() {
@@ -55,12 +165,13 @@ mod tests {
     #[test]
     fn media_rule() {
         let mut parser = Parser::new("@media screen { }".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(vec![Rule::MediaRule(MediaRule {
-                media_queries: vec![MediaQuery::MediaType(MediaType::Screen)],
-                rules: vec![]
+                media_queries: vec![MediaQuery::MediaType(MediaType::Screen).into()],
+                rules: vec![],
+                span: Span::EMPTY,
             })]),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -68,21 +179,108 @@ mod tests {
     #[test]
     fn multiple_media_rules() {
         let mut parser = Parser::new("@media screen { } @media print { }".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(vec![
                 Rule::MediaRule(MediaRule {
-                    media_queries: vec![MediaQuery::MediaType(MediaType::Screen)],
-                    rules: vec![]
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Screen).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
                 }),
                 Rule::MediaRule(MediaRule {
-                    media_queries: vec![MediaQuery::MediaType(MediaType::Print)],
-                    rules: vec![]
-                })
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Print).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
+                }),
             ]),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
 
+    #[test]
+    fn recover_from_invalid_ruleset() {
+        let mut parser =
+            Parser::with_error_recovery("@media print { } div > { } @media screen { }".chars());
+        assert_eq_ignoring_spans(
+            Ok(vec![
+                Rule::MediaRule(MediaRule {
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Print).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
+                }),
+                Rule::MediaRule(MediaRule {
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Screen).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
+                }),
+            ]),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+        assert_eq_ignoring_spans(1, parser.errors.unwrap().len());
+    }
+
+    #[test]
+    fn recover_from_invalid_media_rule() {
+        let mut parser =
+            Parser::with_error_recovery("@media tv { div {} } @media screen { }".chars());
+        assert_eq_ignoring_spans(
+            Ok(vec![Rule::MediaRule(MediaRule {
+                media_queries: vec![MediaQuery::MediaType(MediaType::Screen).into()],
+                rules: vec![],
+                span: Span::EMPTY,
+            })]),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+        assert_eq_ignoring_spans(1, parser.errors.unwrap().len());
+    }
+
+    #[test]
+    fn unknown_at_rules() {
+        let mut parser = Parser::new(
+            "@charset \"utf-8\"; @page { margin: 0 } @media screen { @font-feature-values Font { } }"
+                .chars(),
+        );
+        let rules: Vec<Rule> = parser.parse().unwrap();
+        assert!(matches!(
+            rules.as_slice(),
+            [Rule::Unknown(_), Rule::Unknown(_), Rule::MediaRule(MediaRule { rules, .. })]
+                if matches!(rules.as_slice(), [Rule::Unknown(_)])
+        ));
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn recover_inside_media_rule() {
+        let mut parser =
+            Parser::with_error_recovery("@media screen { 123 {} @media print { } }".chars());
+        assert_eq_ignoring_spans(
+            Ok(vec![Rule::MediaRule(MediaRule {
+                media_queries: vec![MediaQuery::MediaType(MediaType::Screen).into()],
+                rules: vec![Rule::MediaRule(MediaRule {
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Print).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
+                })],
+                span: Span::EMPTY,
+            })]),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+        assert_eq_ignoring_spans(1, parser.errors.unwrap().len());
+    }
+
+    #[test]
+    fn keyframes() {
+        let mut parser = Parser::new("@keyframes spin { } @media print { }".chars());
+        let rules: Vec<Rule> = parser.parse().unwrap();
+        assert!(matches!(
+            rules.as_slice(),
+            [Rule::Keyframes(Keyframes { name, .. }), Rule::MediaRule(_)] if name == "spin"
+        ));
+        assert_eq!(None, parser.tokens.next());
+    }
+
     // TODO: Add tests for rulesets
 }
@@ -0,0 +1,152 @@
+use super::{container_condition::ContainerCondition, *};
+
+/// Rules that only apply if a container is a certain size like `@container card (width > 400px) { a {} }`
+///
+/// adapted from https://www.w3.org/TR/css-contain-3/#container-rule
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ContainerRule {
+    /// the `container-name` of the container to test, or the nearest container if it is `None`
+    pub name: Option<String>,
+    pub condition: ContainerCondition,
+    pub rules: Vec<Rule>,
+    pub span: Span,
+}
+
+impl Parsable for ContainerRule {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
+        parser.expect(Token::AtKeyword("container".to_owned()))?;
+        parser.optional_whitespace();
+
+        let name = match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Identifier(name),
+                ..
+            }) if !name.eq_ignore_ascii_case("not") => {
+                let token_at = parser.tokens.next().expect("the name was peeked");
+                let Token::Identifier(name) = &token_at.token else {
+                    unreachable!("the name was peeked");
+                };
+
+                // these would be ambiguous with the condition or `container-name: none`
+                if ["none", "and", "or"]
+                    .iter()
+                    .any(|keyword| name.eq_ignore_ascii_case(keyword))
+                {
+                    return Err(ParsingError::wrong_token(token_at, "a container name"));
+                }
+
+                let name = name.clone();
+                parser.optional_whitespace();
+                Some(name)
+            }
+            _ => None,
+        };
+
+        let condition = parser.parse()?;
+
+        parser.optional_whitespace();
+        parser.expect(Token::OpenCurlyBracket())?;
+        parser.optional_whitespace();
+
+        let rules: Vec<Rule> = parser.parse()?;
+
+        parser.optional_whitespace();
+        parser.expect_end_of_block()?;
+
+        Ok(ContainerRule {
+            name,
+            condition,
+            rules,
+            span: parser.span_from(start),
+        })
+    }
+}
+
+impl ToCss for ContainerRule {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("@container ")?;
+
+        if let Some(name) = &self.name {
+            printer.print_identifier(name)?;
+            printer.write_char(' ')?;
+        }
+
+        printer.print(&self.condition)?;
+
+        printer.print_rule_block(&self.rules)
+    }
+}
+
+impl fmt::Display for ContainerRule {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::container_condition::SizeFeature;
+    use super::length::{Length, LengthUnit};
+    use super::*;
+    use crate::span::assert_eq_ignoring_spans;
+
+    #[test]
+    fn container_rule() {
+        let mut parser =
+            Parser::new("@container card (min-width: 400px) { @media print {} }".chars());
+
+        assert_eq_ignoring_spans(
+            Ok(ContainerRule {
+                name: Some("card".to_owned()),
+                condition: ContainerCondition::SizeFeature(SizeFeature::MediaFeature(
+                    MediaFeature::MinWidth(Length::Length(400.0, LengthUnit::Pixels)),
+                )),
+                rules: vec![Rule::MediaRule(MediaRule {
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Print).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
+                })],
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn without_name() {
+        let mut parser = Parser::new("@container not (width < 10em) {}".chars());
+        let container_rule: ContainerRule = parser.parse().unwrap();
+        assert_eq!(None, container_rule.name);
+        assert_eq!(
+            "@container not (width < 10em) {}",
+            container_rule.to_string()
+        );
+    }
+
+    #[test]
+    fn reserved_name() {
+        let mut parser = Parser::new("@container none (width > 0) {}".chars());
+        assert!(parser.parse::<ContainerRule>().is_err());
+    }
+
+    #[test]
+    fn no_condition() {
+        let mut parser = Parser::new("@container card {}".chars());
+        assert!(parser.parse::<ContainerRule>().is_err());
+    }
+
+    #[test]
+    fn minify() {
+        let mut parser =
+            Parser::new("@container card (width > 400px) { a {} b { color: red } }".chars());
+        let container_rule: ContainerRule = parser.parse().unwrap();
+        assert_eq!(
+            "@container card (width>400px){b{color:red}}",
+            crate::printer::minify(&container_rule)
+        );
+    }
+}
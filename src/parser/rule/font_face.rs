@@ -0,0 +1,612 @@
+use super::{
+    color::parse_num, component_value::ComponentValue, declaration::parse_declaration_list,
+    font_family::FontName, unicode_range::UnicodeRange, url::Url, *,
+};
+
+/// A font that can be downloaded like `@font-face { font-family: Inter; src: url(inter.woff2) }`
+///
+/// adapted from https://www.w3.org/TR/css-fonts-4/#font-face-rule
+#[derive(Debug, PartialEq, Serialize)]
+pub struct FontFace {
+    pub descriptors: Vec<Spanned<FontFaceDescriptor>>,
+    pub span: Span,
+}
+
+/// The declarations allowed inside of `@font-face`
+#[derive(Debug, PartialEq, Serialize)]
+pub enum FontFaceDescriptor {
+    FontFamily(FontName),
+    Source(Vec<FontSource>),
+    /// a single weight or a range of weights for variable fonts like `100 900`
+    FontWeight(FontWeight, Option<FontWeight>),
+    FontStyle(FontStyle),
+    FontDisplay(FontDisplay),
+    UnicodeRange(Vec<UnicodeRange>),
+    /// a descriptor that is not supported like `size-adjust`, its value is kept as it was written
+    ///
+    /// a strict parser returns an error for these instead
+    Unknown {
+        name: String,
+        value: Vec<ComponentValue>,
+    },
+}
+
+/// Where to load a font from
+#[derive(Debug, PartialEq, Serialize)]
+pub enum FontSource {
+    /// a font file, the format it is in, and the font technologies it needs like
+    /// `url(inter.woff2) format("woff2") tech(variations)`
+    Url(Url, Option<String>, Vec<String>),
+    /// a font that is installed on the user's computer like `local("Inter")`
+    Local(FontName),
+}
+
+/// How bold a font is
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+pub enum FontWeight {
+    Normal,
+    Bold,
+    /// a weight between 1 and 1000
+    Number(f64),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum FontStyle {
+    Normal,
+    Italic,
+    Oblique,
+}
+
+/// How a font is shown while it is loading
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum FontDisplay {
+    Auto,
+    Block,
+    Swap,
+    Fallback,
+    Optional,
+}
+
+impl Parsable for FontFace {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
+        parser.expect(Token::AtKeyword("font-face".to_owned()))?;
+        parser.optional_whitespace();
+        parser.expect(Token::OpenCurlyBracket())?;
+
+        let descriptors = parser.parse()?;
+
+        parser.optional_whitespace();
+        parser.expect_end_of_block()?;
+
+        Ok(FontFace {
+            descriptors,
+            span: parser.span_from(start),
+        })
+    }
+}
+
+impl Parsable for Vec<Spanned<FontFaceDescriptor>> {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        parse_declaration_list(parser)
+    }
+}
+
+impl Parsable for FontFaceDescriptor {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        const EXPECTED: &str = "a font face descriptor";
+
+        let token_at = match parser.tokens.next() {
+            Some(token_at) => token_at,
+            None => return Err(ParsingError::end_of_file(EXPECTED)),
+        };
+
+        let parse_value: fn(&mut Parser<I>) -> Result<FontFaceDescriptor, ParsingError> =
+            match &token_at.token {
+                Token::Identifier(name) => match name.to_ascii_lowercase().as_str() {
+                    "font-family" => |parser| Ok(FontFaceDescriptor::FontFamily(parser.parse()?)),
+                    "src" => |parser| Ok(FontFaceDescriptor::Source(parser.parse()?)),
+                    "font-weight" => |parser| {
+                        let weight = parser.parse()?;
+                        parser.optional_whitespace();
+
+                        let is_range = matches!(
+                            parser.tokens.peek(),
+                            Some(TokenAt {
+                                token: Token::Number(_, _) | Token::Identifier(_),
+                                ..
+                            })
+                        );
+
+                        let maximum = if is_range {
+                            Some(parser.parse()?)
+                        } else {
+                            None
+                        };
+
+                        Ok(FontFaceDescriptor::FontWeight(weight, maximum))
+                    },
+                    "font-style" => |parser| Ok(FontFaceDescriptor::FontStyle(parser.parse()?)),
+                    "font-display" => |parser| Ok(FontFaceDescriptor::FontDisplay(parser.parse()?)),
+                    "unicode-range" => {
+                        |parser| Ok(FontFaceDescriptor::UnicodeRange(parser.parse()?))
+                    }
+                    _ if parser.is_strict => {
+                        return Err(ParsingError::wrong_token(token_at, EXPECTED))
+                    }
+                    _ => {
+                        let name = name.clone();
+                        parser.consume_colon_separator()?;
+                        return Ok(FontFaceDescriptor::Unknown {
+                            name,
+                            value: parser.parse_unknown_value()?,
+                        });
+                    }
+                },
+                _ => return Err(ParsingError::wrong_token(token_at, EXPECTED)),
+            };
+
+        parser.consume_colon_separator()?;
+        parse_value(parser)
+    }
+}
+
+impl FontFaceDescriptor {
+    /// the name of the descriptor like `src`
+    pub fn name(&self) -> &str {
+        match self {
+            FontFaceDescriptor::FontFamily(_) => "font-family",
+            FontFaceDescriptor::Source(_) => "src",
+            FontFaceDescriptor::FontWeight(_, _) => "font-weight",
+            FontFaceDescriptor::FontStyle(_) => "font-style",
+            FontFaceDescriptor::FontDisplay(_) => "font-display",
+            FontFaceDescriptor::UnicodeRange(_) => "unicode-range",
+            FontFaceDescriptor::Unknown { name, .. } => name,
+        }
+    }
+}
+
+impl CommaSeparated for FontSource {}
+
+impl Parsable for FontSource {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Function(name),
+                ..
+            }) if name == "local" => {
+                parser.tokens.next();
+                parser.optional_whitespace();
+                let font_name = parser.parse()?;
+                parser.optional_whitespace();
+                parser.expect(Token::CloseParenthesis())?;
+                return Ok(FontSource::Local(font_name));
+            }
+            None => return Err(ParsingError::end_of_file("a url or local(")),
+            _ => {}
+        }
+
+        let url = parser.parse()?;
+        parser.optional_whitespace();
+
+        let format = match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Function(name),
+                ..
+            }) if name == "format" => {
+                parser.tokens.next();
+                parser.optional_whitespace();
+
+                let format = match parser.tokens.next() {
+                    Some(TokenAt {
+                        token: Token::String(format) | Token::Identifier(format),
+                        ..
+                    }) => format,
+                    Some(token_at) => {
+                        return Err(ParsingError::wrong_token(token_at, "a font format"))
+                    }
+                    None => return Err(ParsingError::end_of_file("a font format")),
+                };
+
+                parser.optional_whitespace();
+                parser.expect(Token::CloseParenthesis())?;
+                Some(format)
+            }
+            _ => None,
+        };
+
+        parser.optional_whitespace();
+
+        let technologies = match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Function(name),
+                ..
+            }) if name == "tech" => {
+                parser.tokens.next();
+                parser.optional_whitespace();
+                let mut technologies = Vec::new();
+
+                loop {
+                    match parser.tokens.next() {
+                        Some(TokenAt {
+                            token: Token::Identifier(technology),
+                            ..
+                        }) => technologies.push(technology),
+                        Some(token_at) => {
+                            return Err(ParsingError::wrong_token(token_at, "a font technology"))
+                        }
+                        None => return Err(ParsingError::end_of_file("a font technology")),
+                    }
+
+                    parser.optional_whitespace();
+
+                    match parser.tokens.peek() {
+                        Some(TokenAt {
+                            token: Token::Comma(),
+                            ..
+                        }) => {
+                            parser.tokens.next();
+                            parser.optional_whitespace();
+                        }
+                        _ => break,
+                    }
+                }
+
+                parser.expect(Token::CloseParenthesis())?;
+                technologies
+            }
+            _ => Vec::new(),
+        };
+
+        Ok(FontSource::Url(url, format, technologies))
+    }
+}
+
+impl Parsable for FontWeight {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        const EXPECTED: &str = "normal, bold, or a number between 1 and 1000";
+
+        match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Number(_, _),
+                ..
+            }) => Ok(FontWeight::Number(parse_num(parser, 1.0, 1000.0)?)),
+            Some(_) => {
+                let token_at = parser.tokens.next().expect("the token was peeked");
+                match &token_at.token {
+                    Token::Identifier(name) if name == "normal" => Ok(FontWeight::Normal),
+                    Token::Identifier(name) if name == "bold" => Ok(FontWeight::Bold),
+                    _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+                }
+            }
+            None => Err(ParsingError::end_of_file(EXPECTED)),
+        }
+    }
+}
+
+impl FromStr for FontStyle {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(FontStyle::Normal),
+            "italic" => Ok(FontStyle::Italic),
+            "oblique" => Ok(FontStyle::Oblique),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromIdentifier for FontStyle {
+    const EXPECTED: &'static str = "normal, italic, or oblique";
+}
+
+impl FromStr for FontDisplay {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(FontDisplay::Auto),
+            "block" => Ok(FontDisplay::Block),
+            "swap" => Ok(FontDisplay::Swap),
+            "fallback" => Ok(FontDisplay::Fallback),
+            "optional" => Ok(FontDisplay::Optional),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromIdentifier for FontDisplay {
+    const EXPECTED: &'static str = "auto, block, swap, fallback, or optional";
+}
+
+impl ToCss for FontFace {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("@font-face")?;
+        printer.print_block(&self.descriptors, ";")
+    }
+}
+
+impl ToCss for FontFaceDescriptor {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.print_identifier(self.name())?;
+        printer.write_char(':')?;
+        printer.print_optional_space()?;
+
+        match self {
+            FontFaceDescriptor::FontFamily(font_name) => printer.print(font_name),
+            FontFaceDescriptor::Source(sources) => printer.print_comma_separated(sources),
+            FontFaceDescriptor::FontWeight(weight, maximum) => {
+                printer.print(weight)?;
+                if let Some(maximum) = maximum {
+                    printer.write_char(' ')?;
+                    printer.print(maximum)?;
+                }
+                Ok(())
+            }
+            FontFaceDescriptor::FontStyle(style) => printer.print(style),
+            FontFaceDescriptor::FontDisplay(display) => printer.print(display),
+            FontFaceDescriptor::UnicodeRange(ranges) => printer.print_comma_separated(ranges),
+            FontFaceDescriptor::Unknown { value, .. } => printer.print_component_values(value),
+        }
+    }
+}
+
+impl ToCss for FontSource {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            FontSource::Url(url, format, technologies) => {
+                printer.print(url)?;
+                if let Some(format) = format {
+                    printer.write_str(" format(")?;
+                    printer.print_string(format)?;
+                    printer.write_char(')')?;
+                }
+                if !technologies.is_empty() {
+                    printer.write_str(" tech(")?;
+                    for (i, technology) in technologies.iter().enumerate() {
+                        if i > 0 {
+                            printer.write_char(',')?;
+                            printer.print_optional_space()?;
+                        }
+                        printer.print_identifier(technology)?;
+                    }
+                    printer.write_char(')')?;
+                }
+                Ok(())
+            }
+            FontSource::Local(font_name) => {
+                printer.write_str("local(")?;
+                printer.print(font_name)?;
+                printer.write_char(')')
+            }
+        }
+    }
+}
+
+/// when minified `normal` and `bold` are written as the numbers `400` and `700` since they are shorter
+impl ToCss for FontWeight {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            FontWeight::Normal if printer.is_minified() => printer.write_str("400"),
+            FontWeight::Normal => printer.write_str("normal"),
+            FontWeight::Bold if printer.is_minified() => printer.write_str("700"),
+            FontWeight::Bold => printer.write_str("bold"),
+            FontWeight::Number(weight) => write!(printer, "{weight}"),
+        }
+    }
+}
+
+impl ToCss for FontStyle {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            FontStyle::Normal => "normal",
+            FontStyle::Italic => "italic",
+            FontStyle::Oblique => "oblique",
+        })
+    }
+}
+
+impl ToCss for FontDisplay {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(match self {
+            FontDisplay::Auto => "auto",
+            FontDisplay::Block => "block",
+            FontDisplay::Swap => "swap",
+            FontDisplay::Fallback => "fallback",
+            FontDisplay::Optional => "optional",
+        })
+    }
+}
+
+impl fmt::Display for FontFace {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for FontFaceDescriptor {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::span::assert_eq_ignoring_spans;
+
+    fn parse_descriptor(input: &str) -> Result<FontFaceDescriptor, ParsingError> {
+        let mut parser = Parser::new(input.chars());
+        parser.parse()
+    }
+
+    #[test]
+    fn font_face() {
+        let mut parser = Parser::new(
+            "@font-face { font-family: Inter; src: url(inter.woff2) format(\"woff2\"); }".chars(),
+        );
+        assert_eq_ignoring_spans(
+            Ok(FontFace {
+                descriptors: vec![
+                    FontFaceDescriptor::FontFamily(FontName::Family("Inter".to_owned())).into(),
+                    FontFaceDescriptor::Source(vec![FontSource::Url(
+                        Url("inter.woff2".to_owned()),
+                        Some("woff2".to_owned()),
+                        vec![],
+                    )])
+                    .into(),
+                ],
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn sources() {
+        assert_eq!(
+            Ok(FontFaceDescriptor::Source(vec![
+                FontSource::Local(FontName::Family("Inter Bold".to_owned())),
+                FontSource::Url(Url("inter.ttf".to_owned()), None, vec![]),
+                FontSource::Url(
+                    Url("inter.woff".to_owned()),
+                    Some("woff".to_owned()),
+                    vec![]
+                ),
+            ])),
+            parse_descriptor(
+                "src: local( Inter Bold ), url('inter.ttf'), url(inter.woff) format(woff)"
+            )
+        );
+    }
+
+    #[test]
+    fn font_weight() {
+        assert_eq!(
+            Ok(FontFaceDescriptor::FontWeight(FontWeight::Bold, None)),
+            parse_descriptor("font-weight: bold")
+        );
+    }
+
+    #[test]
+    fn font_weight_range() {
+        assert_eq!(
+            Ok(FontFaceDescriptor::FontWeight(
+                FontWeight::Number(100.0),
+                Some(FontWeight::Number(900.0))
+            )),
+            parse_descriptor("font-weight: 100 900")
+        );
+    }
+
+    #[test]
+    fn font_weight_out_of_range() {
+        assert!(parse_descriptor("font-weight: 1001").is_err());
+    }
+
+    #[test]
+    fn unicode_range() {
+        assert_eq!(
+            Ok(FontFaceDescriptor::UnicodeRange(vec![
+                UnicodeRange {
+                    start: 0,
+                    end: 0xFF
+                },
+                UnicodeRange {
+                    start: 0x131,
+                    end: 0x131
+                },
+            ])),
+            parse_descriptor("unicode-range: U+0000-00FF, U+0131")
+        );
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(
+            Ok(FontFaceDescriptor::FontStyle(FontStyle::Italic)),
+            parse_descriptor("font-style: italic")
+        );
+        assert_eq!(
+            Ok(FontFaceDescriptor::FontDisplay(FontDisplay::Swap)),
+            parse_descriptor("font-display: swap")
+        );
+    }
+
+    #[test]
+    fn sources_with_technologies() {
+        let input = "src: url(\"a.woff2\") format(\"woff2\") tech(variations, color-COLRv1)";
+        let descriptor = parse_descriptor(input).unwrap();
+        assert_eq!(
+            FontFaceDescriptor::Source(vec![FontSource::Url(
+                Url("a.woff2".to_owned()),
+                Some("woff2".to_owned()),
+                vec!["variations".to_owned(), "color-COLRv1".to_owned()],
+            )]),
+            descriptor
+        );
+        assert_eq!(input, descriptor.to_string());
+    }
+
+    #[test]
+    fn names_ignore_case() {
+        assert_eq!(
+            Ok(FontFaceDescriptor::FontDisplay(FontDisplay::Swap)),
+            parse_descriptor("Font-Display: swap")
+        );
+    }
+
+    #[test]
+    fn unknown_descriptor() {
+        for input in [
+            "font-stretch: 75% 125%",
+            "font-feature-settings: \"liga\" 0",
+            "size-adjust: 90%",
+        ] {
+            let descriptor = parse_descriptor(input).unwrap();
+            assert!(matches!(descriptor, FontFaceDescriptor::Unknown { .. }));
+            assert_eq!(input, descriptor.to_string());
+        }
+    }
+
+    #[test]
+    fn unknown_descriptor_when_strict() {
+        let mut parser = Parser::new("size-adjust: 90%".chars()).strict();
+        assert!(parser.parse::<FontFaceDescriptor>().is_err());
+    }
+
+    #[test]
+    fn unknown_descriptor_without_value() {
+        assert!(parse_descriptor("size-adjust: ;").is_err());
+    }
+
+    #[test]
+    fn recover_from_invalid_descriptor() {
+        let mut parser = Parser::with_error_recovery(
+            "@font-face { font-display: sideways; font-display: swap }".chars(),
+        );
+        let font_face: FontFace = parser.parse().unwrap();
+        assert_eq!(1, font_face.descriptors.len());
+        assert_eq!(1, parser.errors.unwrap().len());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut parser = Parser::new(
+            "@font-face{font-family:\"Open Sans\";src:local(Arial),url(a.woff2)format(woff2);font-weight:normal bold;unicode-range:U+0-7F}"
+                .chars(),
+        );
+        let font_face: FontFace = parser.parse().unwrap();
+        assert_eq!(
+            "@font-face { font-family: Open Sans; src: local(Arial), url(\"a.woff2\") format(\"woff2\"); font-weight: normal bold; unicode-range: U+0-7F; }",
+            font_face.to_string()
+        );
+        assert_eq!(
+            "@font-face{font-family:Open Sans;src:local(Arial),url(\"a.woff2\") format(\"woff2\");font-weight:400 700;unicode-range:U+0-7F}",
+            crate::printer::minify(&font_face)
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use super::{declaration::DeclarationItem, percentage::Percentage, *};
+
+/// An animation's keyframes like `@keyframes fade { from { opacity: 0 } to { opacity: 1 } }`
+///
+/// adapted from https://www.w3.org/TR/css-animations-1/#keyframes
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Keyframes {
+    /// the name that `animation-name` refers to
+    pub name: String,
+    pub keyframes: Vec<Keyframe>,
+    pub span: Span,
+}
+
+/// A block of declarations for one or more points in an animation like `0%, 50% { opacity: 0 }`
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Keyframe {
+    pub selectors: Vec<KeyframeSelector>,
+    pub declarations: Vec<Spanned<DeclarationItem>>,
+    pub span: Span,
+}
+
+/// A point in an animation, `from` is the same as `0%` and `to` is the same as `100%`
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+pub enum KeyframeSelector {
+    From,
+    To,
+    Percentage(Percentage),
+}
+
+impl Parsable for Keyframes {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
+        parser.expect(Token::AtKeyword("keyframes".to_owned()))?;
+        parser.optional_whitespace();
+
+        let name = match parser.tokens.next() {
+            Some(token_at) => match token_at.token {
+                Token::Identifier(name) if !name.eq_ignore_ascii_case("none") => name,
+                Token::String(name) => name,
+                _ => return Err(ParsingError::wrong_token(token_at, "a keyframes name")),
+            },
+            None => return Err(ParsingError::end_of_file("a keyframes name")),
+        };
+
+        parser.optional_whitespace();
+        parser.expect(Token::OpenCurlyBracket())?;
+
+        let mut keyframes = Vec::new();
+
+        loop {
+            parser.optional_whitespace();
+
+            match parser.tokens.peek() {
+                None
+                | Some(TokenAt {
+                    token: Token::CloseCurlyBracket(),
+                    ..
+                }) => break,
+                _ => {
+                    if let Some(keyframe) = parser.parse_rule(false)? {
+                        keyframes.push(keyframe);
+                    }
+                }
+            }
+        }
+
+        parser.expect_end_of_block()?;
+
+        Ok(Keyframes {
+            name,
+            keyframes,
+            span: parser.span_from(start),
+        })
+    }
+}
+
+impl Parsable for Keyframe {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+        let selectors = parser.parse()?;
+        parser.optional_whitespace();
+        parser.expect(Token::OpenCurlyBracket())?;
+        let declarations = parser.parse()?;
+        parser.expect_end_of_block()?;
+        Ok(Keyframe {
+            selectors,
+            declarations,
+            span: parser.span_from(start),
+        })
+    }
+}
+
+impl CommaSeparated for KeyframeSelector {}
+
+impl Parsable for KeyframeSelector {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        const EXPECTED: &str = "from, to, or a percentage between 0% and 100%";
+
+        match parser.tokens.next() {
+            Some(token_at) => match token_at.token {
+                Token::Identifier(ref name) if name.eq_ignore_ascii_case("from") => {
+                    Ok(KeyframeSelector::From)
+                }
+                Token::Identifier(ref name) if name.eq_ignore_ascii_case("to") => {
+                    Ok(KeyframeSelector::To)
+                }
+                Token::Percentage(value) if (0.0..=100.0).contains(&value) => {
+                    Ok(KeyframeSelector::Percentage(Percentage(value)))
+                }
+                _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+            },
+            None => Err(ParsingError::end_of_file(EXPECTED)),
+        }
+    }
+}
+
+impl ToCss for Keyframes {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("@keyframes ")?;
+        printer.print_custom_identifier(&self.name, &["none"])?;
+        printer.print_block(&self.keyframes, "")
+    }
+}
+
+impl ToCss for Keyframe {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.print_comma_separated(&self.selectors)?;
+        printer.print_block(&self.declarations, ";")
+    }
+}
+
+/// when minified `from` is written as `0%` and `100%` is written as `to` since they are shorter
+impl ToCss for KeyframeSelector {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            KeyframeSelector::From if printer.is_minified() => printer.write_str("0%"),
+            KeyframeSelector::From => printer.write_str("from"),
+            KeyframeSelector::To => printer.write_str("to"),
+            KeyframeSelector::Percentage(Percentage(value))
+                if printer.is_minified() && *value == 100.0 =>
+            {
+                printer.write_str("to")
+            }
+            KeyframeSelector::Percentage(percentage) => printer.print(percentage),
+        }
+    }
+}
+
+impl fmt::Display for Keyframes {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for Keyframe {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for KeyframeSelector {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::declaration::Declaration;
+    use crate::span::assert_eq_ignoring_spans;
+
+    #[test]
+    fn keyframes() {
+        let mut parser = Parser::new(
+            "@keyframes fade { from { opacity: 0 } 50%, 75% {} to { opacity: 1 } }".chars(),
+        );
+        assert_eq_ignoring_spans(
+            Ok(Keyframes {
+                name: "fade".to_owned(),
+                keyframes: vec![
+                    Keyframe {
+                        selectors: vec![KeyframeSelector::From],
+                        declarations: vec![Declaration::Opacity(0.0.into()).into()],
+                        span: Span::EMPTY,
+                    },
+                    Keyframe {
+                        selectors: vec![
+                            KeyframeSelector::Percentage(Percentage(50.0)),
+                            KeyframeSelector::Percentage(Percentage(75.0)),
+                        ],
+                        declarations: vec![],
+                        span: Span::EMPTY,
+                    },
+                    Keyframe {
+                        selectors: vec![KeyframeSelector::To],
+                        declarations: vec![Declaration::Opacity(1.0.into()).into()],
+                        span: Span::EMPTY,
+                    },
+                ],
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn string_name() {
+        let mut parser = Parser::new("@keyframes \"slide in\" {}".chars());
+        let keyframes: Keyframes = parser.parse().unwrap();
+        assert_eq!("slide in", keyframes.name);
+        assert_eq!("@keyframes \"slide in\" {}", keyframes.to_string());
+    }
+
+    #[test]
+    fn keywords_keep_their_quotes() {
+        for input in [
+            "@keyframes \"none\" {}",
+            "@keyframes \"initial\" {}",
+            "@keyframes \"INHERIT\" {}",
+            "@keyframes \"default\" {}",
+        ] {
+            let mut parser = Parser::new(input.chars());
+            assert_eq!(input, parser.parse::<Keyframes>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn none_name() {
+        let mut parser = Parser::new("@keyframes none {}".chars());
+        assert!(parser.parse::<Keyframes>().is_err());
+    }
+
+    #[test]
+    fn missing_name() {
+        let mut parser = Parser::new("@keyframes {}".chars());
+        assert!(parser.parse::<Keyframes>().is_err());
+    }
+
+    #[test]
+    fn percentage_out_of_range() {
+        let mut parser = Parser::new("@keyframes a { 120% {} }".chars());
+        assert!(parser.parse::<Keyframes>().is_err());
+    }
+
+    #[test]
+    fn recover_from_invalid_keyframe() {
+        let mut parser =
+            Parser::with_error_recovery("@keyframes a { 120% {} to { opacity: 1 } }".chars());
+        let keyframes: Keyframes = parser.parse().unwrap();
+        assert_eq!(1, keyframes.keyframes.len());
+        assert_eq!(1, parser.errors.unwrap().len());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut parser = Parser::new("@keyframes fade{from,50%{opacity:0}to{opacity:1}}".chars());
+        let keyframes: Keyframes = parser.parse().unwrap();
+        assert_eq!(
+            "@keyframes fade { from, 50% { opacity: 0; } to { opacity: 1; } }",
+            keyframes.to_string()
+        );
+    }
+
+    #[test]
+    fn minify() {
+        let mut parser =
+            Parser::new("@keyframes fade { from { opacity: 0 } 100% { opacity: 1 } }".chars());
+        let keyframes: Keyframes = parser.parse().unwrap();
+        assert_eq!(
+            "@keyframes fade{0%{opacity:0}to{opacity:1}}",
+            crate::printer::minify(&keyframes)
+        );
+    }
+}
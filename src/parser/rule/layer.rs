@@ -0,0 +1,253 @@
+use super::{layer_name::LayerName, *};
+
+/// Declares the order of cascade layers without adding anything to them like `@layer reset, base;`
+///
+/// adapted from https://www.w3.org/TR/css-cascade-5/#layer-empty
+#[derive(Debug, PartialEq, Serialize)]
+pub struct LayerStatement {
+    pub names: Vec<LayerName>,
+    pub span: Span,
+}
+
+/// Rules that are added to a cascade layer like `@layer base { a {} }`
+///
+/// adapted from https://www.w3.org/TR/css-cascade-5/#layer-block
+#[derive(Debug, PartialEq, Serialize)]
+pub struct LayerBlock {
+    /// `None` for an anonymous layer that can not be added to anywhere else
+    pub name: Option<LayerName>,
+    pub rules: Vec<Rule>,
+    pub span: Span,
+}
+
+impl Parsable for LayerStatement {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
+        parser.expect(Token::AtKeyword("layer".to_owned()))?;
+        parser.optional_whitespace();
+
+        let names = parser.parse()?;
+
+        parser.optional_whitespace();
+        parser.expect(Token::Semicolon())?;
+
+        Ok(LayerStatement {
+            names,
+            span: parser.span_from(start),
+        })
+    }
+}
+
+impl Parsable for LayerBlock {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
+        parser.expect(Token::AtKeyword("layer".to_owned()))?;
+        parser.optional_whitespace();
+
+        let name = match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::OpenCurlyBracket(),
+                ..
+            }) => None,
+            _ => Some(parser.parse()?),
+        };
+
+        parser.optional_whitespace();
+        parser.expect(Token::OpenCurlyBracket())?;
+        parser.optional_whitespace();
+
+        let rules: Vec<Rule> = parser.parse()?;
+
+        parser.optional_whitespace();
+        parser.expect_end_of_block()?;
+
+        Ok(LayerBlock {
+            name,
+            rules,
+            span: parser.span_from(start),
+        })
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// whether the `@layer` that is next ends with a `;` instead of a block, without consuming anything
+    pub(crate) fn is_layer_statement(&mut self) -> bool {
+        for n in 0.. {
+            match self.tokens.peek_nth(n).map(|token_at| &token_at.token) {
+                Some(Token::Semicolon()) => return true,
+                Some(Token::OpenCurlyBracket() | Token::CloseCurlyBracket()) | None => {
+                    return false
+                }
+                Some(_) => {}
+            }
+        }
+
+        unreachable!("the loop only ends by returning")
+    }
+}
+
+/// add a layer and the layers it is nested in to `order` if they are not already in it
+pub(crate) fn declare_layer(name: &LayerName, order: &mut Vec<LayerName>) {
+    for name in name.ancestors_and_self() {
+        if !order.contains(&name) {
+            order.push(name);
+        }
+    }
+}
+
+/// add the names of the layers declared by `rules` to `order` in the order they are declared
+///
+/// `parent` is the layer the rules are in, since a layer inside of `@layer a { }` is a sub-layer of `a`
+pub(crate) fn collect_layer_order(rules: &[Rule], parent: &[String], order: &mut Vec<LayerName>) {
+    let declare = |name: &LayerName, order: &mut Vec<LayerName>| {
+        let full_name = LayerName([parent, &name.0].concat());
+        declare_layer(&full_name, order);
+        full_name
+    };
+
+    for rule in rules {
+        match rule {
+            Rule::LayerStatement(statement) => {
+                for name in &statement.names {
+                    declare(name, order);
+                }
+            }
+            Rule::LayerBlock(LayerBlock {
+                name: Some(name),
+                rules,
+                ..
+            }) => {
+                let full_name = declare(name, order);
+                collect_layer_order(rules, &full_name.0, order);
+            }
+            // the layers in an anonymous layer can not be referred to from outside of it
+            Rule::LayerBlock(LayerBlock { name: None, .. }) => {}
+            Rule::MediaRule(MediaRule { rules, .. })
+            | Rule::SupportsRule(SupportsRule { rules, .. })
+            | Rule::ContainerRule(ContainerRule { rules, .. }) => {
+                collect_layer_order(rules, parent, order)
+            }
+            Rule::Ruleset(_) | Rule::Keyframes(_) | Rule::FontFace(_) | Rule::Unknown(_) => {}
+        }
+    }
+}
+
+impl ToCss for LayerStatement {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("@layer ")?;
+        printer.print_comma_separated(&self.names)?;
+        printer.write_char(';')
+    }
+}
+
+impl ToCss for LayerBlock {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("@layer")?;
+
+        if let Some(name) = &self.name {
+            printer.write_char(' ')?;
+            printer.print(name)?;
+        }
+
+        printer.print_rule_block(&self.rules)
+    }
+}
+
+impl fmt::Display for LayerStatement {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl fmt::Display for LayerBlock {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::span::assert_eq_ignoring_spans;
+
+    fn layer_name(name: &str) -> LayerName {
+        LayerName(name.split('.').map(str::to_owned).collect())
+    }
+
+    #[test]
+    fn statement() {
+        let mut parser = Parser::new("@layer reset, framework.base ;".chars());
+        assert_eq_ignoring_spans(
+            Ok(LayerStatement {
+                names: vec![layer_name("reset"), layer_name("framework.base")],
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn statement_without_names() {
+        let mut parser = Parser::new("@layer;".chars());
+        assert!(parser.parse::<LayerStatement>().is_err());
+    }
+
+    #[test]
+    fn block() {
+        let mut parser = Parser::new("@layer base { @layer reset {} }".chars());
+        assert_eq_ignoring_spans(
+            Ok(LayerBlock {
+                name: Some(layer_name("base")),
+                rules: vec![Rule::LayerBlock(LayerBlock {
+                    name: Some(layer_name("reset")),
+                    rules: vec![],
+                    span: Span::EMPTY,
+                })],
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn anonymous_block() {
+        let mut parser = Parser::new("@layer{}".chars());
+        assert_eq_ignoring_spans(
+            Ok(LayerBlock {
+                name: None,
+                rules: vec![],
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn block_with_multiple_names() {
+        let mut parser = Parser::new("@layer a, b {}".chars());
+        assert!(parser.parse::<LayerBlock>().is_err());
+    }
+
+    #[test]
+    fn is_layer_statement() {
+        assert!(Parser::new("@layer a, b; a {}".chars()).is_layer_statement());
+        assert!(!Parser::new("@layer a { b {} }".chars()).is_layer_statement());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut parser = Parser::new("@layer a,b.c;".chars());
+        let statement: LayerStatement = parser.parse().unwrap();
+        assert_eq!("@layer a, b.c;", statement.to_string());
+        assert_eq!("@layer a,b.c;", crate::printer::minify(&statement));
+
+        let mut parser = Parser::new("@layer { a {} b { color: red } }".chars());
+        let block: LayerBlock = parser.parse().unwrap();
+        assert_eq!("@layer{b{color:red}}", crate::printer::minify(&block));
+    }
+}
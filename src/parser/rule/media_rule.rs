@@ -1,17 +1,20 @@
 use super::*;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct MediaRule {
-    pub media_queries: Vec<MediaQuery>,
+    pub media_queries: Vec<Spanned<MediaQuery>>,
     pub rules: Vec<Rule>,
+    pub span: Span,
 }
 
 impl Parsable for MediaRule {
     fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
         parser.expect(Token::AtKeyword("media".to_owned()))?;
         parser.optional_whitespace();
 
-        let media_queries: Vec<MediaQuery> = parser.parse()?;
+        let media_queries: Vec<Spanned<MediaQuery>> = parser.parse()?;
 
         parser.optional_whitespace();
         parser.expect(Token::OpenCurlyBracket())?;
@@ -20,18 +23,35 @@ impl Parsable for MediaRule {
         let rules: Vec<Rule> = parser.parse()?;
 
         parser.optional_whitespace();
-        parser.expect(Token::CloseCurlyBracket())?;
+        parser.expect_end_of_block()?;
 
         Ok(MediaRule {
             media_queries,
             rules,
+            span: parser.span_from(start),
         })
     }
 }
 
+impl ToCss for MediaRule {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("@media ")?;
+        printer.print_comma_separated(&self.media_queries)?;
+
+        printer.print_rule_block(&self.rules)
+    }
+}
+
+impl fmt::Display for MediaRule {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::span::assert_eq_ignoring_spans;
 
     #[test]
     fn no_queries() {
@@ -43,12 +63,13 @@ mod tests {
     fn one_query() {
         let mut parser = Parser::new("@media (color) {}".chars());
 
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(MediaRule {
-                media_queries: vec![MediaQuery::MediaFeature(MediaFeature::Color)],
-                rules: vec![]
+                media_queries: vec![MediaQuery::MediaFeature(MediaFeature::Color).into()],
+                rules: vec![],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
 
         assert_eq!(None, parser.tokens.next());
@@ -58,15 +79,16 @@ mod tests {
     fn two_queries() {
         let mut parser = Parser::new("@media (color), not screen {}".chars());
 
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(MediaRule {
                 media_queries: vec![
-                    MediaQuery::MediaFeature(MediaFeature::Color),
-                    MediaQuery::Not(Box::new(MediaQuery::MediaType(MediaType::Screen)))
+                    MediaQuery::MediaFeature(MediaFeature::Color).into(),
+                    MediaQuery::Not(Box::new(MediaQuery::MediaType(MediaType::Screen))).into(),
                 ],
-                rules: vec![]
+                rules: vec![],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
 
         assert_eq!(None, parser.tokens.next());
@@ -76,15 +98,17 @@ mod tests {
     fn nested_media_query() {
         let mut parser = Parser::new("@media (color) { @media print {} }".chars());
 
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(MediaRule {
-                media_queries: vec![MediaQuery::MediaFeature(MediaFeature::Color)],
+                media_queries: vec![MediaQuery::MediaFeature(MediaFeature::Color).into()],
                 rules: vec![Rule::MediaRule(MediaRule {
-                    media_queries: vec![MediaQuery::MediaType(MediaType::Print)],
-                    rules: vec![]
-                })]
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Print).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
+                })],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
 
         assert_eq!(None, parser.tokens.next());
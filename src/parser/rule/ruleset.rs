@@ -1,48 +1,67 @@
-use super::declaration::Declaration;
+use super::declaration::DeclarationItem;
 use super::selector::Selector;
 
 use super::*;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct Ruleset {
     pub selectors: Vec<Selector>,
-    pub declarations: Vec<Declaration>,
+    pub declarations: Vec<Spanned<DeclarationItem>>,
+    pub span: Span,
 }
 
 impl Parsable for Ruleset {
     fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
         let selectors = parser.parse()?;
         parser.expect(Token::OpenCurlyBracket())?;
         let declarations = parser.parse()?;
-        parser.expect(Token::CloseCurlyBracket())?;
+        parser.expect_end_of_block()?;
         Ok(Ruleset {
             selectors,
             declarations,
+            span: parser.span_from(start),
         })
     }
 }
 
+impl ToCss for Ruleset {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.print_comma_separated(&self.selectors)?;
+        printer.print_block(&self.declarations, ";")
+    }
+}
+
+impl fmt::Display for Ruleset {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::selector::SelectorRestriction;
     use super::*;
-    use crate::parser::color::Color;
+    use crate::parser::{color::Color, declaration::Declaration};
+    use crate::span::assert_eq_ignoring_spans;
 
     const UNIVERSAL_SELECTOR: Selector = Selector {
         element: None,
         restrictions: vec![],
         combinator: None,
+        span: Span::EMPTY,
     };
 
     #[test]
     fn empty_ruleset() {
         let mut parser = Parser::new("* {}".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Ruleset {
                 selectors: vec![UNIVERSAL_SELECTOR],
-                declarations: vec![]
+                declarations: vec![],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -50,15 +69,16 @@ mod tests {
     #[test]
     fn multiple_declarations() {
         let mut parser = Parser::new("* {background-color: blue; opacity: 0.7}".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Ruleset {
                 selectors: vec![UNIVERSAL_SELECTOR],
                 declarations: vec![
-                    Declaration::BackgroundColor(Color::Blue),
-                    Declaration::Opacity(0.7)
-                ]
+                    Declaration::BackgroundColor(Color::Blue.into()).into(),
+                    Declaration::Opacity(0.7.into()).into(),
+                ],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -66,23 +86,26 @@ mod tests {
     #[test]
     fn multiple_selectors() {
         let mut parser = Parser::new("div, #fab {background-color: blue}".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Ruleset {
                 selectors: vec![
                     Selector {
                         element: Some("div".to_owned()),
                         restrictions: vec![],
-                        combinator: None
+                        combinator: None,
+                        span: Span::EMPTY,
                     },
                     Selector {
                         element: None,
                         restrictions: vec![SelectorRestriction::Id("fab".to_owned())],
-                        combinator: None
-                    }
+                        combinator: None,
+                        span: Span::EMPTY,
+                    },
                 ],
-                declarations: vec![Declaration::BackgroundColor(Color::Blue)]
+                declarations: vec![Declaration::BackgroundColor(Color::Blue.into()).into()],
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -104,4 +127,13 @@ mod tests {
         let mut parser = Parser::new("* {background-color: blue".chars());
         assert!(parser.parse::<Ruleset>().is_err());
     }
+
+    #[test]
+    fn serialize() {
+        let mut parser = Parser::new("h1,h2{color:red;margin:0}".chars());
+        assert_eq!(
+            "h1, h2 { color: red; margin: 0; }",
+            parser.parse::<Ruleset>().unwrap().to_string()
+        );
+    }
 }
@@ -0,0 +1,99 @@
+use super::{supports_condition::SupportsCondition, *};
+
+/// Rules that only apply if the browser supports a feature like `@supports (display: grid) { a {} }`
+///
+/// adapted from https://www.w3.org/TR/css-conditional-3/#at-supports
+#[derive(Debug, PartialEq, Serialize)]
+pub struct SupportsRule {
+    pub condition: SupportsCondition,
+    pub rules: Vec<Rule>,
+    pub span: Span,
+}
+
+impl Parsable for SupportsRule {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
+        parser.expect(Token::AtKeyword("supports".to_owned()))?;
+        parser.optional_whitespace();
+
+        let condition = parser.parse()?;
+
+        parser.optional_whitespace();
+        parser.expect(Token::OpenCurlyBracket())?;
+        parser.optional_whitespace();
+
+        let rules: Vec<Rule> = parser.parse()?;
+
+        parser.optional_whitespace();
+        parser.expect_end_of_block()?;
+
+        Ok(SupportsRule {
+            condition,
+            rules,
+            span: parser.span_from(start),
+        })
+    }
+}
+
+impl ToCss for SupportsRule {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("@supports ")?;
+        printer.print(&self.condition)?;
+
+        printer.print_rule_block(&self.rules)
+    }
+}
+
+impl fmt::Display for SupportsRule {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::component_value::ComponentValue;
+    use super::*;
+    use crate::span::assert_eq_ignoring_spans;
+
+    #[test]
+    fn supports_rule() {
+        let mut parser = Parser::new("@supports (display: grid) { @media print {} }".chars());
+
+        assert_eq_ignoring_spans(
+            Ok(SupportsRule {
+                condition: SupportsCondition::Declaration {
+                    name: "display".to_owned(),
+                    value: vec![ComponentValue::Token(Token::Identifier("grid".to_owned()))],
+                },
+                rules: vec![Rule::MediaRule(MediaRule {
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Print).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
+                })],
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn no_condition() {
+        let mut parser = Parser::new("@supports {}".chars());
+        assert!(parser.parse::<SupportsRule>().is_err());
+    }
+
+    #[test]
+    fn minify() {
+        let mut parser =
+            Parser::new("@supports not (display:grid) { a {} b { color: red } }".chars());
+        let supports_rule: SupportsRule = parser.parse().unwrap();
+        assert_eq!(
+            "@supports not (display:grid){b{color:red}}",
+            crate::printer::minify(&supports_rule)
+        );
+    }
+}
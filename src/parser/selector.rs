@@ -1,32 +1,42 @@
+pub mod an_plus_b;
 pub mod attribute_selector;
 pub mod combinator;
 pub mod pseudo_class;
+pub mod pseudo_element;
+pub mod specificity;
 
 use attribute_selector::AttributeSelector;
 use combinator::Combinator;
 use pseudo_class::PseudoClass;
+use pseudo_element::PseudoElement;
 
 use crate::tokenizer::HashType;
 
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub struct Selector {
     pub element: Option<String>,
     pub restrictions: Vec<SelectorRestriction>,
     pub combinator: Option<Box<Combinator>>,
+    /// this includes the selectors after the combinator
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum SelectorRestriction {
     Id(String),
     Class(String),
     Attribute(AttributeSelector),
     PseudoClass(PseudoClass),
+    /// this can only be followed by pseudo-classes like `::before:hover`
+    PseudoElement(PseudoElement),
 }
 
 impl Parsable for Selector {
     fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+
         let element = match parser.tokens.peek() {
             Some(token_at) => match &token_at.token {
                 Token::Identifier(element_name) => {
@@ -45,7 +55,7 @@ impl Parsable for Selector {
                 _ => {
                     return Err(ParsingError::wrong_token(
                         token_at.clone(),
-                        "*, element, id, class, attribute, or pseudo-class",
+                        "*, element, id, class, attribute, pseudo-class, or pseudo-element",
                     ))
                 }
             },
@@ -55,20 +65,41 @@ impl Parsable for Selector {
         let mut restrictions = Vec::new();
 
         loop {
-            const EXPECTED: &str = "id, class, attribute, or pseudo-class";
+            const EXPECTED: &str = "id, class, attribute, pseudo-class, or pseudo-element";
+
+            let is_pseudo_element = parser.is_pseudo_element_next();
+            let has_pseudo_element = restrictions
+                .iter()
+                .any(|restriction| matches!(restriction, SelectorRestriction::PseudoElement(_)));
 
             match parser.tokens.peek() {
                 Some(token_at) => match &token_at.token {
+                    // only pseudo-classes can come after a pseudo-element
+                    Token::Hash(_, _) | Token::Delimiter('.') | Token::OpenSquareBracket()
+                        if has_pseudo_element =>
+                    {
+                        return Err(ParsingError::wrong_token(
+                            token_at.clone(),
+                            "a pseudo-class",
+                        ));
+                    }
+                    Token::Colon() if has_pseudo_element && is_pseudo_element => {
+                        return Err(ParsingError::wrong_token(
+                            token_at.clone(),
+                            "a pseudo-class",
+                        ));
+                    }
                     Token::Hash(id_name, HashType::Id) => {
                         restrictions.push(SelectorRestriction::Id(id_name.clone()));
                         parser.tokens.next();
                     }
                     Token::Hash(id_name, HashType::Unrestricted) => {
                         return Err(ParsingError::WrongToken {
-                            line: token_at.line,
-                            column: token_at.column,
+                            line: token_at.span.start.line,
+                            column: token_at.span.start.column,
                             expected: "valid id".to_owned(),
                             found: id_name.clone(),
+                            span: token_at.span,
                         });
                     }
                     Token::Delimiter('.') => {
@@ -84,23 +115,28 @@ impl Parsable for Selector {
                     Token::OpenSquareBracket() => {
                         restrictions.push(SelectorRestriction::Attribute(parser.parse()?));
                     }
+                    Token::Colon() if is_pseudo_element => {
+                        restrictions.push(SelectorRestriction::PseudoElement(parser.parse()?));
+                    }
                     Token::Colon() => {
                         restrictions.push(SelectorRestriction::PseudoClass(parser.parse()?));
                     }
                     Token::Delimiter('*') => {
                         return Err(ParsingError::WrongToken {
-                            line: token_at.line,
-                            column: token_at.column,
+                            line: token_at.span.start.line,
+                            column: token_at.span.start.column,
                             expected: EXPECTED.to_owned(),
                             found: "*".to_owned(),
+                            span: token_at.span,
                         });
                     }
                     Token::Identifier(_) => {
                         return Err(ParsingError::WrongToken {
-                            line: token_at.line,
-                            column: token_at.column,
+                            line: token_at.span.start.line,
+                            column: token_at.span.start.column,
                             expected: EXPECTED.to_owned(),
                             found: "element".to_owned(),
+                            span: token_at.span,
                         });
                     }
                     _ => break,
@@ -114,12 +150,37 @@ impl Parsable for Selector {
 
         parser.optional_whitespace();
 
+        // a pseudo-element has to be the last thing in a selector
+        let has_pseudo_element = restrictions
+            .iter()
+            .any(|restriction| matches!(restriction, SelectorRestriction::PseudoElement(_)));
+
         let combinator = match parser.tokens.peek() {
             Some(token_at) => match &token_at.token {
+                Token::Delimiter('>' | '+' | '~') if has_pseudo_element => {
+                    return Err(ParsingError::wrong_token(
+                        token_at.clone(),
+                        "a pseudo-class",
+                    ));
+                }
                 Token::Delimiter('>') | Token::Delimiter('+') | Token::Delimiter('~') => {
                     Some(Box::new(parser.parse()?))
                 }
 
+                Token::Delimiter('*')
+                | Token::Identifier(_)
+                | Token::Hash(_, _)
+                | Token::Delimiter('.')
+                | Token::OpenSquareBracket()
+                | Token::Colon()
+                    if has_whitespace && has_pseudo_element =>
+                {
+                    return Err(ParsingError::wrong_token(
+                        token_at.clone(),
+                        "a pseudo-class",
+                    ));
+                }
+
                 Token::Delimiter('*')
                 | Token::Identifier(_)
                 | Token::Hash(_, _)
@@ -140,26 +201,83 @@ impl Parsable for Selector {
             element,
             restrictions,
             combinator,
+            span: parser.span_from(start),
         })
     }
 }
 
 impl CommaSeparated for Selector {}
 
+impl ToCss for Selector {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match &self.element {
+            Some(element) => printer.print_identifier(element)?,
+            None if self.restrictions.is_empty() => printer.write_char('*')?,
+            None => {}
+        }
+
+        for restriction in &self.restrictions {
+            printer.print(restriction)?;
+        }
+
+        if let Some(combinator) = &self.combinator {
+            match combinator.as_ref() {
+                Combinator::Descendant(_) => printer.write_char(' ')?,
+                _ => printer.print_optional_space()?,
+            }
+            printer.print(combinator)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+impl ToCss for SelectorRestriction {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            SelectorRestriction::Id(id) => {
+                printer.write_char('#')?;
+                printer.print_identifier(id)
+            }
+            SelectorRestriction::Class(class) => {
+                printer.write_char('.')?;
+                printer.print_identifier(class)
+            }
+            SelectorRestriction::Attribute(attribute) => printer.print(attribute),
+            SelectorRestriction::PseudoClass(pseudo_class) => printer.print(pseudo_class),
+            SelectorRestriction::PseudoElement(pseudo_element) => printer.print(pseudo_element),
+        }
+    }
+}
+
+impl fmt::Display for SelectorRestriction {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::span::assert_eq_ignoring_spans;
 
     #[test]
     fn universal() {
         let mut parser = Parser::new("*".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: None,
                 restrictions: vec![],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -167,13 +285,14 @@ mod tests {
     #[test]
     fn element() {
         let mut parser = Parser::new("div".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: Some("div".to_owned()),
                 restrictions: vec![],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -181,13 +300,14 @@ mod tests {
     #[test]
     fn id() {
         let mut parser = Parser::new("#id".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: None,
                 restrictions: vec![SelectorRestriction::Id("id".to_owned())],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -195,13 +315,14 @@ mod tests {
     #[test]
     fn class() {
         let mut parser = Parser::new(".class".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: None,
                 restrictions: vec![SelectorRestriction::Class("class".to_owned())],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -209,15 +330,16 @@ mod tests {
     #[test]
     fn attribute() {
         let mut parser = Parser::new("[key]".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: None,
                 restrictions: vec![SelectorRestriction::Attribute(AttributeSelector::Exists(
-                    "key".to_owned()
+                    "key".to_owned(),
                 ))],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -225,27 +347,86 @@ mod tests {
     #[test]
     fn pseudo_class() {
         let mut parser = Parser::new(":focus".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: None,
                 restrictions: vec![SelectorRestriction::PseudoClass(PseudoClass::Focus)],
                 combinator: None,
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.peek());
+    }
+
+    #[test]
+    fn pseudo_element() {
+        let mut parser = Parser::new("a::before".chars());
+        assert_eq_ignoring_spans(
+            Ok(Selector {
+                element: Some("a".to_owned()),
+                restrictions: vec![SelectorRestriction::PseudoElement(PseudoElement::Before)],
+                combinator: None,
+                span: Span::EMPTY,
+            }),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.peek());
+    }
+
+    #[test]
+    fn legacy_pseudo_element_with_pseudo_class() {
+        let mut parser = Parser::new("a:hover:first-line:focus".chars());
+        assert_eq_ignoring_spans(
+            Ok(Selector {
+                element: Some("a".to_owned()),
+                restrictions: vec![
+                    SelectorRestriction::PseudoClass(PseudoClass::Hover),
+                    SelectorRestriction::PseudoElement(PseudoElement::FirstLine),
+                    SelectorRestriction::PseudoClass(PseudoClass::Focus),
+                ],
+                combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
 
+    #[test]
+    fn class_after_pseudo_element() {
+        let mut parser = Parser::new("a::after.class".chars());
+        assert!(parser.parse::<Selector>().is_err());
+    }
+
+    #[test]
+    fn combinator_after_pseudo_element() {
+        for input in ["a::before .b", "a::before > b", "a:first-line + b"] {
+            let mut parser = Parser::new(input.chars());
+            assert!(
+                parser.parse::<Selector>().is_err(),
+                "{input} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn two_pseudo_elements() {
+        let mut parser = Parser::new("a::before::after".chars());
+        assert!(parser.parse::<Selector>().is_err());
+    }
+
     #[test]
     fn element_with_class() {
         let mut parser = Parser::new("div.class".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: Some("div".to_owned()),
                 restrictions: vec![SelectorRestriction::Class("class".to_owned())],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -253,13 +434,14 @@ mod tests {
     #[test]
     fn universal_with_class() {
         let mut parser = Parser::new("*.class".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: None,
                 restrictions: vec![SelectorRestriction::Class("class".to_owned())],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -267,13 +449,14 @@ mod tests {
     #[test]
     fn element_with_id_and_class() {
         let mut parser = Parser::new("div#id".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: Some("div".to_owned()),
                 restrictions: vec![SelectorRestriction::Id("id".to_owned())],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -281,17 +464,18 @@ mod tests {
     #[test]
     fn three_classes() {
         let mut parser = Parser::new(".class1.class2.class3".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: None,
                 restrictions: vec![
                     SelectorRestriction::Class("class1".to_owned()),
                     SelectorRestriction::Class("class2".to_owned()),
-                    SelectorRestriction::Class("class3".to_owned())
+                    SelectorRestriction::Class("class3".to_owned()),
                 ],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -335,19 +519,21 @@ mod tests {
     #[test]
     fn not() {
         let mut parser = Parser::new(":not(div)".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: None,
-                restrictions: vec![SelectorRestriction::PseudoClass(PseudoClass::Not(
+                restrictions: vec![SelectorRestriction::PseudoClass(PseudoClass::Not(vec![
                     Selector {
                         element: Some("div".to_owned()),
                         restrictions: vec![],
                         combinator: None,
-                    }
-                ))],
+                        span: Span::EMPTY,
+                    },
+                ]))],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -355,19 +541,21 @@ mod tests {
     #[test]
     fn has() {
         let mut parser = Parser::new(":has(div)".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: None,
-                restrictions: vec![SelectorRestriction::PseudoClass(PseudoClass::Has(
+                restrictions: vec![SelectorRestriction::PseudoClass(PseudoClass::Has(vec![
                     Combinator::Descendant(Selector {
                         element: Some("div".to_owned()),
                         restrictions: vec![],
                         combinator: None,
-                    })
-                ))],
+                        span: Span::EMPTY,
+                    }),
+                ]))],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -375,19 +563,21 @@ mod tests {
     #[test]
     fn has_sibling() {
         let mut parser = Parser::new(":has(~ div)".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: None,
-                restrictions: vec![SelectorRestriction::PseudoClass(PseudoClass::Has(
+                restrictions: vec![SelectorRestriction::PseudoClass(PseudoClass::Has(vec![
                     Combinator::GeneralSibling(Selector {
                         element: Some("div".to_owned()),
                         restrictions: vec![],
                         combinator: None,
-                    })
-                ))],
+                        span: Span::EMPTY,
+                    }),
+                ]))],
                 combinator: None,
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -395,7 +585,7 @@ mod tests {
     #[test]
     fn descendant() {
         let mut parser = Parser::new("div span".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: Some("div".to_owned()),
                 restrictions: vec![],
@@ -403,9 +593,11 @@ mod tests {
                     element: Some("span".to_owned()),
                     restrictions: vec![],
                     combinator: None,
+                    span: Span::EMPTY,
                 }))),
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -413,7 +605,7 @@ mod tests {
     #[test]
     fn sibling() {
         let mut parser = Parser::new("div ~ span".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Selector {
                 element: Some("div".to_owned()),
                 restrictions: vec![],
@@ -421,10 +613,58 @@ mod tests {
                     element: Some("span".to_owned()),
                     restrictions: vec![],
                     combinator: None,
+                    span: Span::EMPTY,
                 }))),
+                span: Span::EMPTY,
             }),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
+
+    #[test]
+    fn adjacent_sibling_that_looks_like_a_unicode_range() {
+        let mut parser = Parser::new("u+a {}".chars());
+        let selector: Selector = parser.parse().unwrap();
+        assert_eq!("u + a", selector.to_string());
+
+        let mut parser = Parser::new("ul+u+a".chars());
+        let selector: Selector = parser.parse().unwrap();
+        assert_eq!("ul + u + a", selector.to_string());
+        assert_eq!(None, parser.tokens.peek());
+    }
+
+    #[test]
+    fn serialize_compound() {
+        let mut parser = Parser::new("a#home.nav-link[target=\"_blank\"]:hover".chars());
+        let selector: Selector = parser.parse().unwrap();
+        assert_eq!(
+            "a#home.nav-link[target=\"_blank\"]:hover",
+            selector.to_string()
+        );
+    }
+
+    #[test]
+    fn serialize_universal() {
+        let mut parser = Parser::new("*".chars());
+        assert_eq!("*", parser.parse::<Selector>().unwrap().to_string());
+    }
+
+    #[test]
+    fn serialize_combinators() {
+        let mut parser = Parser::new("ul>li+li~ p:not(.a):has(> img)".chars());
+        assert_eq!(
+            "ul > li + li ~ p:not(.a):has(> img)",
+            parser.parse::<Selector>().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn serialize_escaped_class() {
+        let mut parser = Parser::new(".sm\\:hidden".chars());
+        assert_eq!(
+            ".sm\\:hidden",
+            parser.parse::<Selector>().unwrap().to_string()
+        );
+    }
 }
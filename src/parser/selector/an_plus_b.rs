@@ -0,0 +1,288 @@
+use super::*;
+use crate::tokenizer::Sign;
+
+/// The positions `An+B` matches in pseudo-classes like `:nth-child(2n+1)`, these are every `A`th
+/// element starting from the `B`th one
+///
+/// `odd` and `even` are the same as `2n+1` and `2n`, and are printed that way
+///
+/// adapted from https://www.w3.org/TR/css-syntax-3/#anb-microsyntax
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub struct AnPlusB {
+    pub a: i32,
+    pub b: i32,
+}
+
+const EXPECTED: &str = "odd, even, an integer, or an expression like 2n+1";
+
+/// the value of a number token if it is an integer
+fn integer(token_at: &TokenAt, value: f64) -> Result<i32, ParsingError> {
+    if value.fract() == 0.0 && value.abs() <= i32::MAX as f64 {
+        Ok(value as i32)
+    } else {
+        Err(ParsingError::wrong_token(token_at.clone(), "an integer"))
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// parse what comes after the `n`, `rest` is the part of the token after the `n` like `-1` in `2n-1`
+    ///
+    /// a `B` needs a sign, either written on the number like `2n +1` or as a `+` or `-` before a
+    /// number without one like `2n + 1`
+    fn parse_an_plus_b_end(
+        &mut self,
+        token_at: TokenAt,
+        a: i32,
+        rest: &str,
+    ) -> Result<AnPlusB, ParsingError> {
+        if rest == "-" {
+            self.optional_whitespace();
+            return match self.tokens.next() {
+                Some(token_at) => match token_at.token {
+                    Token::Number(value, Sign::Signless) => Ok(AnPlusB {
+                        a,
+                        b: -integer(&token_at, value)?,
+                    }),
+                    _ => Err(ParsingError::wrong_token(token_at, "an integer")),
+                },
+                None => Err(ParsingError::end_of_file("an integer")),
+            };
+        }
+
+        if let Some(digits) = rest.strip_prefix('-') {
+            return match digits.parse::<i32>() {
+                Ok(b) if digits.bytes().all(|byte| byte.is_ascii_digit()) => {
+                    Ok(AnPlusB { a, b: -b })
+                }
+                _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+            };
+        }
+
+        if !rest.is_empty() {
+            return Err(ParsingError::wrong_token(token_at, EXPECTED));
+        }
+
+        let n = match self.tokens.peek_nth(0).map(|token_at| &token_at.token) {
+            Some(Token::Whitespace()) => 1,
+            _ => 0,
+        };
+
+        match self
+            .tokens
+            .peek_nth(n)
+            .map(|token_at| token_at.token.clone())
+        {
+            Some(Token::Delimiter(sign @ ('+' | '-'))) => {
+                self.optional_whitespace();
+                self.tokens.next();
+                self.optional_whitespace();
+
+                match self.tokens.next() {
+                    Some(token_at) => match token_at.token {
+                        Token::Number(value, Sign::Signless) => {
+                            let b = integer(&token_at, value)?;
+                            Ok(AnPlusB {
+                                a,
+                                b: if sign == '-' { -b } else { b },
+                            })
+                        }
+                        _ => Err(ParsingError::wrong_token(token_at, "an integer")),
+                    },
+                    None => Err(ParsingError::end_of_file("an integer")),
+                }
+            }
+            Some(Token::Number(_, Sign::Signless)) => {
+                self.optional_whitespace();
+                let token_at = self.tokens.next().expect("the token was peeked");
+                Err(ParsingError::wrong_token(
+                    token_at,
+                    "+ or - before the integer",
+                ))
+            }
+            Some(Token::Number(value, _)) => {
+                self.optional_whitespace();
+                let token_at = self.tokens.next().expect("the token was peeked");
+                Ok(AnPlusB {
+                    a,
+                    b: integer(&token_at, value)?,
+                })
+            }
+            _ => Ok(AnPlusB { a, b: 0 }),
+        }
+    }
+}
+
+impl Parsable for AnPlusB {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let Some(mut token_at) = parser.tokens.next() else {
+            return Err(ParsingError::end_of_file(EXPECTED));
+        };
+
+        // a `+` before the `n` like `+n+1` is a token of its own, but it can not be followed by whitespace
+        let has_plus = token_at.token == Token::Delimiter('+');
+        if has_plus {
+            token_at = match parser.tokens.next() {
+                Some(token_at) => token_at,
+                None => return Err(ParsingError::end_of_file(EXPECTED)),
+            };
+        }
+
+        match &token_at.token {
+            Token::Number(value, _) if !has_plus => Ok(AnPlusB {
+                a: 0,
+                b: integer(&token_at, *value)?,
+            }),
+            Token::Dimension(value, _, unit) if !has_plus => {
+                let a = integer(&token_at, *value)?;
+                let unit = unit.to_ascii_lowercase();
+
+                match unit.strip_prefix('n') {
+                    Some(rest) => parser.parse_an_plus_b_end(token_at.clone(), a, rest),
+                    None => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+                }
+            }
+            Token::Identifier(name) => {
+                let name = name.to_ascii_lowercase();
+
+                match name.as_str() {
+                    "odd" if !has_plus => return Ok(AnPlusB { a: 2, b: 1 }),
+                    "even" if !has_plus => return Ok(AnPlusB { a: 2, b: 0 }),
+                    _ => {}
+                }
+
+                let (a, rest) = match name.strip_prefix("-n") {
+                    Some(rest) if !has_plus => (-1, rest),
+                    _ => match name.strip_prefix('n') {
+                        Some(rest) => (1, rest),
+                        None => return Err(ParsingError::wrong_token(token_at, EXPECTED)),
+                    },
+                };
+
+                parser.parse_an_plus_b_end(token_at.clone(), a, rest)
+            }
+            _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+        }
+    }
+}
+
+impl ToCss for AnPlusB {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self.a {
+            0 => return write!(printer, "{}", self.b),
+            1 => printer.write_char('n')?,
+            -1 => printer.write_str("-n")?,
+            a => write!(printer, "{a}n")?,
+        }
+
+        match self.b {
+            0 => Ok(()),
+            b if b > 0 => write!(printer, "+{b}"),
+            b => write!(printer, "{b}"),
+        }
+    }
+}
+
+impl fmt::Display for AnPlusB {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<AnPlusB, ParsingError> {
+        let mut parser = Parser::new(input.chars());
+        let result = parser.parse();
+        assert_eq!(None, parser.tokens.next(), "{input} was not fully parsed");
+        result
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(Ok(AnPlusB { a: 2, b: 1 }), parse("odd"));
+        assert_eq!(Ok(AnPlusB { a: 2, b: 0 }), parse("EVEN"));
+    }
+
+    #[test]
+    fn integer_only() {
+        assert_eq!(Ok(AnPlusB { a: 0, b: 3 }), parse("3"));
+        assert_eq!(Ok(AnPlusB { a: 0, b: -3 }), parse("-3"));
+        assert_eq!(Ok(AnPlusB { a: 0, b: 3 }), parse("+3"));
+    }
+
+    #[test]
+    fn dimensions() {
+        assert_eq!(Ok(AnPlusB { a: 2, b: 0 }), parse("2n"));
+        assert_eq!(Ok(AnPlusB { a: 2, b: 1 }), parse("2n+1"));
+        assert_eq!(Ok(AnPlusB { a: 2, b: -1 }), parse("2n-1"));
+        assert_eq!(Ok(AnPlusB { a: -3, b: 4 }), parse("-3n+4"));
+        assert_eq!(Ok(AnPlusB { a: 2, b: -1 }), parse("2n- 1"));
+        assert_eq!(Ok(AnPlusB { a: 2, b: 1 }), parse("2n + 1"));
+        assert_eq!(Ok(AnPlusB { a: 2, b: -1 }), parse("2N -1"));
+    }
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(Ok(AnPlusB { a: 1, b: 0 }), parse("n"));
+        assert_eq!(Ok(AnPlusB { a: 1, b: 0 }), parse("+n"));
+        assert_eq!(Ok(AnPlusB { a: -1, b: 3 }), parse("-n+3"));
+        assert_eq!(Ok(AnPlusB { a: 1, b: -1 }), parse("n-1"));
+        assert_eq!(Ok(AnPlusB { a: 1, b: -1 }), parse("+n-1"));
+        assert_eq!(Ok(AnPlusB { a: -1, b: -2 }), parse("-n- 2"));
+        assert_eq!(Ok(AnPlusB { a: 1, b: 5 }), parse("n + 5"));
+    }
+
+    #[test]
+    fn invalid() {
+        for input in [
+            "", "1.5", "2x", "+ n", "+-n", "n-a", "2n + -1", "2n + +1", "2n- +1", "n -", "+odd",
+        ] {
+            let mut parser = Parser::new(input.chars());
+            assert!(
+                parser.parse::<AnPlusB>().is_err(),
+                "{input} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn b_needs_a_sign() {
+        assert_eq!(Ok(AnPlusB { a: 2, b: 1 }), parse("2n +1"));
+        assert_eq!(Ok(AnPlusB { a: 2, b: -1 }), parse("2n -1"));
+
+        let mut parser = Parser::new("2n 1".chars());
+        assert!(matches!(
+            parser.parse::<AnPlusB>(),
+            Err(ParsingError::WrongToken { expected, .. }) if expected == "+ or - before the integer"
+        ));
+    }
+
+    #[test]
+    fn stops_before_of() {
+        let mut parser = Parser::new("2n+1 of .a".chars());
+        assert_eq!(Ok(AnPlusB { a: 2, b: 1 }), parser.parse());
+        assert_eq!(
+            Some(Token::Whitespace()),
+            parser.tokens.next().map(|token_at| token_at.token)
+        );
+    }
+
+    #[test]
+    fn print() {
+        for (input, output) in [
+            ("odd", "2n+1"),
+            ("even", "2n"),
+            ("-n+3", "-n+3"),
+            ("n- 1", "n-1"),
+            ("+5", "5"),
+            ("0n+0", "0"),
+        ] {
+            assert_eq!(
+                Ok(output.to_owned()),
+                parse(input).map(|an_plus_b| an_plus_b.to_string())
+            );
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum AttributeSelector {
     /// [key]
     Exists(String),
@@ -70,8 +70,36 @@ impl Parsable for AttributeSelector {
     }
 }
 
-#[cfg(test)]
+impl ToCss for AttributeSelector {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        let (name, operator, value) = match self {
+            AttributeSelector::Exists(name) => {
+                printer.write_char('[')?;
+                printer.print_identifier(name)?;
+                return printer.write_char(']');
+            }
+            AttributeSelector::Equals(name, value) => (name, "=", value),
+            AttributeSelector::ListContains(name, value) => (name, "~=", value),
+            AttributeSelector::StartsWith(name, value) => (name, "^=", value),
+            AttributeSelector::EndsWith(name, value) => (name, "$=", value),
+            AttributeSelector::StringContains(name, value) => (name, "*=", value),
+        };
+
+        printer.write_char('[')?;
+        printer.print_identifier(name)?;
+        printer.write_str(operator)?;
+        printer.print_string(value)?;
+        printer.write_char(']')
+    }
+}
 
+impl fmt::Display for AttributeSelector {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
 
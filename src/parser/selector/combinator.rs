@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum Combinator {
     /// whitespace
     Descendant(Selector),
@@ -38,22 +38,55 @@ impl Parsable for Combinator {
     }
 }
 
+impl CommaSeparated for Combinator {}
+
+impl ToCss for Combinator {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            Combinator::Descendant(selector) => printer.print(selector),
+            Combinator::Child(selector) => {
+                printer.write_char('>')?;
+                printer.print_optional_space()?;
+                printer.print(selector)
+            }
+            Combinator::GeneralSibling(selector) => {
+                printer.write_char('~')?;
+                printer.print_optional_space()?;
+                printer.print(selector)
+            }
+            Combinator::AdjacentSibling(selector) => {
+                printer.write_char('+')?;
+                printer.print_optional_space()?;
+                printer.print(selector)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Combinator {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::span::assert_eq_ignoring_spans;
 
     const UNIVERSAL_SELECTOR: Selector = Selector {
         element: None,
         restrictions: vec![],
         combinator: None,
+        span: Span::EMPTY,
     };
 
     #[test]
     fn descendant() {
         let mut parser = Parser::new("*".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Combinator::Descendant(UNIVERSAL_SELECTOR)),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -61,16 +94,16 @@ mod tests {
     #[test]
     fn child() {
         let mut parser = Parser::new("> *".chars());
-        assert_eq!(Ok(Combinator::Child(UNIVERSAL_SELECTOR)), parser.parse());
+        assert_eq_ignoring_spans(Ok(Combinator::Child(UNIVERSAL_SELECTOR)), parser.parse());
         assert_eq!(None, parser.tokens.peek());
     }
 
     #[test]
     fn general_sibling() {
         let mut parser = Parser::new("~ *".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Combinator::GeneralSibling(UNIVERSAL_SELECTOR)),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
@@ -78,9 +111,9 @@ mod tests {
     #[test]
     fn adjacent_sibling() {
         let mut parser = Parser::new("+ *".chars());
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Combinator::AdjacentSibling(UNIVERSAL_SELECTOR)),
-            parser.parse()
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.peek());
     }
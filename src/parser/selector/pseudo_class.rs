@@ -1,6 +1,6 @@
-use super::*;
+use super::{an_plus_b::AnPlusB, *};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum PseudoClass {
     Focus,
     FocusWithin,
@@ -12,11 +12,210 @@ pub enum PseudoClass {
     Target,
     Root,
     Checked,
-    Not(Selector),
-    Has(Combinator),
+    FirstChild,
+    LastChild,
+    OnlyChild,
+    FirstOfType,
+    LastOfType,
+    OnlyOfType,
+    Empty,
+    /// matches elements that match none of the selectors
+    Not(Vec<Selector>),
+    /// relative selectors like `:has(> img, + p)` start from the element this is on
+    Has(Vec<Combinator>),
+    /// a forgiving selector list, so invalid selectors in it are dropped instead of making it invalid
+    Is(Vec<Selector>),
+    /// the same as `:is()` but it does not add to the specificity
+    Where(Vec<Selector>),
+    /// the selectors after `of` like `:nth-child(2n of .item)` limit which siblings are counted
+    NthChild(AnPlusB, Option<Vec<Selector>>),
+    NthLastChild(AnPlusB, Option<Vec<Selector>>),
+    NthOfType(AnPlusB),
+    NthLastOfType(AnPlusB),
 }
 
-const EXPECTED: &str = "focus, focus-within, focus-visible, hover, visited, default, active, target, root, checked, not(), or has()";
+const EXPECTED: &str = "focus, focus-within, focus-visible, hover, visited, default, active, target, root, checked, first-child, last-child, only-child, first-of-type, last-of-type, only-of-type, empty, not(), has(), is(), where(), nth-child(), nth-last-child(), nth-of-type(), or nth-last-of-type()";
+
+impl Selector {
+    /// whether this or a selector after its combinator has a pseudo-element
+    fn has_pseudo_element(&self) -> bool {
+        self.restrictions
+            .iter()
+            .any(|restriction| matches!(restriction, SelectorRestriction::PseudoElement(_)))
+            || self
+                .combinator
+                .as_ref()
+                .is_some_and(|combinator| combinator.selector().has_pseudo_element())
+    }
+
+    /// whether `:has()` is anywhere in this selector, including in the arguments of other pseudo-classes
+    fn has_has(&self) -> bool {
+        self.restrictions
+            .iter()
+            .any(|restriction| match restriction {
+                SelectorRestriction::PseudoClass(pseudo_class) => pseudo_class.has_has(),
+                _ => false,
+            })
+            || self
+                .combinator
+                .as_ref()
+                .is_some_and(|combinator| combinator.selector().has_has())
+    }
+}
+
+impl PseudoClass {
+    fn has_has(&self) -> bool {
+        match self {
+            PseudoClass::Has(_) => true,
+            PseudoClass::Not(selectors)
+            | PseudoClass::Is(selectors)
+            | PseudoClass::Where(selectors)
+            | PseudoClass::NthChild(_, Some(selectors))
+            | PseudoClass::NthLastChild(_, Some(selectors)) => {
+                selectors.iter().any(Selector::has_has)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// an error for a selector that parsed but is not allowed where it is
+fn not_allowed(selector: &Selector, found: String, expected: &str) -> ParsingError {
+    ParsingError::WrongToken {
+        line: selector.span.start.line,
+        column: selector.span.start.column,
+        expected: expected.to_owned(),
+        found,
+        span: selector.span,
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// where the next item in a comma separated list inside a function ends, which is the next `,` or `)` that is not nested
+    fn end_of_list_item(&mut self) -> Result<usize, ParsingError> {
+        let mut depth: usize = 0;
+        let mut n = 0;
+
+        loop {
+            let Some(token_at) = self.tokens.peek_nth(n) else {
+                return Err(ParsingError::end_of_file(")"));
+            };
+
+            match token_at.token {
+                Token::Comma() | Token::CloseParenthesis() if depth == 0 => {
+                    return Ok(token_at.span.start.offset)
+                }
+                Token::OpenCurlyBracket() | Token::CloseCurlyBracket() | Token::Semicolon()
+                    if depth == 0 =>
+                {
+                    return Err(ParsingError::wrong_token(token_at.clone(), ")"))
+                }
+                Token::OpenCurlyBracket()
+                | Token::OpenParenthesis()
+                | Token::OpenSquareBracket()
+                | Token::Function(_) => depth += 1,
+                Token::CloseCurlyBracket()
+                | Token::CloseParenthesis()
+                | Token::CloseSquareBracket() => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+
+            n += 1;
+        }
+    }
+
+    /// parse the selectors inside `:is()` or `:where()`, the `)` after them is not consumed
+    ///
+    /// a selector that is not valid or has a pseudo-element is skipped, so the list can end up empty
+    ///
+    /// adapted from https://www.w3.org/TR/selectors-4/#forgiving-selector
+    fn parse_forgiving_selector_list(&mut self) -> Result<Vec<Selector>, ParsingError> {
+        let mut selectors = Vec::new();
+
+        loop {
+            self.optional_whitespace();
+            let end = self.end_of_list_item()?;
+
+            if self.position().offset < end {
+                if let Ok(selector) = self.parse::<Selector>() {
+                    self.optional_whitespace();
+                    if self.position().offset == end && !selector.has_pseudo_element() {
+                        selectors.push(selector);
+                    }
+                }
+
+                while self.position().offset < end {
+                    self.tokens.next();
+                }
+            }
+
+            match self.tokens.peek() {
+                Some(TokenAt {
+                    token: Token::Comma(),
+                    ..
+                }) => {
+                    self.tokens.next();
+                }
+                _ => return Ok(selectors),
+            }
+        }
+    }
+
+    /// parse the `An+B` and the optional `of S` inside `:nth-child()` or `:nth-last-child()` and the `)` after them
+    ///
+    /// adapted from https://www.w3.org/TR/selectors-4/#the-nth-child-pseudo
+    fn parse_nth_child_arguments(
+        &mut self,
+    ) -> Result<(AnPlusB, Option<Vec<Selector>>), ParsingError> {
+        self.optional_whitespace();
+        let an_plus_b = self.parse()?;
+
+        let has_whitespace = matches!(
+            self.tokens.peek(),
+            Some(TokenAt {
+                token: Token::Whitespace(),
+                ..
+            })
+        );
+        self.optional_whitespace();
+
+        let selectors = match self.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Identifier(name),
+                ..
+            }) if has_whitespace && name.eq_ignore_ascii_case("of") => {
+                self.tokens.next();
+                self.optional_whitespace();
+                let selectors: Vec<Selector> = self.parse()?;
+                if let Some(selector) = selectors
+                    .iter()
+                    .find(|selector| selector.has_pseudo_element())
+                {
+                    return Err(not_allowed(
+                        selector,
+                        selector.to_string(),
+                        "a selector without a pseudo-element",
+                    ));
+                }
+                Some(selectors)
+            }
+            _ => None,
+        };
+
+        self.optional_whitespace();
+        self.expect(Token::CloseParenthesis())?;
+        Ok((an_plus_b, selectors))
+    }
+
+    /// parse the `An+B` inside `:nth-of-type()` or `:nth-last-of-type()` and the `)` after it
+    fn parse_nth_of_type_arguments(&mut self) -> Result<AnPlusB, ParsingError> {
+        self.optional_whitespace();
+        let an_plus_b = self.parse()?;
+        self.optional_whitespace();
+        self.expect(Token::CloseParenthesis())?;
+        Ok(an_plus_b)
+    }
+}
 
 impl Parsable for PseudoClass {
     fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
@@ -35,23 +234,85 @@ impl Parsable for PseudoClass {
                     "target" => Ok(PseudoClass::Target),
                     "root" => Ok(PseudoClass::Root),
                     "checked" => Ok(PseudoClass::Checked),
+                    "first-child" => Ok(PseudoClass::FirstChild),
+                    "last-child" => Ok(PseudoClass::LastChild),
+                    "only-child" => Ok(PseudoClass::OnlyChild),
+                    "first-of-type" => Ok(PseudoClass::FirstOfType),
+                    "last-of-type" => Ok(PseudoClass::LastOfType),
+                    "only-of-type" => Ok(PseudoClass::OnlyOfType),
+                    "empty" => Ok(PseudoClass::Empty),
                     _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
                 },
                 Token::Function(pseudo_class_name) => match pseudo_class_name.as_str() {
                     "not" => {
                         parser.optional_whitespace();
-                        let selector = parser.parse()?;
+                        let selectors: Vec<Selector> = parser.parse()?;
+                        if let Some(selector) = selectors
+                            .iter()
+                            .find(|selector| selector.has_pseudo_element())
+                        {
+                            return Err(not_allowed(
+                                selector,
+                                selector.to_string(),
+                                "a selector without a pseudo-element",
+                            ));
+                        }
                         parser.optional_whitespace();
                         parser.expect(Token::CloseParenthesis())?;
-                        Ok(PseudoClass::Not(selector))
+                        Ok(PseudoClass::Not(selectors))
                     }
                     "has" => {
                         parser.optional_whitespace();
-                        let combinator = parser.parse()?;
+                        let combinators: Vec<Combinator> = parser.parse()?;
+                        // `:has()` can not be nested in `:has()`
+                        if let Some(combinator) = combinators
+                            .iter()
+                            .find(|combinator| combinator.selector().has_has())
+                        {
+                            return Err(not_allowed(
+                                combinator.selector(),
+                                combinator.to_string(),
+                                "a relative selector without :has()",
+                            ));
+                        }
+                        if let Some(combinator) = combinators
+                            .iter()
+                            .find(|combinator| combinator.selector().has_pseudo_element())
+                        {
+                            return Err(not_allowed(
+                                combinator.selector(),
+                                combinator.to_string(),
+                                "a relative selector without a pseudo-element",
+                            ));
+                        }
                         parser.optional_whitespace();
                         parser.expect(Token::CloseParenthesis())?;
-                        Ok(PseudoClass::Has(combinator))
+                        Ok(PseudoClass::Has(combinators))
+                    }
+                    "is" => {
+                        let selectors = parser.parse_forgiving_selector_list()?;
+                        parser.expect(Token::CloseParenthesis())?;
+                        Ok(PseudoClass::Is(selectors))
+                    }
+                    "where" => {
+                        let selectors = parser.parse_forgiving_selector_list()?;
+                        parser.expect(Token::CloseParenthesis())?;
+                        Ok(PseudoClass::Where(selectors))
+                    }
+                    "nth-child" => {
+                        let (an_plus_b, selectors) = parser.parse_nth_child_arguments()?;
+                        Ok(PseudoClass::NthChild(an_plus_b, selectors))
+                    }
+                    "nth-last-child" => {
+                        let (an_plus_b, selectors) = parser.parse_nth_child_arguments()?;
+                        Ok(PseudoClass::NthLastChild(an_plus_b, selectors))
                     }
+                    "nth-of-type" => Ok(PseudoClass::NthOfType(
+                        parser.parse_nth_of_type_arguments()?,
+                    )),
+                    "nth-last-of-type" => Ok(PseudoClass::NthLastOfType(
+                        parser.parse_nth_of_type_arguments()?,
+                    )),
                     _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
                 },
                 _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
@@ -61,9 +322,94 @@ impl Parsable for PseudoClass {
     }
 }
 
+impl ToCss for PseudoClass {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            PseudoClass::Focus => printer.write_str(":focus"),
+            PseudoClass::FocusWithin => printer.write_str(":focus-within"),
+            PseudoClass::FocusVisible => printer.write_str(":focus-visible"),
+            PseudoClass::Hover => printer.write_str(":hover"),
+            PseudoClass::Visited => printer.write_str(":visited"),
+            PseudoClass::Default => printer.write_str(":default"),
+            PseudoClass::Active => printer.write_str(":active"),
+            PseudoClass::Target => printer.write_str(":target"),
+            PseudoClass::Root => printer.write_str(":root"),
+            PseudoClass::Checked => printer.write_str(":checked"),
+            PseudoClass::FirstChild => printer.write_str(":first-child"),
+            PseudoClass::LastChild => printer.write_str(":last-child"),
+            PseudoClass::OnlyChild => printer.write_str(":only-child"),
+            PseudoClass::FirstOfType => printer.write_str(":first-of-type"),
+            PseudoClass::LastOfType => printer.write_str(":last-of-type"),
+            PseudoClass::OnlyOfType => printer.write_str(":only-of-type"),
+            PseudoClass::Empty => printer.write_str(":empty"),
+            PseudoClass::Not(selectors) => {
+                printer.write_str(":not(")?;
+                printer.print_comma_separated(selectors)?;
+                printer.write_char(')')
+            }
+            PseudoClass::Has(combinators) => {
+                printer.write_str(":has(")?;
+                printer.print_comma_separated(combinators)?;
+                printer.write_char(')')
+            }
+            PseudoClass::Is(selectors) => {
+                printer.write_str(":is(")?;
+                printer.print_comma_separated(selectors)?;
+                printer.write_char(')')
+            }
+            PseudoClass::Where(selectors) => {
+                printer.write_str(":where(")?;
+                printer.print_comma_separated(selectors)?;
+                printer.write_char(')')
+            }
+            PseudoClass::NthChild(an_plus_b, selectors) => {
+                printer.write_str(":nth-child(")?;
+                print_nth_child_arguments(printer, an_plus_b, selectors)
+            }
+            PseudoClass::NthLastChild(an_plus_b, selectors) => {
+                printer.write_str(":nth-last-child(")?;
+                print_nth_child_arguments(printer, an_plus_b, selectors)
+            }
+            PseudoClass::NthOfType(an_plus_b) => {
+                printer.write_str(":nth-of-type(")?;
+                printer.print(an_plus_b)?;
+                printer.write_char(')')
+            }
+            PseudoClass::NthLastOfType(an_plus_b) => {
+                printer.write_str(":nth-last-of-type(")?;
+                printer.print(an_plus_b)?;
+                printer.write_char(')')
+            }
+        }
+    }
+}
+
+/// print the `An+B`, the `of S` if there is one, and the closing `)`
+fn print_nth_child_arguments(
+    printer: &mut Printer,
+    an_plus_b: &AnPlusB,
+    selectors: &Option<Vec<Selector>>,
+) -> fmt::Result {
+    printer.print(an_plus_b)?;
+
+    if let Some(selectors) = selectors {
+        printer.write_str(" of ")?;
+        printer.print_comma_separated(selectors)?;
+    }
+
+    printer.write_char(')')
+}
+
+impl fmt::Display for PseudoClass {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::span::assert_eq_ignoring_spans;
 
     #[test]
     fn focus() {
@@ -150,13 +496,14 @@ mod tests {
     #[test]
     fn not() {
         let mut parser = Parser::new(":not(div)".chars());
-        assert_eq!(
-            Ok(PseudoClass::Not(Selector {
+        assert_eq_ignoring_spans(
+            Ok(PseudoClass::Not(vec![Selector {
                 element: Some("div".to_owned()),
                 restrictions: vec![],
                 combinator: None,
-            })),
-            parser.parse()
+                span: Span::EMPTY,
+            }])),
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -164,13 +511,14 @@ mod tests {
     #[test]
     fn has() {
         let mut parser = Parser::new(":has(div)".chars());
-        assert_eq!(
-            Ok(PseudoClass::Has(Combinator::Descendant(Selector {
+        assert_eq_ignoring_spans(
+            Ok(PseudoClass::Has(vec![Combinator::Descendant(Selector {
                 element: Some("div".to_owned()),
                 restrictions: vec![],
                 combinator: None,
-            }))),
-            parser.parse()
+                span: Span::EMPTY,
+            })])),
+            parser.parse(),
         );
         assert_eq!(None, parser.tokens.next());
     }
@@ -178,14 +526,264 @@ mod tests {
     #[test]
     fn has_with_whitespace() {
         let mut parser = Parser::new(":has( div )".chars());
-        assert_eq!(
-            Ok(PseudoClass::Has(Combinator::Descendant(Selector {
+        assert_eq_ignoring_spans(
+            Ok(PseudoClass::Has(vec![Combinator::Descendant(Selector {
                 element: Some("div".to_owned()),
                 restrictions: vec![],
                 combinator: None,
-            }))),
+                span: Span::EMPTY,
+            })])),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn not_list() {
+        let mut parser = Parser::new(":not(div, .class)".chars());
+        assert_eq_ignoring_spans(
+            Ok(PseudoClass::Not(vec![
+                Selector {
+                    element: Some("div".to_owned()),
+                    restrictions: vec![],
+                    combinator: None,
+                    span: Span::EMPTY,
+                },
+                Selector {
+                    element: None,
+                    restrictions: vec![SelectorRestriction::Class("class".to_owned())],
+                    combinator: None,
+                    span: Span::EMPTY,
+                },
+            ])),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn not_with_invalid_selector() {
+        let mut parser = Parser::new(":not(div, :invalid)".chars());
+        assert!(parser.parse::<PseudoClass>().is_err());
+    }
+
+    #[test]
+    fn has_relative_list() {
+        let mut parser = Parser::new(":has(> img, + p)".chars());
+        assert_eq_ignoring_spans(
+            Ok(PseudoClass::Has(vec![
+                Combinator::Child(Selector {
+                    element: Some("img".to_owned()),
+                    restrictions: vec![],
+                    combinator: None,
+                    span: Span::EMPTY,
+                }),
+                Combinator::AdjacentSibling(Selector {
+                    element: Some("p".to_owned()),
+                    restrictions: vec![],
+                    combinator: None,
+                    span: Span::EMPTY,
+                }),
+            ])),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn is() {
+        let mut parser = Parser::new(":is( h1 , h2 )".chars());
+        assert_eq_ignoring_spans(
+            Ok(PseudoClass::Is(vec![
+                Selector {
+                    element: Some("h1".to_owned()),
+                    restrictions: vec![],
+                    combinator: None,
+                    span: Span::EMPTY,
+                },
+                Selector {
+                    element: Some("h2".to_owned()),
+                    restrictions: vec![],
+                    combinator: None,
+                    span: Span::EMPTY,
+                },
+            ])),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn where_drops_invalid_selectors() {
+        let mut parser = Parser::new(":where(:invalid(a, b), p, 123 , ::before.a, a], )".chars());
+        assert_eq_ignoring_spans(
+            Ok(PseudoClass::Where(vec![Selector {
+                element: Some("p".to_owned()),
+                restrictions: vec![],
+                combinator: None,
+                span: Span::EMPTY,
+            }])),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn is_drops_pseudo_elements() {
+        let mut parser = Parser::new(":is(p::before, a, b ::after)".chars());
+        let pseudo_class: PseudoClass = parser.parse().unwrap();
+        assert_eq!(":is(a)", pseudo_class.to_string());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn not_with_pseudo_element() {
+        let mut parser = Parser::new(":not(a, p::before)".chars());
+        assert!(matches!(
+            parser.parse::<PseudoClass>(),
+            Err(ParsingError::WrongToken { column: 8, found, .. }) if found == "p::before"
+        ));
+    }
+
+    #[test]
+    fn nested_has() {
+        for input in [
+            ":has(> a:has(b))",
+            ":has(a :is(:has(b)))",
+            ":has(a, + b:not(:has(c)))",
+        ] {
+            let mut parser = Parser::new(input.chars());
+            assert!(
+                parser.parse::<PseudoClass>().is_err(),
+                "{input} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn nth_child_of_pseudo_element() {
+        let mut parser = Parser::new(":nth-child(2n of a, b::before)".chars());
+        assert!(matches!(
+            parser.parse::<PseudoClass>(),
+            Err(ParsingError::WrongToken { found, .. }) if found == "b::before"
+        ));
+    }
+
+    #[test]
+    fn has_with_pseudo_element() {
+        for input in [":has(::before)", ":has(> b::after)"] {
+            let mut parser = Parser::new(input.chars());
+            assert!(matches!(
+                parser.parse::<PseudoClass>(),
+                Err(ParsingError::WrongToken { expected, .. })
+                    if expected == "a relative selector without a pseudo-element"
+            ));
+        }
+    }
+
+    #[test]
+    fn has_in_other_pseudo_classes() {
+        let mut parser = Parser::new(":is(a:has(b)):not(:has(c))".chars());
+        let selector: Selector = parser.parse().unwrap();
+        assert_eq!(":is(a:has(b)):not(:has(c))", selector.to_string());
+    }
+
+    #[test]
+    fn empty_is() {
+        let mut parser = Parser::new(":is()".chars());
+        assert_eq!(Ok(PseudoClass::Is(vec![])), parser.parse());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn unclosed_is() {
+        let mut parser = Parser::new(":is(a, b {}".chars());
+        assert!(parser.parse::<PseudoClass>().is_err());
+    }
+
+    #[test]
+    fn print_selector_lists() {
+        let mut parser = Parser::new(":is(a,:invalid,b):where():not(a,b):has(>a,~b)".chars());
+        let selector: Selector = parser.parse().unwrap();
+        assert_eq!(
+            ":is(a, b):where():not(a, b):has(> a, ~ b)",
+            selector.to_string()
+        );
+    }
+
+    #[test]
+    fn structural() {
+        for (input, pseudo_class) in [
+            (":first-child", PseudoClass::FirstChild),
+            (":last-child", PseudoClass::LastChild),
+            (":only-child", PseudoClass::OnlyChild),
+            (":first-of-type", PseudoClass::FirstOfType),
+            (":last-of-type", PseudoClass::LastOfType),
+            (":only-of-type", PseudoClass::OnlyOfType),
+            (":empty", PseudoClass::Empty),
+        ] {
+            let mut parser = Parser::new(input.chars());
+            assert_eq!(Ok(pseudo_class), parser.parse());
+            assert_eq!(None, parser.tokens.next());
+        }
+    }
+
+    #[test]
+    fn nth_child() {
+        let mut parser = Parser::new(":nth-child( odd )".chars());
+        assert_eq!(
+            Ok(PseudoClass::NthChild(AnPlusB { a: 2, b: 1 }, None)),
             parser.parse()
         );
         assert_eq!(None, parser.tokens.next());
     }
+
+    #[test]
+    fn nth_last_child_of_selectors() {
+        let mut parser = Parser::new(":nth-last-child(-n+3 of li.item, .other)".chars());
+        assert_eq_ignoring_spans(
+            Ok(PseudoClass::NthLastChild(
+                AnPlusB { a: -1, b: 3 },
+                Some(vec![
+                    Selector {
+                        element: Some("li".to_owned()),
+                        restrictions: vec![SelectorRestriction::Class("item".to_owned())],
+                        combinator: None,
+                        span: Span::EMPTY,
+                    },
+                    Selector {
+                        element: None,
+                        restrictions: vec![SelectorRestriction::Class("other".to_owned())],
+                        combinator: None,
+                        span: Span::EMPTY,
+                    },
+                ]),
+            )),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn nth_of_type() {
+        let mut parser = Parser::new(":nth-of-type(2n-1)".chars());
+        assert_eq!(
+            Ok(PseudoClass::NthOfType(AnPlusB { a: 2, b: -1 })),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn nth_of_type_of_selectors() {
+        let mut parser = Parser::new(":nth-last-of-type(2 of p)".chars());
+        assert!(parser.parse::<PseudoClass>().is_err());
+    }
+
+    #[test]
+    fn print_nth_child() {
+        let mut parser = Parser::new(":nth-child(2n + 1 of .a,.b)".chars());
+        let pseudo_class: PseudoClass = parser.parse().unwrap();
+        assert_eq!(":nth-child(2n+1 of .a, .b)", pseudo_class.to_string());
+    }
 }
@@ -0,0 +1,257 @@
+use super::*;
+
+/// A part of an element that is not in the document tree like `::before`
+///
+/// the legacy single colon forms like `:before` are also accepted, but are always printed with two colons
+///
+/// adapted from https://www.w3.org/TR/css-pseudo-4/
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub enum PseudoElement {
+    Before,
+    After,
+    FirstLine,
+    FirstLetter,
+    Marker,
+    Placeholder,
+    Selection,
+    Backdrop,
+    FileSelectorButton,
+    /// the parts of a shadow tree with every one of the names like `::part(label active)`
+    Part(Vec<String>),
+    /// the elements slotted into a shadow tree that match a compound selector like `::slotted(span.icon)`
+    Slotted(Selector),
+}
+
+const EXPECTED: &str = "before, after, first-line, first-letter, marker, placeholder, selection, backdrop, file-selector-button, part(), or slotted()";
+
+/// the pseudo-elements that can also be written with a single colon
+const LEGACY_PSEUDO_ELEMENTS: [&str; 4] = ["before", "after", "first-line", "first-letter"];
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// whether the next `:` starts a pseudo-element instead of a pseudo-class
+    pub(crate) fn is_pseudo_element_next(&mut self) -> bool {
+        if !matches!(
+            self.tokens.peek().map(|token_at| &token_at.token),
+            Some(Token::Colon())
+        ) {
+            return false;
+        }
+
+        match self.tokens.peek_nth(1).map(|token_at| &token_at.token) {
+            Some(Token::Colon()) => true,
+            Some(Token::Identifier(name)) => LEGACY_PSEUDO_ELEMENTS.contains(&name.as_str()),
+            _ => false,
+        }
+    }
+}
+
+impl Parsable for PseudoElement {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        parser.expect(Token::Colon())?;
+
+        let is_legacy = match parser.tokens.peek() {
+            Some(TokenAt {
+                token: Token::Colon(),
+                ..
+            }) => {
+                parser.tokens.next();
+                false
+            }
+            _ => true,
+        };
+
+        match parser.tokens.next() {
+            Some(token_at) => match &token_at.token {
+                Token::Identifier(name)
+                    if is_legacy && !LEGACY_PSEUDO_ELEMENTS.contains(&name.as_str()) =>
+                {
+                    Err(ParsingError::wrong_token(token_at, "::"))
+                }
+                Token::Identifier(name) => match name.as_str() {
+                    "before" => Ok(PseudoElement::Before),
+                    "after" => Ok(PseudoElement::After),
+                    "first-line" => Ok(PseudoElement::FirstLine),
+                    "first-letter" => Ok(PseudoElement::FirstLetter),
+                    "marker" => Ok(PseudoElement::Marker),
+                    "placeholder" => Ok(PseudoElement::Placeholder),
+                    "selection" => Ok(PseudoElement::Selection),
+                    "backdrop" => Ok(PseudoElement::Backdrop),
+                    "file-selector-button" => Ok(PseudoElement::FileSelectorButton),
+                    _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+                },
+                Token::Function(_) if is_legacy => Err(ParsingError::wrong_token(token_at, "::")),
+                Token::Function(name) => match name.as_str() {
+                    "part" => {
+                        let mut names = Vec::new();
+
+                        loop {
+                            parser.optional_whitespace();
+
+                            match parser.tokens.next() {
+                                Some(TokenAt {
+                                    token: Token::Identifier(name),
+                                    ..
+                                }) => names.push(name),
+                                Some(TokenAt {
+                                    token: Token::CloseParenthesis(),
+                                    ..
+                                }) if !names.is_empty() => return Ok(PseudoElement::Part(names)),
+                                Some(token_at) => {
+                                    return Err(ParsingError::wrong_token(token_at, "a part name"))
+                                }
+                                None => return Err(ParsingError::end_of_file("a part name")),
+                            }
+                        }
+                    }
+                    "slotted" => {
+                        parser.optional_whitespace();
+                        let selector: Selector = parser.parse()?;
+
+                        // only a compound selector is allowed, so there can not be a combinator
+                        if selector.combinator.is_some() {
+                            return Err(ParsingError::WrongToken {
+                                line: selector.span.start.line,
+                                column: selector.span.start.column,
+                                expected: "a compound selector".to_owned(),
+                                found: selector.to_string(),
+                                span: selector.span,
+                            });
+                        }
+
+                        parser.optional_whitespace();
+                        parser.expect(Token::CloseParenthesis())?;
+                        Ok(PseudoElement::Slotted(selector))
+                    }
+                    _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+                },
+                _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+            },
+            None => Err(ParsingError::end_of_file(EXPECTED)),
+        }
+    }
+}
+
+impl ToCss for PseudoElement {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            PseudoElement::Before => printer.write_str("::before"),
+            PseudoElement::After => printer.write_str("::after"),
+            PseudoElement::FirstLine => printer.write_str("::first-line"),
+            PseudoElement::FirstLetter => printer.write_str("::first-letter"),
+            PseudoElement::Marker => printer.write_str("::marker"),
+            PseudoElement::Placeholder => printer.write_str("::placeholder"),
+            PseudoElement::Selection => printer.write_str("::selection"),
+            PseudoElement::Backdrop => printer.write_str("::backdrop"),
+            PseudoElement::FileSelectorButton => printer.write_str("::file-selector-button"),
+            PseudoElement::Part(names) => {
+                printer.write_str("::part(")?;
+                for (index, name) in names.iter().enumerate() {
+                    if index > 0 {
+                        printer.write_char(' ')?;
+                    }
+                    printer.print_identifier(name)?;
+                }
+                printer.write_char(')')
+            }
+            PseudoElement::Slotted(selector) => {
+                printer.write_str("::slotted(")?;
+                printer.print(selector)?;
+                printer.write_char(')')
+            }
+        }
+    }
+}
+
+impl fmt::Display for PseudoElement {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::span::assert_eq_ignoring_spans;
+
+    #[test]
+    fn before() {
+        let mut parser = Parser::new("::before".chars());
+        assert_eq!(Ok(PseudoElement::Before), parser.parse());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn legacy_after() {
+        let mut parser = Parser::new(":after".chars());
+        assert_eq!(Ok(PseudoElement::After), parser.parse());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn placeholder() {
+        let mut parser = Parser::new("::placeholder".chars());
+        assert_eq!(Ok(PseudoElement::Placeholder), parser.parse());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn placeholder_with_one_colon() {
+        let mut parser = Parser::new(":placeholder".chars());
+        assert!(parser.parse::<PseudoElement>().is_err());
+    }
+
+    #[test]
+    fn invalid_pseudo_element() {
+        let mut parser = Parser::new("::hover".chars());
+        assert!(parser.parse::<PseudoElement>().is_err());
+    }
+
+    #[test]
+    fn part() {
+        let mut parser = Parser::new("::part( label  active )".chars());
+        assert_eq!(
+            Ok(PseudoElement::Part(vec![
+                "label".to_owned(),
+                "active".to_owned()
+            ])),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn empty_part() {
+        let mut parser = Parser::new("::part()".chars());
+        assert!(parser.parse::<PseudoElement>().is_err());
+    }
+
+    #[test]
+    fn slotted() {
+        let mut parser = Parser::new("::slotted(span.icon)".chars());
+        assert_eq_ignoring_spans(
+            Ok(PseudoElement::Slotted(Selector {
+                element: Some("span".to_owned()),
+                restrictions: vec![SelectorRestriction::Class("icon".to_owned())],
+                combinator: None,
+                span: Span::EMPTY,
+            })),
+            parser.parse(),
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn slotted_with_combinator() {
+        let mut parser = Parser::new("::slotted(div > span)".chars());
+        assert!(parser.parse::<PseudoElement>().is_err());
+    }
+
+    #[test]
+    fn print() {
+        assert_eq!("::first-line", PseudoElement::FirstLine.to_string());
+        assert_eq!(
+            "::part(a b)",
+            PseudoElement::Part(vec!["a".to_owned(), "b".to_owned()]).to_string()
+        );
+    }
+}
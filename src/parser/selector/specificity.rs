@@ -0,0 +1,197 @@
+use std::ops::Add;
+
+use super::*;
+
+/// How specific a selector is, a selector with a higher specificity wins when declarations conflict
+///
+/// the counts are compared in order, so one id beats any number of classes
+///
+/// adapted from https://www.w3.org/TR/selectors-4/#specificity-rules
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default, Serialize)]
+pub struct Specificity {
+    /// id selectors like `#id`
+    pub ids: u32,
+    /// class selectors, attribute selectors, and pseudo-classes
+    pub classes: u32,
+    /// type selectors like `div` and pseudo-elements
+    pub types: u32,
+}
+
+impl Specificity {
+    const ID: Specificity = Specificity {
+        ids: 1,
+        classes: 0,
+        types: 0,
+    };
+
+    const CLASS: Specificity = Specificity {
+        ids: 0,
+        classes: 1,
+        types: 0,
+    };
+
+    const TYPE: Specificity = Specificity {
+        ids: 0,
+        classes: 0,
+        types: 1,
+    };
+}
+
+impl Add for Specificity {
+    type Output = Specificity;
+
+    fn add(self, other: Specificity) -> Specificity {
+        Specificity {
+            ids: self.ids + other.ids,
+            classes: self.classes + other.classes,
+            types: self.types + other.types,
+        }
+    }
+}
+
+impl fmt::Display for Specificity {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "({}, {}, {})",
+            self.ids, self.classes, self.types
+        )
+    }
+}
+
+/// the specificity of the most specific selector in a list, which is what `:is()`, `:not()`, and `:has()` count as
+fn max_specificity<'a>(selectors: impl IntoIterator<Item = &'a Selector>) -> Specificity {
+    selectors
+        .into_iter()
+        .map(Selector::specificity)
+        .max()
+        .unwrap_or_default()
+}
+
+impl Selector {
+    /// the specificity of this selector and the selectors after its combinator
+    pub fn specificity(&self) -> Specificity {
+        let element = match self.element {
+            Some(_) => Specificity::TYPE,
+            None => Specificity::default(),
+        };
+
+        let restrictions = self
+            .restrictions
+            .iter()
+            .map(SelectorRestriction::specificity)
+            .fold(element, Add::add);
+
+        match &self.combinator {
+            Some(combinator) => restrictions + combinator.selector().specificity(),
+            None => restrictions,
+        }
+    }
+}
+
+impl SelectorRestriction {
+    fn specificity(&self) -> Specificity {
+        match self {
+            SelectorRestriction::Id(_) => Specificity::ID,
+            SelectorRestriction::Class(_) | SelectorRestriction::Attribute(_) => Specificity::CLASS,
+            SelectorRestriction::PseudoClass(pseudo_class) => match pseudo_class {
+                PseudoClass::Not(selectors) | PseudoClass::Is(selectors) => {
+                    max_specificity(selectors)
+                }
+                PseudoClass::Has(combinators) => {
+                    max_specificity(combinators.iter().map(Combinator::selector))
+                }
+                PseudoClass::Where(_) => Specificity::default(),
+                PseudoClass::NthChild(_, Some(selectors))
+                | PseudoClass::NthLastChild(_, Some(selectors)) => {
+                    Specificity::CLASS + max_specificity(selectors)
+                }
+                _ => Specificity::CLASS,
+            },
+            SelectorRestriction::PseudoElement(PseudoElement::Slotted(selector)) => {
+                Specificity::TYPE + selector.specificity()
+            }
+            SelectorRestriction::PseudoElement(_) => Specificity::TYPE,
+        }
+    }
+}
+
+impl Combinator {
+    /// the selector after the combinator
+    pub fn selector(&self) -> &Selector {
+        match self {
+            Combinator::Descendant(selector)
+            | Combinator::Child(selector)
+            | Combinator::GeneralSibling(selector)
+            | Combinator::AdjacentSibling(selector) => selector,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn specificity(input: &str) -> (u32, u32, u32) {
+        let mut parser = Parser::new(input.chars());
+        let specificity = parser.parse::<Selector>().unwrap().specificity();
+        (specificity.ids, specificity.classes, specificity.types)
+    }
+
+    #[test]
+    fn simple_selectors() {
+        assert_eq!((0, 0, 0), specificity("*"));
+        assert_eq!((0, 0, 1), specificity("div"));
+        assert_eq!((0, 1, 0), specificity(".class"));
+        assert_eq!((0, 1, 0), specificity("[href]"));
+        assert_eq!((0, 1, 0), specificity(":hover"));
+        assert_eq!((1, 0, 0), specificity("#id"));
+        assert_eq!((0, 0, 1), specificity("::before"));
+    }
+
+    #[test]
+    fn compound_and_complex() {
+        assert_eq!((1, 2, 1), specificity("a#home.nav:hover"));
+        assert_eq!((0, 1, 3), specificity("ul > li + li.item"));
+        assert_eq!((0, 1, 2), specificity("a:hover::before"));
+        assert_eq!((0, 0, 1), specificity("div *"));
+    }
+
+    #[test]
+    fn selector_lists() {
+        assert_eq!((1, 0, 1), specificity("div:not(.a, #b)"));
+        assert_eq!((0, 1, 1), specificity("a:is(p, .a)"));
+        assert_eq!((0, 2, 0), specificity(":has(> .a.b, + p)"));
+        assert_eq!((0, 0, 1), specificity("a:where(#id, .a)"));
+        assert_eq!((0, 0, 0), specificity(":is()"));
+    }
+
+    #[test]
+    fn structural() {
+        assert_eq!((0, 1, 1), specificity("li:nth-child(2n+1)"));
+        assert_eq!((1, 1, 0), specificity(":nth-child(odd of #a, .b)"));
+        assert_eq!((0, 1, 1), specificity("li:nth-of-type(2)"));
+    }
+
+    #[test]
+    fn slotted() {
+        assert_eq!((0, 1, 2), specificity("::slotted(span.icon)"));
+        assert_eq!((0, 0, 1), specificity("::part(label)"));
+    }
+
+    #[test]
+    fn ordering() {
+        let id = Specificity {
+            ids: 1,
+            classes: 0,
+            types: 0,
+        };
+        let classes = Specificity {
+            ids: 0,
+            classes: 10,
+            types: 10,
+        };
+        assert!(id > classes);
+        assert_eq!("(0, 10, 10)", classes.to_string());
+    }
+}
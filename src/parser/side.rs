@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum Sides<T> {
     Single(T),
     Double(T, T),
@@ -41,6 +41,52 @@ impl<T: CanStart> Parsable for Sides<T> {
     }
 }
 
+impl<T: ToCss + PartialEq> ToCss for Sides<T> {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            Sides::Single(all) => printer.print(all),
+            // when minified sides with the same value are only written once
+            Sides::Double(vertical, horizontal)
+                if printer.is_minified() && vertical == horizontal =>
+            {
+                printer.print(vertical)
+            }
+            Sides::Quad(top, right, bottom, left)
+                if printer.is_minified() && top == right && top == bottom && top == left =>
+            {
+                printer.print(top)
+            }
+            Sides::Quad(top, right, bottom, left)
+                if printer.is_minified() && top == bottom && right == left =>
+            {
+                printer.print(top)?;
+                printer.write_char(' ')?;
+                printer.print(right)
+            }
+            Sides::Double(vertical, horizontal) => {
+                printer.print(vertical)?;
+                printer.write_char(' ')?;
+                printer.print(horizontal)
+            }
+            Sides::Quad(top, right, bottom, left) => {
+                printer.print(top)?;
+                printer.write_char(' ')?;
+                printer.print(right)?;
+                printer.write_char(' ')?;
+                printer.print(bottom)?;
+                printer.write_char(' ')?;
+                printer.print(left)
+            }
+        }
+    }
+}
+
+impl<T: ToCss + PartialEq> fmt::Display for Sides<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 pub trait CanStart: Parsable {
     fn can_start(token: &Token) -> bool;
 }
@@ -48,7 +94,11 @@ pub trait CanStart: Parsable {
 #[cfg(test)]
 mod tests {
     use crate::parser::{
-        color::Color, length::Length, length_or_percentage::LengthOrPercentage, side::Sides, *,
+        color::Color,
+        length::{Length, LengthUnit},
+        length_or_percentage::LengthOrPercentage,
+        side::Sides,
+        *,
     };
 
     #[test]
@@ -257,4 +307,29 @@ mod tests {
         );
         assert_ne!(None, parser.tokens.next());
     }
+
+    #[test]
+    fn minify_equal_quad() {
+        let sides = Sides::Quad(
+            Length::Zero(),
+            Length::Zero(),
+            Length::Zero(),
+            Length::Zero(),
+        );
+        assert_eq!("0", crate::printer::minify(&sides));
+    }
+
+    #[test]
+    fn minify_paired_quad() {
+        let pixels = Length::Length(1.0, LengthUnit::Pixels);
+        let sides = Sides::Quad(pixels, Length::Zero(), pixels, Length::Zero());
+        assert_eq!("1px 0", crate::printer::minify(&sides));
+    }
+
+    #[test]
+    fn minify_different_quad() {
+        let pixels = Length::Length(1.0, LengthUnit::Pixels);
+        let sides = Sides::Quad(pixels, Length::Zero(), Length::Zero(), Length::Zero());
+        assert_eq!("1px 0 0 0", crate::printer::minify(&sides));
+    }
 }
@@ -1,11 +1,54 @@
-use super::{import::Import, rule::Rule, *};
+use super::{
+    import::{Import, ImportLayer},
+    layer_name::LayerName,
+    rule::{
+        layer::{collect_layer_order, declare_layer},
+        Rule,
+    },
+    *,
+};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct Stylesheet {
+    /// the `@charset` and `@layer` statements that come before the imports
+    ///
+    /// these are only kept apart from the rules when there are imports after them
+    pub statements: Vec<Rule>,
     pub imports: Vec<Import>,
     pub rules: Vec<Rule>,
 }
 
+impl Stylesheet {
+    /// parse a stylesheet skipping over any invalid rules or declarations
+    ///
+    /// returns the rules that could be parsed along with every error that was found
+    pub fn parse_with_recovery(input: &str) -> (Self, Vec<ParsingError>) {
+        Parser::with_error_recovery(input.chars()).into_stylesheet_with_errors()
+    }
+
+    /// the names of the cascade layers in the order they are declared, which is the order they
+    /// are applied in with later layers taking priority
+    ///
+    /// sub-layers like `a.b` are included with their full name after `a`, and anonymous layers
+    /// are left out since nothing else can refer to them
+    ///
+    /// adapted from https://www.w3.org/TR/css-cascade-5/#layer-ordering
+    pub fn layer_order(&self) -> Vec<LayerName> {
+        let mut order: Vec<LayerName> = Vec::new();
+
+        collect_layer_order(&self.statements, &[], &mut order);
+
+        for import in &self.imports {
+            if let Some(ImportLayer::Named(name)) = &import.layer {
+                declare_layer(name, &mut order);
+            }
+        }
+
+        collect_layer_order(&self.rules, &[], &mut order);
+        order
+    }
+}
+
 impl FromStr for Stylesheet {
     type Err = ParsingError;
 
@@ -18,25 +61,97 @@ impl Parsable for Stylesheet {
     fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
         parser.optional_whitespace();
 
+        let mut statements = Vec::new();
         let mut imports = Vec::new();
 
-        while let Some(token_at) = parser.tokens.peek() {
-            match &token_at.token {
-                Token::AtKeyword(keyword) if keyword == "import" => {
-                    imports.push(parser.parse()?);
-                    parser.optional_whitespace();
+        // `@charset` and `@layer` statements can come before the imports but not in between them
+        //
+        // adapted from https://www.w3.org/TR/css-cascade-5/#at-import
+        loop {
+            let is_layer_statement = parser.is_layer_statement();
+
+            match parser.tokens.peek().map(|token_at| &token_at.token) {
+                Some(Token::AtKeyword(keyword)) if keyword == "import" => {
+                    if let Some(import) = parser.parse_rule(true)? {
+                        imports.push(import);
+                    }
+                }
+                Some(Token::AtKeyword(keyword)) if keyword == "charset" && imports.is_empty() => {
+                    if let Some(charset) = parser.parse_rule(true)? {
+                        statements.push(Rule::Unknown(charset));
+                    }
+                }
+                Some(Token::AtKeyword(keyword))
+                    if keyword == "layer" && is_layer_statement && imports.is_empty() =>
+                {
+                    if let Some(statement) = parser.parse_rule(true)? {
+                        statements.push(Rule::LayerStatement(statement));
+                    }
                 }
                 _ => break,
             }
+
+            parser.optional_whitespace();
         }
 
-        let rules = parser.parse()?;
+        let mut rules: Vec<Rule> = parser.parse()?;
 
-        if let Some(token_at) = parser.tokens.next() {
-            Err(ParsingError::wrong_token(token_at, "end of file"))?
+        if imports.is_empty() {
+            statements.append(&mut rules);
+            rules = statements;
+            statements = Vec::new();
         }
 
-        Ok(Stylesheet { imports, rules })
+        // in error recovery mode rules can only stop early at an unmatched `}`
+        while let Some(token_at) = parser.tokens.next() {
+            parser.recover(ParsingError::wrong_token(token_at, "end of file"))?;
+            rules.append(&mut parser.parse()?);
+        }
+
+        Ok(Stylesheet {
+            statements,
+            imports,
+            rules,
+        })
+    }
+}
+
+impl ToCss for Stylesheet {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        // minified output has no line breaks at all
+        let line_break = if printer.is_minified() { "" } else { "\n" };
+
+        for statement in &self.statements {
+            printer.print(statement)?;
+            printer.write_str(line_break)?;
+        }
+
+        for import in &self.imports {
+            printer.print(import)?;
+            printer.write_str(line_break)?;
+        }
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            if printer.is_minified() && rule.is_empty() {
+                continue;
+            }
+
+            // pretty output separates top level rules with a blank line
+            if printer.is_pretty() && (index > 0 || !self.imports.is_empty()) {
+                printer.write_char('\n')?;
+            }
+
+            printer.print(rule)?;
+            printer.write_str(line_break)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Stylesheet {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
     }
 }
 
@@ -46,6 +161,7 @@ mod tests {
     use super::rule::media_rule::MediaRule;
     use super::url::Url;
     use super::*;
+    use crate::span::assert_eq_ignoring_spans;
 
     #[test]
     fn empty() {
@@ -53,6 +169,7 @@ mod tests {
 
         assert_eq!(
             Ok(Stylesheet {
+                statements: vec![],
                 imports: vec![],
                 rules: vec![]
             }),
@@ -66,15 +183,18 @@ mod tests {
     fn import() {
         let mut parser = Parser::new("@import url(example.com);".chars());
 
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Stylesheet {
+                statements: vec![],
                 imports: vec![Import {
                     url: Url("example.com".to_owned()),
-                    media_queries: vec![]
+                    layer: None,
+                    media_queries: vec![],
+                    span: Span::EMPTY,
                 }],
-                rules: vec![]
+                rules: vec![],
             }),
-            parser.parse()
+            parser.parse(),
         );
 
         assert_eq!(None, parser.tokens.next());
@@ -86,25 +206,32 @@ mod tests {
             "@import url(example.com/1);\n@import url(example.com/2);\n@import url(example.com/3);"
                 .chars(),
         );
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Stylesheet {
+                statements: vec![],
                 imports: vec![
                     Import {
                         url: Url("example.com/1".to_owned()),
-                        media_queries: vec![]
+                        layer: None,
+                        media_queries: vec![],
+                        span: Span::EMPTY,
                     },
                     Import {
                         url: Url("example.com/2".to_owned()),
-                        media_queries: vec![]
+                        layer: None,
+                        media_queries: vec![],
+                        span: Span::EMPTY,
                     },
                     Import {
                         url: Url("example.com/3".to_owned()),
-                        media_queries: vec![]
-                    }
+                        layer: None,
+                        media_queries: vec![],
+                        span: Span::EMPTY,
+                    },
                 ],
-                rules: vec![]
+                rules: vec![],
             }),
-            parser.parse()
+            parser.parse(),
         );
 
         assert_eq!(None, parser.tokens.next());
@@ -114,15 +241,18 @@ mod tests {
     fn import_with_leading_whitespace() {
         let mut parser = Parser::new(" @import url(example.com);".chars());
 
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Stylesheet {
+                statements: vec![],
                 imports: vec![Import {
                     url: Url("example.com".to_owned()),
-                    media_queries: vec![]
+                    layer: None,
+                    media_queries: vec![],
+                    span: Span::EMPTY,
                 }],
-                rules: vec![]
+                rules: vec![],
             }),
-            parser.parse()
+            parser.parse(),
         );
 
         assert_eq!(None, parser.tokens.next());
@@ -132,15 +262,17 @@ mod tests {
     fn media_rule() {
         let mut parser = Parser::new("@media screen { }".chars());
 
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Stylesheet {
+                statements: vec![],
                 imports: vec![],
                 rules: vec![Rule::MediaRule(MediaRule {
-                    media_queries: vec![MediaQuery::MediaType(MediaType::Screen)],
-                    rules: vec![]
-                })]
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Screen).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
+                })],
             }),
-            parser.parse()
+            parser.parse(),
         );
 
         assert_eq!(None, parser.tokens.next());
@@ -151,26 +283,247 @@ mod tests {
         let mut parser =
             Parser::new("@import url(example.com);\n@media screen { }\n@media print { }".chars());
 
-        assert_eq!(
+        assert_eq_ignoring_spans(
             Ok(Stylesheet {
+                statements: vec![],
                 imports: vec![Import {
                     url: Url("example.com".to_owned()),
-                    media_queries: vec![]
+                    layer: None,
+                    media_queries: vec![],
+                    span: Span::EMPTY,
                 }],
                 rules: vec![
                     Rule::MediaRule(MediaRule {
-                        media_queries: vec![MediaQuery::MediaType(MediaType::Screen)],
-                        rules: vec![]
+                        media_queries: vec![MediaQuery::MediaType(MediaType::Screen).into()],
+                        rules: vec![],
+                        span: Span::EMPTY,
                     }),
                     Rule::MediaRule(MediaRule {
-                        media_queries: vec![MediaQuery::MediaType(MediaType::Print)],
-                        rules: vec![]
-                    })
-                ]
+                        media_queries: vec![MediaQuery::MediaType(MediaType::Print).into()],
+                        rules: vec![],
+                        span: Span::EMPTY,
+                    }),
+                ],
             }),
-            parser.parse()
+            parser.parse(),
         );
 
         assert_eq!(None, parser.tokens.next());
     }
+    #[test]
+    fn error_without_recovery() {
+        assert!("@media tv { } @media print { }"
+            .parse::<Stylesheet>()
+            .is_err());
+    }
+
+    #[test]
+    fn recovery_collects_every_error() {
+        let (stylesheet, errors) = Stylesheet::parse_with_recovery(
+            "@import 5;\n@media tv { }\n@media print { }\n} 123 { }",
+        );
+
+        assert_eq_ignoring_spans(
+            Stylesheet {
+                statements: vec![],
+                imports: vec![],
+                rules: vec![Rule::MediaRule(MediaRule {
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Print).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
+                })],
+            },
+            stylesheet,
+        );
+
+        assert_eq_ignoring_spans(
+            vec![
+                ParsingError::WrongToken {
+                    line: 0,
+                    column: 8,
+                    expected: "a url or a string".to_owned(),
+                    found: "the number 5".to_owned(),
+                    span: Span::EMPTY,
+                },
+                ParsingError::WrongToken {
+                    line: 1,
+                    column: 7,
+                    expected: "all, print, or screen".to_owned(),
+                    found: "the identifier \"tv\"".to_owned(),
+                    span: Span::EMPTY,
+                },
+                ParsingError::WrongToken {
+                    line: 3,
+                    column: 0,
+                    expected: "end of file".to_owned(),
+                    found: "}".to_owned(),
+                    span: Span::EMPTY,
+                },
+                ParsingError::WrongToken {
+                    line: 3,
+                    column: 2,
+                    expected: "a rule".to_owned(),
+                    found: "the number 123".to_owned(),
+                    span: Span::EMPTY,
+                },
+            ],
+            errors,
+        );
+    }
+
+    #[test]
+    fn recovery_keeps_unclosed_block() {
+        let (stylesheet, errors) = Stylesheet::parse_with_recovery("@media print {");
+
+        assert_eq_ignoring_spans(
+            Stylesheet {
+                statements: vec![],
+                imports: vec![],
+                rules: vec![Rule::MediaRule(MediaRule {
+                    media_queries: vec![MediaQuery::MediaType(MediaType::Print).into()],
+                    rules: vec![],
+                    span: Span::EMPTY,
+                })],
+            },
+            stylesheet,
+        );
+
+        assert_eq_ignoring_spans(vec![ParsingError::end_of_file("}")], errors);
+    }
+
+    #[test]
+    fn recovery_skips_blocks_the_invalid_rule_opened() {
+        for (input, output) in [
+            ("div[{ color: red } p {color: red}", ""),
+            ("a:nth-child({} p{}", ""),
+            (
+                "@supports (color: red) and { p{} } p{color: red}",
+                "p{color:red}",
+            ),
+            (
+                "@media print { @supports (color: red) and { p{} } a{color: red} } b{color: red}",
+                "@media print{a{color:red}}b{color:red}",
+            ),
+        ] {
+            let (stylesheet, errors) = Stylesheet::parse_with_recovery(input);
+            assert_eq!(output, crate::printer::minify(&stylesheet), "{input}");
+            assert_eq!(1, errors.len(), "{input} had the errors {errors:?}");
+        }
+    }
+
+    #[test]
+    fn recovery_without_errors() {
+        let (stylesheet, errors) = Stylesheet::parse_with_recovery("* { color: red }");
+
+        assert_eq!(Ok(stylesheet), "* { color: red }".parse());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn spans() {
+        let input = "@import url(a.css) screen;\n\nh1, p > a {\n  color: red;\n}\n@media print and (color) {\n  b { margin: 0 }\n}\n";
+        let stylesheet: Stylesheet = input.parse().unwrap();
+        let text = |span: Span| span.source_text(input).unwrap();
+
+        let import = &stylesheet.imports[0];
+        assert_eq!("@import url(a.css) screen;", text(import.span));
+        assert_eq!("screen", text(import.media_queries[0].span));
+
+        let Rule::Ruleset(ruleset) = &stylesheet.rules[0] else {
+            panic!("expected a ruleset");
+        };
+        assert_eq!("h1, p > a {\n  color: red;\n}", text(ruleset.span));
+        assert_eq!("h1", text(ruleset.selectors[0].span));
+        assert_eq!("p > a", text(ruleset.selectors[1].span));
+        assert_eq!("color: red", text(ruleset.declarations[0].span));
+        assert_eq!(
+            Position {
+                offset: 42,
+                line: 3,
+                column: 2
+            },
+            ruleset.declarations[0].span.start
+        );
+
+        let Rule::MediaRule(media_rule) = &stylesheet.rules[1] else {
+            panic!("expected a media rule");
+        };
+        assert_eq!(
+            "@media print and (color) {\n  b { margin: 0 }\n}",
+            text(media_rule.span)
+        );
+        assert_eq!("print and (color)", text(media_rule.media_queries[0].span));
+    }
+
+    #[test]
+    fn statements_before_imports() {
+        let input = "@charset \"utf-8\";\n\
+             @layer base, components;\n\
+             @import url(\"a.css\") layer(components.buttons);\n\
+             @import url(\"b.css\");\n\
+             @layer utilities;\n";
+        let stylesheet: Stylesheet = input.parse().unwrap();
+
+        assert_eq!(2, stylesheet.statements.len());
+        assert_eq!(2, stylesheet.imports.len());
+        assert_eq!(1, stylesheet.rules.len());
+        assert_eq!(
+            vec!["base", "components", "components.buttons", "utilities"],
+            stylesheet
+                .layer_order()
+                .iter()
+                .map(LayerName::to_string)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(input, stylesheet.to_string());
+    }
+
+    #[test]
+    fn statements_without_imports_are_rules() {
+        let stylesheet: Stylesheet = "@layer a; b { color: red }".parse().unwrap();
+        assert!(stylesheet.statements.is_empty());
+        assert_eq!(2, stylesheet.rules.len());
+    }
+
+    #[test]
+    fn import_after_layer_statement_after_import() {
+        let stylesheet: Stylesheet = "@import url(a.css); @layer a; @import url(b.css);"
+            .parse()
+            .unwrap();
+        assert_eq!(1, stylesheet.imports.len());
+        assert!(matches!(stylesheet.rules[0], Rule::LayerStatement(_)));
+        assert!(matches!(stylesheet.rules[1], Rule::Unknown(_)));
+    }
+
+    #[test]
+    fn layer_order() {
+        let stylesheet: Stylesheet = "
+            @import url(a.css) layer(framework.base);
+            @layer reset, components;
+            @media print { @layer print {} }
+            @layer { @layer hidden {} }
+            @layer components { @layer buttons, reset; }
+            @layer utilities {}
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                "framework",
+                "framework.base",
+                "reset",
+                "components",
+                "print",
+                "components.buttons",
+                "components.reset",
+                "utilities"
+            ],
+            stylesheet
+                .layer_order()
+                .iter()
+                .map(LayerName::to_string)
+                .collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,259 @@
+use super::{
+    component_value::{trim_whitespace, ComponentValue},
+    condition::{print_condition, Condition, Operation},
+    selector::Selector,
+    *,
+};
+
+/// A test for whether the browser supports a feature like `(display: grid) and (not selector(a:hover))`
+///
+/// `and` and `or` can not be mixed without parentheses and `not` only applies to the condition right after it
+///
+/// adapted from https://www.w3.org/TR/css-conditional-3/#at-supports
+#[derive(Debug, PartialEq, Serialize)]
+pub enum SupportsCondition {
+    /// a declaration like `(display: grid)`, the property and value are kept as written since
+    /// the point of testing for them is that they might not be supported
+    Declaration {
+        name: String,
+        value: Vec<ComponentValue>,
+    },
+    Selector(Selector),
+    Not(Box<SupportsCondition>),
+    And(Box<SupportsCondition>, Box<SupportsCondition>),
+    Or(Box<SupportsCondition>, Box<SupportsCondition>),
+}
+
+impl Parsable for SupportsCondition {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        parser.parse_condition(
+            Parser::parse_supports_in_parentheses,
+            SupportsCondition::Not,
+            SupportsCondition::And,
+            SupportsCondition::Or,
+        )
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// parse a condition in parentheses, a declaration, or `selector()`
+    fn parse_supports_in_parentheses(&mut self) -> Result<SupportsCondition, ParsingError> {
+        const EXPECTED: &str = "( or selector(";
+
+        let token_at = match self.tokens.next() {
+            Some(token_at) => token_at,
+            None => return Err(ParsingError::end_of_file(EXPECTED)),
+        };
+
+        match &token_at.token {
+            Token::Function(name) if name.eq_ignore_ascii_case("selector") => {
+                self.optional_whitespace();
+                let selector = self.parse()?;
+                self.optional_whitespace();
+                self.expect(Token::CloseParenthesis())?;
+                Ok(SupportsCondition::Selector(selector))
+            }
+            Token::OpenParenthesis() => {
+                self.optional_whitespace();
+
+                let condition = match self.tokens.peek() {
+                    Some(TokenAt {
+                        token: Token::Identifier(name),
+                        ..
+                    }) if !name.eq_ignore_ascii_case("not") => {
+                        let Some(TokenAt {
+                            token: Token::Identifier(name),
+                            ..
+                        }) = self.tokens.next()
+                        else {
+                            unreachable!("the identifier was peeked")
+                        };
+
+                        self.consume_colon_separator()?;
+                        self.optional_whitespace();
+
+                        if let Some(
+                            token_at @ TokenAt {
+                                token: Token::CloseParenthesis(),
+                                ..
+                            },
+                        ) = self.tokens.peek()
+                        {
+                            return Err(ParsingError::wrong_token(token_at.clone(), "a value"));
+                        }
+
+                        let mut value =
+                            self.parse_component_values_until(Token::CloseParenthesis())?;
+                        trim_whitespace(&mut value);
+
+                        return Ok(SupportsCondition::Declaration { name, value });
+                    }
+                    Some(_) => self.parse()?,
+                    None => return Err(ParsingError::end_of_file("a supports condition")),
+                };
+
+                self.optional_whitespace();
+                self.expect(Token::CloseParenthesis())?;
+                Ok(condition)
+            }
+            _ => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+        }
+    }
+}
+
+impl Condition for SupportsCondition {
+    fn operation(&self) -> Option<Operation<'_, Self>> {
+        match self {
+            SupportsCondition::Not(operand) => Some(Operation::Not(operand)),
+            SupportsCondition::And(left, right) => Some(Operation::And(left, right)),
+            SupportsCondition::Or(left, right) => Some(Operation::Or(left, right)),
+            _ => None,
+        }
+    }
+}
+
+impl ToCss for SupportsCondition {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            SupportsCondition::Declaration { name, value } => {
+                printer.write_char('(')?;
+                printer.print_identifier(name)?;
+                printer.write_char(':')?;
+                printer.print_optional_space()?;
+                printer.print_component_values(value)?;
+                printer.write_char(')')
+            }
+            SupportsCondition::Selector(selector) => {
+                printer.write_str("selector(")?;
+                printer.print(selector)?;
+                printer.write_char(')')
+            }
+            SupportsCondition::Not(_)
+            | SupportsCondition::And(_, _)
+            | SupportsCondition::Or(_, _) => print_condition(printer, self),
+        }
+    }
+}
+
+impl fmt::Display for SupportsCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn declaration(name: &str, value: &str) -> SupportsCondition {
+        SupportsCondition::Declaration {
+            name: name.to_owned(),
+            value: vec![ComponentValue::Token(Token::Identifier(value.to_owned()))],
+        }
+    }
+
+    #[test]
+    fn declaration_condition() {
+        let mut parser = Parser::new("( display : grid )".chars());
+        assert_eq!(Ok(declaration("display", "grid")), parser.parse());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn selector() {
+        let mut parser = Parser::new("selector(a > b)".chars());
+        let condition: SupportsCondition = parser.parse().unwrap();
+        assert!(matches!(condition, SupportsCondition::Selector(_)));
+        assert_eq!("selector(a > b)", condition.to_string());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn not() {
+        let mut parser = Parser::new("not (display: grid)".chars());
+        assert_eq!(
+            Ok(SupportsCondition::Not(Box::new(declaration(
+                "display", "grid"
+            )))),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn and() {
+        let mut parser = Parser::new("(a: b) and (c: d) and (e: f)".chars());
+        assert_eq!(
+            Ok(SupportsCondition::And(
+                Box::new(declaration("a", "b")),
+                Box::new(SupportsCondition::And(
+                    Box::new(declaration("c", "d")),
+                    Box::new(declaration("e", "f"))
+                ))
+            )),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn or_with_nested_not() {
+        let mut parser = Parser::new("(not (a: b)) or (c: d)".chars());
+        assert_eq!(
+            Ok(SupportsCondition::Or(
+                Box::new(SupportsCondition::Not(Box::new(declaration("a", "b")))),
+                Box::new(declaration("c", "d"))
+            )),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn does_not_consume_trailing_whitespace() {
+        let mut parser = Parser::new("(a: b) {".chars());
+        assert_eq!(Ok(declaration("a", "b")), parser.parse());
+        assert_eq!(
+            Some(Token::Whitespace()),
+            parser.tokens.next().map(|token_at| token_at.token)
+        );
+    }
+
+    #[test]
+    fn mixed_and_or() {
+        let mut parser = Parser::new("(a: b) and (c: d) or (e: f)".chars());
+        assert!(parser.parse::<SupportsCondition>().is_err());
+    }
+
+    #[test]
+    fn not_without_parentheses() {
+        let mut parser = Parser::new("not not (a: b)".chars());
+        assert!(parser.parse::<SupportsCondition>().is_err());
+    }
+
+    #[test]
+    fn missing_value() {
+        for (input, column) in [("(display: )", 10), ("(color:)", 7)] {
+            let mut parser = Parser::new(input.chars());
+            assert!(matches!(
+                parser.parse::<SupportsCondition>(),
+                Err(ParsingError::WrongToken { column: found_column, expected, .. })
+                    if found_column == column && expected == "a value"
+            ));
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        let mut parser = Parser::new("((a:b) or (c:1px  2px)) and (not selector(.a))".chars());
+        let condition: SupportsCondition = parser.parse().unwrap();
+        assert_eq!(
+            "((a: b) or (c: 1px 2px)) and (not selector(.a))",
+            condition.to_string()
+        );
+        assert_eq!(
+            "((a:b) or (c:1px 2px)) and (not selector(.a))",
+            crate::printer::minify(&condition)
+        );
+    }
+}
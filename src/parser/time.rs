@@ -0,0 +1,81 @@
+use super::*;
+
+/// A duration like `2s` or `150ms`
+/// Grammar: `<time>`
+///
+/// adapted from https://www.w3.org/TR/css-values-4/#time
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+pub enum Time {
+    Seconds(f64),
+    Milliseconds(f64),
+}
+
+impl CommaSeparated for Time {}
+
+impl Parsable for Time {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        match parser.tokens.next() {
+            Some(token_at) => match token_at.token {
+                Token::Dimension(value, _, ref unit) if unit.eq_ignore_ascii_case("s") => {
+                    Ok(Time::Seconds(value))
+                }
+                Token::Dimension(value, _, ref unit) if unit.eq_ignore_ascii_case("ms") => {
+                    Ok(Time::Milliseconds(value))
+                }
+                _ => Err(ParsingError::wrong_token(token_at, "a time")),
+            },
+            None => Err(ParsingError::end_of_file("a time")),
+        }
+    }
+}
+
+impl ToCss for Time {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            Time::Seconds(value) => write!(printer, "{value}s"),
+            Time::Milliseconds(value) => write!(printer, "{value}ms"),
+        }
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seconds() {
+        let mut parser = Parser::new("1.5s".chars());
+        assert_eq!(Ok(Time::Seconds(1.5)), parser.parse());
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn milliseconds() {
+        let mut parser = Parser::new("-150MS".chars());
+        assert_eq!(Ok(Time::Milliseconds(-150.0)), parser.parse());
+    }
+
+    #[test]
+    fn unitless() {
+        let mut parser = Parser::new("0".chars());
+        assert!(parser.parse::<Time>().is_err());
+    }
+
+    #[test]
+    fn length() {
+        let mut parser = Parser::new("5px".chars());
+        assert!(parser.parse::<Time>().is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!("150ms", Time::Milliseconds(150.0).to_string());
+        assert_eq!("0.5s", Time::Seconds(0.5).to_string());
+    }
+}
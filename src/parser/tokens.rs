@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+
+use super::*;
+
+/// The tokens being parsed
+///
+/// this works like `Peekable` but can look more than one token ahead, and also remembers where the last token
+/// that is not whitespace ended, so that the parser knows where the syntax tree node it just finished ends
+///
+/// it also keeps track of the blocks and functions that have been opened but not closed, so that an invalid
+/// rule can be skipped over even when part of it was already consumed
+pub struct Tokens<I: Iterator<Item = char>> {
+    tokenizer: Tokenizer<I>,
+    /// tokens that have been peeked but not consumed yet
+    buffer: VecDeque<TokenAt>,
+    end: Position,
+    is_after_whitespace: bool,
+    /// the token that closes each block or function that is open, the innermost one is last
+    open_blocks: Vec<Token>,
+}
+
+impl<I: Iterator<Item = char>> Tokens<I> {
+    pub fn new(input: I) -> Self {
+        Self {
+            tokenizer: Tokenizer::new(input),
+            buffer: VecDeque::new(),
+            end: Position::default(),
+            is_after_whitespace: false,
+            open_blocks: Vec::new(),
+        }
+    }
+
+    /// returns a reference to the next token without consuming it
+    pub fn peek(&mut self) -> Option<&TokenAt> {
+        self.peek_nth(0)
+    }
+
+    /// returns a reference to the token `n` tokens ahead without consuming anything, `peek_nth(0)` is `peek()`
+    pub fn peek_nth(&mut self, n: usize) -> Option<&TokenAt> {
+        while self.buffer.len() <= n {
+            let token_at = self.tokenizer.next()?;
+            self.buffer.push_back(token_at);
+        }
+
+        self.buffer.get(n)
+    }
+
+    /// where the last token that was consumed and is not whitespace ended
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// how many blocks and functions have been opened by the consumed tokens and not closed yet
+    pub fn depth(&self) -> usize {
+        self.open_blocks.len()
+    }
+
+    /// the text a token was written as, `span` has to be the span of a token that was already peeked or consumed
+    pub fn source(&self, span: Span) -> &str {
+        self.tokenizer.source(span)
+    }
+
+    /// whether the last token that was consumed is whitespace
+    pub fn is_after_whitespace(&self) -> bool {
+        self.is_after_whitespace
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for Tokens<I> {
+    type Item = TokenAt;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token_at = match self.buffer.pop_front() {
+            Some(token_at) => token_at,
+            None => self.tokenizer.next()?,
+        };
+
+        self.is_after_whitespace = token_at.token == Token::Whitespace();
+        if !self.is_after_whitespace {
+            self.end = token_at.span.end;
+        }
+
+        // a closing token that does not match the innermost block is just a token in that block
+        match token_at.token {
+            Token::OpenCurlyBracket() => self.open_blocks.push(Token::CloseCurlyBracket()),
+            Token::OpenSquareBracket() => self.open_blocks.push(Token::CloseSquareBracket()),
+            Token::OpenParenthesis() | Token::Function(_) => {
+                self.open_blocks.push(Token::CloseParenthesis())
+            }
+            Token::CloseCurlyBracket()
+            | Token::CloseSquareBracket()
+            | Token::CloseParenthesis()
+                if self.open_blocks.last() == Some(&token_at.token) =>
+            {
+                self.open_blocks.pop();
+            }
+            _ => {}
+        }
+
+        Some(token_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn end_skips_whitespace() {
+        let mut tokens = Tokens::new("a  b ".chars());
+        assert_eq!(Position::default(), tokens.end());
+
+        tokens.next();
+        tokens.next();
+        assert_eq!(1, tokens.end().offset);
+
+        tokens.next();
+        tokens.next();
+        assert_eq!(4, tokens.end().offset);
+        assert_eq!(None, tokens.next());
+    }
+
+    #[test]
+    fn source() {
+        let mut tokens = Tokens::new("a +0001.50 b".chars());
+        tokens.next();
+        tokens.next();
+        let token_at = tokens.next().unwrap();
+        assert_eq!("+0001.50", tokens.source(token_at.span));
+    }
+
+    #[test]
+    fn peek_nth() {
+        let mut tokens = Tokens::new("a b".chars());
+        assert_eq!(
+            Some(&Token::Identifier("b".to_owned())),
+            tokens.peek_nth(2).map(|token_at| &token_at.token)
+        );
+        assert_eq!(None, tokens.peek_nth(3));
+
+        assert_eq!(
+            Some(Token::Identifier("a".to_owned())),
+            tokens.next().map(|token_at| token_at.token)
+        );
+        assert_eq!(
+            Some(Token::Whitespace()),
+            tokens.next().map(|token_at| token_at.token)
+        );
+        assert_eq!(2, tokens.peek().unwrap().span.start.offset);
+    }
+
+    #[test]
+    fn depth() {
+        let mut tokens = Tokens::new("a { b( [ } ] ) }".chars());
+        let depths: Vec<usize> =
+            std::iter::from_fn(|| tokens.next().map(|_| tokens.depth())).collect();
+        assert_eq!(vec![0, 0, 1, 1, 2, 2, 3, 3, 3, 3, 2, 2, 1, 1, 0], depths);
+    }
+}
@@ -0,0 +1,257 @@
+use super::*;
+
+/// A range of code points like `U+0000-00FF`, the start and end are inclusive
+/// Grammar: `<unicode-range>`
+///
+/// adapted from https://www.w3.org/TR/css-fonts-4/#unicode-range-desc
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub struct UnicodeRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl CommaSeparated for UnicodeRange {}
+
+impl Parsable for UnicodeRange {
+    /// the tokenizer reads a range like `U+0-FF` as an identifier and a dimension, and reads `u+a` the same way
+    /// in a selector, so the range is put back together from the text of the tokens that were written without
+    /// whitespace between them
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        const EXPECTED: &str = "a unicode range";
+
+        let token_at = match parser.tokens.next() {
+            Some(token_at) => token_at,
+            None => return Err(ParsingError::end_of_file(EXPECTED)),
+        };
+
+        match token_at.token {
+            Token::Identifier(ref name) if name.eq_ignore_ascii_case("u") => {}
+            _ => return Err(ParsingError::wrong_token(token_at, EXPECTED)),
+        }
+
+        match parse_unicode_range_text(&parser.consume_unicode_range_text()) {
+            Some((start, end)) if start <= end && end <= 0x10FFFF => {
+                Ok(UnicodeRange { start, end })
+            }
+            Some(_) => Err(ParsingError::wrong_token(
+                token_at,
+                "a unicode range that ends after it starts and at most at U+10FFFF",
+            )),
+            None => Err(ParsingError::wrong_token(token_at, EXPECTED)),
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// consume the tokens after the `u` of a unicode range and return the text they were written as
+    ///
+    /// the text comes from the source instead of the token values, so leading zeros like in `U+0000001` are kept
+    ///
+    /// adapted from https://www.w3.org/TR/css-syntax-3/#urange-syntax
+    fn consume_unicode_range_text(&mut self) -> String {
+        let mut text = String::new();
+
+        match self.tokens.peek().map(|token_at| &token_at.token) {
+            Some(Token::Delimiter('+')) => {
+                self.push_next_token_text(&mut text);
+
+                if let Some(Token::Identifier(_)) =
+                    self.tokens.peek().map(|token_at| &token_at.token)
+                {
+                    self.push_next_token_text(&mut text);
+                }
+            }
+            Some(Token::Dimension(..)) => self.push_next_token_text(&mut text),
+            Some(Token::Number(..)) => {
+                self.push_next_token_text(&mut text);
+
+                if let Some(Token::Number(..) | Token::Dimension(..)) =
+                    self.tokens.peek().map(|token_at| &token_at.token)
+                {
+                    self.push_next_token_text(&mut text);
+                }
+            }
+            _ => return text,
+        }
+
+        while let Some(Token::Delimiter('?')) = self.tokens.peek().map(|token_at| &token_at.token) {
+            self.push_next_token_text(&mut text);
+        }
+
+        text
+    }
+
+    /// consume the next token and add the text it was written as to `text`
+    fn push_next_token_text(&mut self, text: &mut String) {
+        if let Some(token_at) = self.tokens.next() {
+            text.push_str(self.tokens.source(token_at.span));
+        }
+    }
+}
+
+/// the start and end of a unicode range written like `+0-FF`, `+26`, or `+4??`
+///
+/// adapted from https://www.w3.org/TR/css-syntax-3/#urange-syntax
+fn parse_unicode_range_text(text: &str) -> Option<(u32, u32)> {
+    let text = text.strip_prefix('+')?;
+
+    let digits = text.len()
+        - text
+            .trim_start_matches(|c: char| c.is_ascii_hexdigit())
+            .len();
+    let (start, rest) = text.split_at(digits);
+    let wildcards = rest.len() - rest.trim_start_matches('?').len();
+    let (wildcard_text, rest) = rest.split_at(wildcards);
+
+    if start.len() + wildcards == 0 || start.len() + wildcards > 6 {
+        return None;
+    }
+
+    if wildcards > 0 {
+        if !rest.is_empty() {
+            return None;
+        }
+
+        let end = format!("{start}{}", wildcard_text.replace('?', "F"));
+        let start = format!("{start}{}", wildcard_text.replace('?', "0"));
+        return Some((parse_hex(&start)?, parse_hex(&end)?));
+    }
+
+    let start = parse_hex(start)?;
+
+    if rest.is_empty() {
+        return Some((start, start));
+    }
+
+    let end = rest.strip_prefix('-')?;
+    if end.is_empty() || end.len() > 6 || !end.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some((start, parse_hex(end)?))
+}
+
+fn parse_hex(hex: &str) -> Option<u32> {
+    u32::from_str_radix(hex, 16).ok()
+}
+
+impl ToCss for UnicodeRange {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        write!(printer, "U+{:X}", self.start)?;
+
+        if self.end != self.start {
+            write!(printer, "-{:X}", self.end)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for UnicodeRange {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unicode_ranges() {
+        let mut parser = Parser::new("U+0000-00FF, U+4??".chars());
+        assert_eq!(
+            Ok(vec![
+                UnicodeRange {
+                    start: 0,
+                    end: 0xFF
+                },
+                UnicodeRange {
+                    start: 0x400,
+                    end: 0x4FF
+                }
+            ]),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn written_like_other_tokens() {
+        for (input, start, end) in [
+            ("U+26", 0x26, 0x26),
+            ("u+a", 0xA, 0xA),
+            ("U+abc-def", 0xABC, 0xDEF),
+            ("U+0-7F", 0, 0x7F),
+            ("U+1e3", 0x1E3, 0x1E3),
+            ("U+00??", 0, 0xFF),
+            ("U+??", 0, 0xFF),
+            ("U+0000-00FF", 0, 0xFF),
+            ("U+10-20", 0x10, 0x20),
+        ] {
+            let mut parser = Parser::new(input.chars());
+            assert_eq!(
+                Ok(UnicodeRange { start, end }),
+                parser.parse(),
+                "{input} was not parsed"
+            );
+            assert_eq!(None, parser.tokens.next(), "{input} was not parsed");
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for input in [
+            "U+",
+            "U+z",
+            "u +1",
+            "U+1234567",
+            "U+0000001",
+            "U+0-0000001",
+            "U+00000??",
+            "U+1.5",
+            "U+???????",
+            "U+1-z",
+            "U+1+2",
+            "U.5",
+        ] {
+            let mut parser = Parser::new(input.chars());
+            assert!(
+                parser.parse::<UnicodeRange>().is_err(),
+                "{input} should not be a unicode range"
+            );
+        }
+    }
+
+    #[test]
+    fn backwards() {
+        let mut parser = Parser::new("U+FF-0".chars());
+        assert!(parser.parse::<UnicodeRange>().is_err());
+    }
+
+    #[test]
+    fn past_last_code_point() {
+        let mut parser = Parser::new("U+110000".chars());
+        assert!(parser.parse::<UnicodeRange>().is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            "U+0-FF",
+            UnicodeRange {
+                start: 0,
+                end: 0xFF
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "U+26",
+            UnicodeRange {
+                start: 0x26,
+                end: 0x26
+            }
+            .to_string()
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use super::*;
 use crate::tokenizer::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub struct Url(pub String);
 
 impl Parsable for Url {
@@ -41,6 +41,20 @@ impl<I: Iterator<Item = char>> Parser<I> {
     }
 }
 
+impl ToCss for Url {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("url(")?;
+        printer.print_string(&self.0)?;
+        printer.write_char(')')
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,196 @@
+use super::{
+    component_value::{trim_whitespace, ComponentValue},
+    *,
+};
+
+/// A reference to a custom property like `var(--brand-color, red)`
+///
+/// adapted from https://www.w3.org/TR/css-variables-1/#using-variables
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Variable {
+    /// the name of the custom property including the `--`
+    pub name: String,
+    /// the value to use if the custom property is not set, `Some(vec![])` for `var(--name,)`
+    pub fallback: Option<Vec<ComponentValue>>,
+}
+
+impl Parsable for Variable {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        match parser.tokens.next() {
+            Some(token_at) => match &token_at.token {
+                Token::Function(name) if name.eq_ignore_ascii_case("var") => {}
+                _ => return Err(ParsingError::wrong_token(token_at, "var(")),
+            },
+            None => return Err(ParsingError::end_of_file("var(")),
+        }
+
+        parser.optional_whitespace();
+
+        let name = match parser.tokens.next() {
+            Some(TokenAt {
+                token: Token::Identifier(name),
+                ..
+            }) if name.starts_with("--") => name,
+            Some(token_at) => {
+                return Err(ParsingError::wrong_token(
+                    token_at,
+                    "a custom property name",
+                ))
+            }
+            None => return Err(ParsingError::end_of_file("a custom property name")),
+        };
+
+        parser.optional_whitespace();
+
+        let fallback = match parser.tokens.next() {
+            Some(TokenAt {
+                token: Token::CloseParenthesis(),
+                ..
+            }) => None,
+            Some(TokenAt {
+                token: Token::Comma(),
+                ..
+            }) => {
+                let mut fallback = parser.parse_component_values_until(Token::CloseParenthesis())?;
+                trim_whitespace(&mut fallback);
+                Some(fallback)
+            }
+            Some(token_at) => return Err(ParsingError::wrong_token(token_at, ", or )")),
+            None => return Err(ParsingError::end_of_file(", or )")),
+        };
+
+        Ok(Variable { name, fallback })
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    /// whether the value of the declaration that is next uses `var()` anywhere, without consuming anything
+    pub fn is_value_with_variables(&mut self) -> bool {
+        let mut depth: usize = 0;
+
+        for n in 0.. {
+            match self.tokens.peek_nth(n).map(|token_at| &token_at.token) {
+                None => return false,
+                Some(Token::Function(name)) if name.eq_ignore_ascii_case("var") => return true,
+                Some(Token::Semicolon() | Token::CloseCurlyBracket()) if depth == 0 => {
+                    return false
+                }
+                Some(
+                    Token::OpenCurlyBracket()
+                    | Token::OpenParenthesis()
+                    | Token::OpenSquareBracket()
+                    | Token::Function(_),
+                ) => depth += 1,
+                Some(
+                    Token::CloseCurlyBracket()
+                    | Token::CloseParenthesis()
+                    | Token::CloseSquareBracket(),
+                ) => depth = depth.saturating_sub(1),
+                Some(_) => {}
+            }
+        }
+
+        unreachable!("the loop only ends by returning")
+    }
+}
+
+impl ToCss for Variable {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("var(")?;
+        printer.print_identifier(&self.name)?;
+
+        if let Some(fallback) = &self.fallback {
+            printer.write_char(',')?;
+            if !fallback.is_empty() {
+                printer.print_optional_space()?;
+                printer.print_component_values(fallback)?;
+            }
+        }
+
+        printer.write_char(')')
+    }
+}
+
+impl fmt::Display for Variable {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::Sign;
+
+    #[test]
+    fn variable() {
+        let mut parser = Parser::new("var(--brand-color)".chars());
+        assert_eq!(
+            Ok(Variable {
+                name: "--brand-color".to_owned(),
+                fallback: None,
+            }),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn fallback() {
+        let mut parser = Parser::new("var( --brand-color , red )".chars());
+        assert_eq!(
+            Ok(Variable {
+                name: "--brand-color".to_owned(),
+                fallback: Some(vec![ComponentValue::Token(Token::Identifier(
+                    "red".to_owned()
+                ))]),
+            }),
+            parser.parse()
+        );
+        assert_eq!(None, parser.tokens.next());
+    }
+
+    #[test]
+    fn nested_fallback() {
+        let mut parser = Parser::new("var(--a, var(--b, 1px 2px))".chars());
+        let variable: Variable = parser.parse().unwrap();
+        assert!(matches!(
+            variable.fallback.as_deref(),
+            Some([ComponentValue::Variable(_)])
+        ));
+        assert_eq!("var(--a, var(--b, 1px 2px))", variable.to_string());
+    }
+
+    #[test]
+    fn empty_fallback() {
+        let mut parser = Parser::new("var(--a,)".chars());
+        let variable: Variable = parser.parse().unwrap();
+        assert_eq!(Some(vec![]), variable.fallback);
+        assert_eq!("var(--a,)", variable.to_string());
+    }
+
+    #[test]
+    fn not_a_custom_property() {
+        let mut parser = Parser::new("var(color)".chars());
+        assert!(parser.parse::<Variable>().is_err());
+    }
+
+    #[test]
+    fn missing_close_parenthesis() {
+        let mut parser = Parser::new("var(--a red)".chars());
+        assert!(parser.parse::<Variable>().is_err());
+    }
+
+    #[test]
+    fn value_with_variables() {
+        let mut parser = Parser::new("1px var(--a); b".chars());
+        assert!(parser.is_value_with_variables());
+        assert_eq!(
+            Some(Token::Dimension(1.0, Sign::Signless, "px".to_owned())),
+            parser.tokens.next().map(|t| t.token)
+        );
+
+        let mut parser = Parser::new("1px; var(--a)".chars());
+        assert!(!parser.is_value_with_variables());
+    }
+}
@@ -0,0 +1,467 @@
+use std::fmt::{self, Write};
+
+use crate::parser::{declaration::css_wide_keyword::CssWideKeyword, rule::Rule};
+
+const INDENTATION: &str = "  ";
+
+/// How much whitespace a `Printer` writes
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Mode {
+    Compact,
+    Pretty,
+    Minified,
+}
+
+/// Writes a syntax tree back out as CSS
+pub struct Printer<'a> {
+    output: &'a mut dyn Write,
+    mode: Mode,
+    indentation: usize,
+}
+
+impl<'a> Printer<'a> {
+    /// Creates a new printer that writes everything on a single line
+    pub fn new(output: &'a mut dyn Write) -> Self {
+        Self {
+            output,
+            mode: Mode::Compact,
+            indentation: 0,
+        }
+    }
+
+    /// Creates a new printer that puts every rule and declaration on its own indented line
+    pub fn pretty(output: &'a mut dyn Write) -> Self {
+        Self {
+            mode: Mode::Pretty,
+            ..Self::new(output)
+        }
+    }
+
+    /// Creates a new printer that writes the smallest equivalent CSS
+    ///
+    /// the output is equivalent but may not parse back into an equal syntax tree,
+    /// e.g. empty rulesets are left out
+    pub fn minified(output: &'a mut dyn Write) -> Self {
+        Self {
+            mode: Mode::Minified,
+            ..Self::new(output)
+        }
+    }
+
+    /// whether this printer was created with `Printer::pretty`
+    pub fn is_pretty(&self) -> bool {
+        self.mode == Mode::Pretty
+    }
+
+    /// whether this printer was created with `Printer::minified`
+    pub fn is_minified(&self) -> bool {
+        self.mode == Mode::Minified
+    }
+
+    /// print any type that implements ToCss
+    pub fn print<T: ToCss + ?Sized>(&mut self, node: &T) -> fmt::Result {
+        node.to_css(self)
+    }
+
+    /// print a list of nodes separated by `, `, or just `,` when minified
+    pub fn print_comma_separated<T: ToCss>(&mut self, nodes: &[T]) -> fmt::Result {
+        for (index, node) in nodes.iter().enumerate() {
+            if index > 0 {
+                self.write_char(',')?;
+                self.print_optional_space()?;
+            }
+            self.print(node)?;
+        }
+        Ok(())
+    }
+
+    /// print a block like `{ a; b; }` with the terminator after each node
+    ///
+    /// in pretty mode each node is on its own line and indented one level further,
+    /// when minified the last terminator is left out
+    pub fn print_block<T: ToCss>(&mut self, nodes: &[T], terminator: &str) -> fmt::Result {
+        self.print_block_with(nodes, |_| terminator)
+    }
+
+    /// print the block of rules in an at-rule like `@media`, leaving out empty rules when minified
+    pub fn print_rule_block(&mut self, rules: &[Rule]) -> fmt::Result {
+        if self.is_minified() {
+            let rules: Vec<&Rule> = rules.iter().filter(|rule| !rule.is_empty()).collect();
+            self.print_block(&rules, "")
+        } else {
+            self.print_block(rules, "")
+        }
+    }
+
+    /// print a block like `print_block` where each node chooses its own terminator
+    pub fn print_block_with<'t, T: ToCss>(
+        &mut self,
+        nodes: &[T],
+        terminator: impl Fn(&T) -> &'t str,
+    ) -> fmt::Result {
+        self.print_optional_space()?;
+
+        if nodes.is_empty() {
+            return self.write_str("{}");
+        }
+
+        self.write_char('{')?;
+        self.indentation += 1;
+
+        for (index, node) in nodes.iter().enumerate() {
+            self.print_newline()?;
+            self.print(node)?;
+
+            if !self.is_minified() || index + 1 < nodes.len() {
+                self.write_str(terminator(node))?;
+            }
+        }
+
+        self.indentation -= 1;
+        self.print_newline()?;
+        self.write_char('}')
+    }
+
+    /// print a line break followed by the current indentation
+    ///
+    /// this is a single space when not pretty, and nothing when minified
+    pub fn print_newline(&mut self) -> fmt::Result {
+        match self.mode {
+            Mode::Compact => self.write_char(' '),
+            Mode::Minified => Ok(()),
+            Mode::Pretty => {
+                self.write_char('\n')?;
+                for _ in 0..self.indentation {
+                    self.write_str(INDENTATION)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// print a space that is only there for readability and is left out when minified
+    pub fn print_optional_space(&mut self) -> fmt::Result {
+        if self.is_minified() {
+            Ok(())
+        } else {
+            self.write_char(' ')
+        }
+    }
+
+    /// print an identifier escaping any characters that can not appear in an identifier
+    ///
+    /// adapted from https://www.w3.org/TR/cssom-1/#serialize-an-identifier
+    pub fn print_identifier(&mut self, identifier: &str) -> fmt::Result {
+        let first_character = identifier.chars().next();
+
+        for (index, character) in identifier.chars().enumerate() {
+            match character {
+                '\0' => self.write_char(char::REPLACEMENT_CHARACTER)?,
+                '\x01'..='\x1F' | '\x7F' => self.print_hex_escape(character)?,
+                '0'..='9' if index == 0 => self.print_hex_escape(character)?,
+                '0'..='9' if index == 1 && first_character == Some('-') => {
+                    self.print_hex_escape(character)?
+                }
+                '-' if index == 0 && identifier.len() == 1 => self.write_str("\\-")?,
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '\u{80}'.. => {
+                    self.write_char(character)?
+                }
+                _ => {
+                    self.write_char('\\')?;
+                    self.write_char(character)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// print a name chosen by the author like a font family as an identifier, or as a string when it
+    /// could not be read back as the same name, such as a CSS-wide keyword or one of the `keywords`
+    pub fn print_custom_identifier(&mut self, name: &str, keywords: &[&str]) -> fmt::Result {
+        if is_custom_identifier(name, keywords) {
+            self.write_str(name)
+        } else {
+            self.print_string(name)
+        }
+    }
+
+    /// print a name like the one after `#` in `#0f0`, unlike an identifier it can start with a digit or `--`
+    pub fn print_name(&mut self, name: &str) -> fmt::Result {
+        for character in name.chars() {
+            match character {
+                '\0' => self.write_char(char::REPLACEMENT_CHARACTER)?,
+                '\x01'..='\x1F' | '\x7F' => self.print_hex_escape(character)?,
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '\u{80}'.. => {
+                    self.write_char(character)?
+                }
+                _ => {
+                    self.write_char('\\')?;
+                    self.write_char(character)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// print a string wrapped in double quotes escaping any characters that need it
+    ///
+    /// adapted from https://www.w3.org/TR/cssom-1/#serialize-a-string
+    pub fn print_string(&mut self, string: &str) -> fmt::Result {
+        self.write_char('"')?;
+
+        for character in string.chars() {
+            match character {
+                '\0' => self.write_char(char::REPLACEMENT_CHARACTER)?,
+                '\x01'..='\x1F' | '\x7F' => self.print_hex_escape(character)?,
+                '"' | '\\' => {
+                    self.write_char('\\')?;
+                    self.write_char(character)?;
+                }
+                _ => self.write_char(character)?,
+            }
+        }
+
+        self.write_char('"')
+    }
+
+    /// print a character as an escaped code point like `\31 `
+    fn print_hex_escape(&mut self, character: char) -> fmt::Result {
+        write!(self, "\\{:x} ", character as u32)
+    }
+}
+
+impl Write for Printer<'_> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        self.output.write_str(string)
+    }
+}
+
+/// This trait signifies that a type can be written back out as CSS
+///
+/// the output can be parsed back into an equal value
+pub trait ToCss {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result;
+}
+
+impl<T: ToCss + ?Sized> ToCss for &T {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.print(*self)
+    }
+}
+
+impl<T: ToCss> ToCss for Box<T> {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.print(self.as_ref())
+    }
+}
+
+/// Formats a node with `Printer::pretty`
+pub fn format<T: ToCss + ?Sized>(node: &T) -> String {
+    let mut output = String::new();
+    Printer::pretty(&mut output)
+        .print(node)
+        .expect("writing to a string can not fail");
+    output
+}
+
+/// Formats a node with `Printer::minified`
+pub fn minify<T: ToCss + ?Sized>(node: &T) -> String {
+    let mut output = String::new();
+    Printer::minified(&mut output)
+        .print(node)
+        .expect("writing to a string can not fail");
+    output
+}
+
+/// if a name can be written as an identifier that is read back as the same name, which it can not be
+/// if it needs escaping or is a CSS-wide keyword, `default`, or one of the `keywords` that would be read as
+/// something else
+///
+/// adapted from https://www.w3.org/TR/css-values-4/#custom-idents
+pub(crate) fn is_custom_identifier(name: &str, keywords: &[&str]) -> bool {
+    let mut identifier = String::new();
+    Printer::new(&mut identifier).print_identifier(name).is_ok()
+        && !name.is_empty()
+        && identifier == name
+        && name.parse::<CssWideKeyword>().is_err()
+        && !name.eq_ignore_ascii_case("default")
+        && !keywords
+            .iter()
+            .any(|keyword| name.eq_ignore_ascii_case(keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::*;
+    use crate::parser::Stylesheet;
+    use crate::span::assert_eq_ignoring_spans;
+
+    fn identifier(input: &str) -> String {
+        let mut output = String::new();
+        Printer::new(&mut output).print_identifier(input).unwrap();
+        output
+    }
+
+    fn string(input: &str) -> String {
+        let mut output = String::new();
+        Printer::new(&mut output).print_string(input).unwrap();
+        output
+    }
+
+    #[test]
+    fn custom_identifiers() {
+        assert!(is_custom_identifier("fade-in", &[]));
+        assert!(!is_custom_identifier("fade in", &[]));
+        assert!(!is_custom_identifier("", &[]));
+        assert!(!is_custom_identifier("Inherit", &[]));
+        assert!(!is_custom_identifier("default", &[]));
+        assert!(!is_custom_identifier("NONE", &["none"]));
+    }
+
+    #[test]
+    fn plain_identifier() {
+        assert_eq!("a-b_c1", identifier("a-b_c1"));
+    }
+
+    #[test]
+    fn identifier_with_special_character() {
+        assert_eq!("sm\\:hidden", identifier("sm:hidden"));
+    }
+
+    #[test]
+    fn identifier_starting_with_digit() {
+        assert_eq!("\\31 0", identifier("10"));
+    }
+
+    #[test]
+    fn identifier_starting_with_hyphen_and_digit() {
+        assert_eq!("-\\31 ", identifier("-1"));
+    }
+
+    #[test]
+    fn hyphen_identifier() {
+        assert_eq!("\\-", identifier("-"));
+    }
+
+    #[test]
+    fn non_ascii_identifier() {
+        assert_eq!("café", identifier("café"));
+    }
+
+    #[test]
+    fn plain_string() {
+        assert_eq!("\"a b\"", string("a b"));
+    }
+
+    #[test]
+    fn string_with_quotes() {
+        assert_eq!("\"a \\\" \\\\ b\"", string("a \" \\ b"));
+    }
+
+    #[test]
+    fn string_with_newline() {
+        assert_eq!("\"a\\a b\"", string("a\nb"));
+    }
+
+    #[test]
+    fn round_trip_test_files() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_files/good");
+
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            let input = fs::read_to_string(&path).unwrap();
+
+            let stylesheet: Stylesheet = input.parse().unwrap();
+            let output = stylesheet.to_string();
+
+            let reparsed: Stylesheet = output
+                .parse()
+                .unwrap_or_else(|error| panic!("{} did not round trip: {error}", path.display()));
+            assert_eq_ignoring_spans(&stylesheet, &reparsed);
+            assert_eq!(output, reparsed.to_string());
+        }
+    }
+
+    #[test]
+    fn format_stylesheet() {
+        let stylesheet: Stylesheet =
+            "@import url(a.css);div>p{color:red;margin:0}@media print{a{}b{color:blue}}"
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            "@import url(\"a.css\");\n\
+             \n\
+             div > p {\n\
+             \x20 color: red;\n\
+             \x20 margin: 0;\n\
+             }\n\
+             \n\
+             @media print {\n\
+             \x20 a {}\n\
+             \x20 b {\n\
+             \x20   color: blue;\n\
+             \x20 }\n\
+             }\n",
+            format(&stylesheet)
+        );
+    }
+
+    #[test]
+    fn format_test_files() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_files/good");
+
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            let stylesheet: Stylesheet = fs::read_to_string(&path).unwrap().parse().unwrap();
+            let output = format(&stylesheet);
+
+            let reparsed: Stylesheet = output
+                .parse()
+                .unwrap_or_else(|error| panic!("{} did not round trip: {error}", path.display()));
+            assert_eq_ignoring_spans(&stylesheet, &reparsed);
+            assert_eq!(output, format(&reparsed));
+        }
+    }
+
+    #[test]
+    fn minify_stylesheet() {
+        let stylesheet: Stylesheet = "@import url(a.css) screen, print;\n\
+             div > p, a:not(.b) { color: rgb(255, 0, 0); margin: 0 0 0 0; font-family: Arial, serif }\n\
+             a {}\n\
+             @media (min-width: 100px) { a {} b { padding: 1px 2px 1px 2px; } }\n\
+             @media print { c {} }\n\
+             p { width: 0px; height: calc(0px + 1em); transform: translate( 1px , 2px ) }\n\
+             @page :first { margin: 1in }"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            "@import url(\"a.css\") screen,print;\
+             div>p,a:not(.b){color:#f00;margin:0;font-family:Arial,serif}\
+             @media (min-width:100px){b{padding:1px 2px}}\
+             p{width:0;height:calc(0px + 1em);transform:translate(1px,2px)}\
+             @page :first{margin:1in}",
+            minify(&stylesheet)
+        );
+    }
+
+    #[test]
+    fn minify_test_files() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_files/good");
+
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            let stylesheet: Stylesheet = fs::read_to_string(&path).unwrap().parse().unwrap();
+            let output = minify(&stylesheet);
+
+            let reparsed: Result<Stylesheet, _> = output.parse();
+            assert!(reparsed.is_ok(), "{} did not reparse", path.display());
+            assert_eq!(output, minify(&reparsed.unwrap()));
+        }
+    }
+}
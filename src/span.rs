@@ -0,0 +1,195 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use serde::Serialize;
+
+use crate::parser::{CommaSeparated, Parsable, Parser, ParsingError};
+use crate::printer::{Printer, ToCss};
+
+/// A location in the source text
+///
+/// `offset` is in bytes while `line` and `column` are in characters, all of them start at zero
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default, Serialize)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// the position of the first character
+    pub const START: Position = Position {
+        offset: 0,
+        line: 0,
+        column: 0,
+    };
+}
+
+/// The range of source text something was parsed from, `end` is exclusive
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    /// an empty span at the start of the source, this is useful for building syntax trees by hand
+    pub const EMPTY: Span = Span::new(Position::START, Position::START);
+
+    pub const fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    /// the part of the source text this span covers
+    ///
+    /// `None` if the span is not in the source or not on character boundaries
+    pub fn source_text<'a>(&self, source: &'a str) -> Option<&'a str> {
+        source.get(self.start.offset..self.end.offset)
+    }
+}
+
+/// A syntax tree node along with the span it was parsed from
+///
+/// this is used for enums like `Declaration` which can not have a span field of their own
+#[derive(Debug, PartialEq, Clone, Default, Serialize)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(node: T, span: Span) -> Self {
+        Self { node, span }
+    }
+}
+
+/// wraps a node with `Span::EMPTY`
+impl<T> From<T> for Spanned<T> {
+    fn from(node: T) -> Self {
+        Self::new(node, Span::EMPTY)
+    }
+}
+
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.node
+    }
+}
+
+impl<T> DerefMut for Spanned<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.node
+    }
+}
+
+impl<T: Parsable> Parsable for Spanned<T> {
+    fn parse<I: Iterator<Item = char>>(parser: &mut Parser<I>) -> Result<Self, ParsingError> {
+        let start = parser.position();
+        let node = parser.parse()?;
+        Ok(Self::new(node, parser.span_from(start)))
+    }
+}
+
+impl<T: CommaSeparated> CommaSeparated for Spanned<T> {}
+
+impl<T: ToCss> ToCss for Spanned<T> {
+    fn to_css(&self, printer: &mut Printer) -> fmt::Result {
+        printer.print(&self.node)
+    }
+}
+
+impl<T: ToCss> fmt::Display for Spanned<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(formatter).print(self)
+    }
+}
+
+/// asserts that two syntax trees are equal no matter where they were parsed from
+#[cfg(test)]
+#[track_caller]
+pub(crate) fn assert_eq_ignoring_spans<T: Serialize + fmt::Debug>(expected: T, actual: T) {
+    fn without_spans(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                map.remove("span");
+                map.values_mut().for_each(without_spans);
+            }
+            serde_json::Value::Array(values) => values.iter_mut().for_each(without_spans),
+            _ => {}
+        }
+    }
+
+    let mut expected_value = serde_json::to_value(&expected).unwrap();
+    let mut actual_value = serde_json::to_value(&actual).unwrap();
+    without_spans(&mut expected_value);
+    without_spans(&mut actual_value);
+    assert!(
+        expected_value == actual_value,
+        "syntax trees are not equal\nexpected: {expected:#?}\n  actual: {actual:#?}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::color::Color;
+    use crate::parser::declaration::Declaration;
+
+    #[test]
+    fn spans_compare_positions() {
+        let span = Span::new(
+            Position::default(),
+            Position {
+                offset: 3,
+                line: 0,
+                column: 3,
+            },
+        );
+        assert_ne!(Span::default(), span);
+        assert_eq!(span, span);
+    }
+
+    #[test]
+    fn ignoring_spans() {
+        let mut parser = Parser::new("color: red".chars());
+        let declaration: Spanned<Declaration> = parser.parse().unwrap();
+        let expected = Spanned::from(Declaration::Color(Color::Red.into()));
+        assert_ne!(expected, declaration);
+        assert_eq_ignoring_spans(expected, declaration);
+    }
+
+    #[test]
+    fn source_text() {
+        let span = Span::new(
+            Position {
+                offset: 1,
+                line: 0,
+                column: 1,
+            },
+            Position {
+                offset: 4,
+                line: 0,
+                column: 4,
+            },
+        );
+        assert_eq!(Some("bcd"), span.source_text("abcde"));
+        assert_eq!(None, span.source_text("ab"));
+    }
+
+    #[test]
+    fn spanned_declaration() {
+        let mut parser = Parser::new("color: red ;".chars());
+        let declaration: Spanned<Declaration> = parser.parse().unwrap();
+        assert_eq!(Position::default(), declaration.span.start);
+        assert_eq!(
+            Position {
+                offset: 10,
+                line: 0,
+                column: 10
+            },
+            declaration.span.end
+        );
+    }
+}
@@ -1,17 +1,21 @@
 use std::fmt;
-use std::iter::Peekable;
 
 mod line_counter;
+mod lookahead;
 
 #[cfg(test)]
 mod tests;
 
+use serde::Serialize;
+
+use crate::span::{Position, Span};
 use line_counter::LineCounter;
+use lookahead::Lookahead;
 
 /// All the types of tokens found in CSS
 ///
 /// adapted from https://www.w3.org/TR/css-syntax-3/#tokenization
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub enum Token {
     BadComment(),
     Identifier(String),
@@ -23,9 +27,9 @@ pub enum Token {
     Url(String),
     BadUrl(),
     Delimiter(char),
-    Number(f64),
+    Number(f64, Sign),
     Percentage(f64),
-    Dimension(f64, String),
+    Dimension(f64, Sign, String),
     Whitespace(),
     Colon(),
     Semicolon(),
@@ -38,7 +42,28 @@ pub enum Token {
     CloseCurlyBracket(),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// The sign a number was written with
+///
+/// `+1` and `1` have the same value, but An+B and unicode ranges need to tell them apart
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum Sign {
+    /// written without a sign like `1`
+    Signless,
+    Plus,
+    Minus,
+}
+
+impl Sign {
+    /// what to write before a number's value to keep its sign, the value already has the `-`
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Sign::Plus => "+",
+            Sign::Signless | Sign::Minus => "",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
 pub enum HashType {
     Id,
     Unrestricted,
@@ -58,9 +83,11 @@ impl fmt::Display for Token {
             Self::Url(_) => write!(f, "a url"),
             Self::BadUrl() => write!(f, "an invalid url"),
             Self::Delimiter(char) => write!(f, "{char}"),
-            Self::Number(value) => write!(f, "the number {value}"),
+            Self::Number(value, sign) => write!(f, "the number {}{value}", sign.prefix()),
             Self::Percentage(value) => write!(f, "{value}%"),
-            Self::Dimension(value, unit) => write!(f, "the dimension {value}{unit}"),
+            Self::Dimension(value, sign, unit) => {
+                write!(f, "the dimension {}{value}{unit}", sign.prefix())
+            }
             Self::Whitespace() => write!(f, "some whitespace"),
             Self::Colon() => write!(f, ":"),
             Self::Semicolon() => write!(f, ";"),
@@ -77,27 +104,104 @@ impl fmt::Display for Token {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct TokenAt {
-    pub line: usize,
-    pub column: usize,
     pub token: Token,
+    pub span: Span,
+}
+
+/// If a character can start an identifier
+///
+/// https://www.w3.org/TR/css-syntax-3/#ident-start-code-point
+fn is_identifier_start(character: char) -> bool {
+    matches!(character, 'a'..='z' | 'A'..='Z' | '_' | '\u{80}'..)
+}
+
+/// If a character can be part of an identifier
+///
+/// https://www.w3.org/TR/css-syntax-3/#ident-code-point
+fn is_identifier_character(character: char) -> bool {
+    is_identifier_start(character) || matches!(character, '0'..='9' | '-')
+}
+
+/// If two characters are a valid escape
+///
+/// https://www.w3.org/TR/css-syntax-3/#starts-with-a-valid-escape
+fn is_valid_escape(first: Option<char>, second: Option<char>) -> bool {
+    first == Some('\\') && second != Some('\n')
+}
+
+/// If three characters would start an identifier
+///
+/// https://www.w3.org/TR/css-syntax-3/#would-start-an-identifier
+fn starts_identifier(first: Option<char>, second: Option<char>, third: Option<char>) -> bool {
+    match first {
+        Some('-') => match second {
+            Some(character) if is_identifier_start(character) || character == '-' => true,
+            _ => is_valid_escape(second, third),
+        },
+        Some('\\') => is_valid_escape(first, second),
+        Some(character) => is_identifier_start(character),
+        None => false,
+    }
+}
+
+/// If three characters would start a number
+///
+/// https://www.w3.org/TR/css-syntax-3/#starts-with-a-number
+fn starts_number(first: Option<char>, second: Option<char>, third: Option<char>) -> bool {
+    match first {
+        Some('+' | '-') => match second {
+            Some('0'..='9') => true,
+            Some('.') => matches!(third, Some('0'..='9')),
+            _ => false,
+        },
+        Some('.') => matches!(second, Some('0'..='9')),
+        Some('0'..='9') => true,
+        _ => false,
+    }
 }
 
 /// Converts a iterator of characters into an iterator of tokens
 pub struct Tokenizer<I: Iterator<Item = char>> {
-    chars: Peekable<LineCounter<I>>,
+    chars: Lookahead<LineCounter<I>>,
+    /// comments are skipped instead of being tokens, but this remembers that there were some
+    has_comments: bool,
 }
 
 impl<I: Iterator<Item = char>> Tokenizer<I> {
     /// Creates a new tokenizer from a iterator of characters
     pub fn new(chars: I) -> Self {
         Self {
-            chars: LineCounter::new(chars).peekable(),
+            chars: Lookahead::new(LineCounter::new(chars)),
+            has_comments: false,
         }
     }
 
+    /// whether any comments have been skipped over so far
+    pub fn has_comments(&self) -> bool {
+        self.has_comments
+    }
+
+    /// the text a token was written as, `span` has to be the span of a token that was already returned
+    pub fn source(&self, span: Span) -> &str {
+        &self.chars.get_ref().source()[span.start.offset..span.end.offset]
+    }
+
+    /// the position of the next character that has not been consumed
+    fn position(&mut self) -> Position {
+        match self.chars.peek() {
+            Some((position, _)) => *position,
+            None => self.chars.get_ref().position(),
+        }
+    }
+
+    /// looks at the character `n` places ahead without consuming anything
+    fn peek_character(&mut self, n: usize) -> Option<char> {
+        self.chars.peek_nth(n).map(|&(_, character)| character)
+    }
+
     /// consumes whitespace from chars
     fn consume_whitespace(&mut self) {
-        while let Some((_, _, character)) = self.chars.peek() {
+        while let Some((_, character)) = self.chars.peek() {
             match character {
                 ' ' | '\t' | '\r' | '\n' => {
                     self.chars.next();
@@ -107,39 +211,94 @@ impl<I: Iterator<Item = char>> Tokenizer<I> {
         }
     }
 
-    /// If the next character would start a identifier
+    /// If the next characters would start a identifier
     fn would_start_identifier(&mut self) -> bool {
-        match self.chars.peek() {
-            Some((_, _, character)) => match character {
-                'a'..='z' | 'A'..='Z' | '_' => true,
-                '\\' => unimplemented!("escape sequences"),
-                _ => false,
-            },
-            None => false,
+        starts_identifier(
+            self.peek_character(0),
+            self.peek_character(1),
+            self.peek_character(2),
+        )
+    }
+
+    /// If the next characters are a valid escape
+    fn would_start_escape(&mut self) -> bool {
+        is_valid_escape(self.peek_character(0), self.peek_character(1))
+    }
+
+    /// Consumes an escaped code point
+    ///
+    /// Assumes that the `\` has already been consumed and that it is a valid escape
+    ///
+    /// adapted from https://www.w3.org/TR/css-syntax-3/#consume-escaped-code-point
+    fn consume_escaped_code_point(&mut self) -> char {
+        match self.chars.next() {
+            Some((_, character)) if character.is_ascii_hexdigit() => {
+                self.consume_hex_escape(character)
+            }
+            Some((_, character)) => character,
+            None => char::REPLACEMENT_CHARACTER,
         }
     }
 
-    /// Consumes a sequence of numbers letters hyphens and underscores
-    fn consume_identifier_sequence(&mut self) -> String {
-        let mut identifier = String::new();
+    /// Consumes the rest of a hex escape like `\201C`
+    ///
+    /// Assumes that the `\` and the first hex digit have already been consumed
+    fn consume_hex_escape(&mut self, first_digit: char) -> char {
+        let mut hex = String::from(first_digit);
 
-        while let Some(&(_, _, character)) = self.chars.peek() {
-            match character {
-                'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' => {
+        while hex.len() < 6 {
+            match self.peek_character(0) {
+                Some(character) if character.is_ascii_hexdigit() => {
                     self.chars.next();
-                    identifier.push(character);
-                }
-                '\\' => {
-                    unimplemented!("escape sequences");
+                    hex.push(character);
                 }
                 _ => break,
             }
         }
 
+        // a single whitespace character after a hex escape is part of the escape
+        match self.peek_character(0) {
+            Some('\r') => {
+                self.chars.next();
+                if let Some('\n') = self.peek_character(0) {
+                    self.chars.next();
+                }
+            }
+            Some(' ' | '\t' | '\n') => {
+                self.chars.next();
+            }
+            _ => {}
+        }
+
+        let code_point = u32::from_str_radix(&hex, 16)
+            .expect("failed to parse hex digits, this should never happen");
+
+        match code_point {
+            0 => char::REPLACEMENT_CHARACTER,
+            _ => char::from_u32(code_point).unwrap_or(char::REPLACEMENT_CHARACTER),
+        }
+    }
+
+    /// Consumes a sequence of identifier characters and escapes
+    fn consume_identifier_sequence(&mut self) -> String {
+        let mut identifier = String::new();
+
+        while let Some(character) = self.peek_character(0) {
+            if is_identifier_character(character) {
+                self.chars.next();
+                identifier.push(character);
+            } else if self.would_start_escape() {
+                self.chars.next();
+                identifier.push(self.consume_escaped_code_point());
+            } else {
+                break;
+            }
+        }
+
         identifier
     }
 
-    /// Consumes a sequence of numbers letters hyphens and underscores then returns a Token
+    /// Consumes a sequence of identifier characters and escapes then returns a Token
     ///
     /// can return a Token::Identifier, Token::Function, Token::Url, or Token::BadUrl
     fn consume_identifier_like_token(&mut self, first_character: char) -> Token {
@@ -147,13 +306,13 @@ impl<I: Iterator<Item = char>> Tokenizer<I> {
 
         identifier.insert(0, first_character);
 
-        if let Some((_, _, '(')) = self.chars.peek() {
+        if let Some((_, '(')) = self.chars.peek() {
             self.chars.next();
 
             if identifier.eq_ignore_ascii_case("url") {
                 self.consume_whitespace();
 
-                if let Some((_, _, '"' | '\'')) = self.chars.peek() {
+                if let Some((_, '"' | '\'')) = self.chars.peek() {
                     Token::Function(identifier)
                 } else {
                     self.consume_url_token()
@@ -176,13 +335,17 @@ impl<I: Iterator<Item = char>> Tokenizer<I> {
 
         let mut url = String::new();
 
-        while let Some(&(_, _, character)) = self.chars.peek() {
+        while let Some(&(_, character)) = self.chars.peek() {
             match character {
                 ')' => break,
                 ' ' | '\t' | '\r' | '\n' => break,
                 '"' | '\'' | '(' | '\0' | '\x08' | '\x0B' | '\x0E'..='\x1F' | '\x7F' => break,
                 '\\' => {
-                    unimplemented!("escape sequences");
+                    if !self.would_start_escape() {
+                        break;
+                    }
+                    self.chars.next();
+                    url.push(self.consume_escaped_code_point());
                 }
                 _ => {
                     self.chars.next();
@@ -197,7 +360,7 @@ impl<I: Iterator<Item = char>> Tokenizer<I> {
             return Token::BadUrl();
         }
 
-        if let Some((_, _, ')')) = self.chars.peek() {
+        if let Some((_, ')')) = self.chars.peek() {
             self.chars.next();
             Token::Url(url)
         } else {
@@ -205,15 +368,20 @@ impl<I: Iterator<Item = char>> Tokenizer<I> {
         }
     }
 
-    /// Consume a number
-    fn consume_number(&mut self, first_character: char) -> f64 {
+    /// Consume a number along with the sign it was written with
+    fn consume_number(&mut self, first_character: char) -> (f64, Sign) {
+        let sign = match first_character {
+            '+' => Sign::Plus,
+            '-' => Sign::Minus,
+            _ => Sign::Signless,
+        };
         let mut number = String::new();
 
         number.push(first_character);
 
         let mut has_dot = first_character == '.';
 
-        while let Some(&(_, _, character)) = self.chars.peek() {
+        while let Some(&(_, character)) = self.chars.peek() {
             match character {
                 '0'..='9' => {
                     self.chars.next();
@@ -232,27 +400,30 @@ impl<I: Iterator<Item = char>> Tokenizer<I> {
             }
         }
 
-        number
+        let value = number
             .parse()
-            .expect("failed to parse number, this should never happen")
+            .expect("failed to parse number, this should never happen");
+
+        (value, sign)
     }
 
     /// Consume a numeric token
     ///
     /// can return a Token::Number, Token::Percentage, or Token::Dimension
     fn consume_numeric_token(&mut self, first_character: char) -> Token {
-        let number = self.consume_number(first_character);
+        let (number, sign) = self.consume_number(first_character);
+
+        if self.would_start_identifier() {
+            let unit = self.consume_identifier_sequence();
+            return Token::Dimension(number, sign, unit);
+        }
 
         match self.chars.peek() {
-            Some((_, _, 'a'..='z' | 'A'..='Z' | '_')) => {
-                let unit = self.consume_identifier_sequence();
-                Token::Dimension(number, unit)
-            }
-            Some((_, _, '%')) => {
+            Some((_, '%')) => {
                 self.chars.next();
                 Token::Percentage(number)
             }
-            _ => Token::Number(number),
+            _ => Token::Number(number, sign),
         }
     }
 
@@ -261,26 +432,25 @@ impl<I: Iterator<Item = char>> Tokenizer<I> {
         let mut string = String::new();
 
         #[allow(clippy::while_let_on_iterator)] // because we are not exhausting chars
-        while let Some((_, _, character)) = self.chars.next() {
+        while let Some((_, character)) = self.chars.next() {
             match character {
-                '"' | '\'' => {
-                    if character == end_character {
-                        return Token::String(string);
-                    } else {
-                        string.push(character);
-                    }
+                '"' | '\'' if character == end_character => {
+                    return Token::String(string);
                 }
                 '\n' => {
                     return Token::BadString();
                 }
                 '\\' => match self.chars.next() {
-                    Some((_, _, character)) => match character {
+                    Some((_, character)) => match character {
                         '\r' => {
-                            if let Some((_, _, '\n')) = self.chars.peek() {
+                            if let Some((_, '\n')) = self.chars.peek() {
                                 self.chars.next();
                             }
                         }
                         '\n' => {}
+                        '0'..='9' | 'a'..='f' | 'A'..='F' => {
+                            string.push(self.consume_hex_escape(character));
+                        }
                         _ => {
                             string.push(character);
                         }
@@ -303,18 +473,19 @@ impl<I: Iterator<Item = char>> Iterator for Tokenizer<I> {
     type Item = TokenAt;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (line, column, character) = self.chars.next()?;
+        let (start, character) = self.chars.next()?;
 
         // adapted from https://www.w3.org/TR/css-syntax-3/#consume-token
         let token = match character {
             // comments
             '/' => {
-                if let Some((_, _, '*')) = self.chars.peek() {
+                if let Some((_, '*')) = self.chars.peek() {
                     self.chars.next();
+                    self.has_comments = true;
 
-                    while let Some((_, _, character)) = self.chars.next() {
+                    while let Some((_, character)) = self.chars.next() {
                         if character == '*' {
-                            if let Some((_, _, '/')) = self.chars.peek() {
+                            if let Some((_, '/')) = self.chars.peek() {
                                 self.chars.next();
                                 return self.next();
                             }
@@ -334,22 +505,33 @@ impl<I: Iterator<Item = char>> Iterator for Tokenizer<I> {
             }
 
             // identifiers, functions, and urls
-            'a'..='z' | 'A'..='Z' | '_' => self.consume_identifier_like_token(character),
+            _ if is_identifier_start(character) => self.consume_identifier_like_token(character),
+            '\\' => {
+                if self.peek_character(0) == Some('\n') {
+                    Token::Delimiter('\\')
+                } else {
+                    let character = self.consume_escaped_code_point();
+                    self.consume_identifier_like_token(character)
+                }
+            }
 
             // ids and hashes
-            '#' => match self.chars.peek() {
-                Some((_, _, next_character)) => match next_character {
-                    'a'..='z' | 'A'..='Z' | '_' => {
-                        Token::Hash(self.consume_identifier_sequence(), HashType::Id)
-                    }
-                    '0'..='9' | '-' => {
-                        Token::Hash(self.consume_identifier_sequence(), HashType::Unrestricted)
-                    }
-                    '\\' => unimplemented!("escape sequences"),
-                    _ => Token::Delimiter('#'),
-                },
-                None => Token::Delimiter('#'),
-            },
+            '#' => {
+                let next_character = self.peek_character(0);
+
+                if next_character.is_some_and(is_identifier_character) || self.would_start_escape()
+                {
+                    let hash_type = if self.would_start_identifier() {
+                        HashType::Id
+                    } else {
+                        HashType::Unrestricted
+                    };
+
+                    Token::Hash(self.consume_identifier_sequence(), hash_type)
+                } else {
+                    Token::Delimiter('#')
+                }
+            }
 
             // strings
             '"' => self.consume_string_token('"'),
@@ -357,27 +539,18 @@ impl<I: Iterator<Item = char>> Iterator for Tokenizer<I> {
 
             // numbers
             '0'..='9' => self.consume_numeric_token(character),
-            '+' => match self.chars.peek() {
-                Some(&(_, _, next_character)) => match next_character {
-                    '0'..='9' | '.' => self.consume_numeric_token(character),
-                    _ => Token::Delimiter('+'),
-                },
-                None => Token::Delimiter('+'),
-            },
-            '-' => match self.chars.peek() {
-                Some(&(_, _, next_character)) => match next_character {
-                    '0'..='9' | '.' => self.consume_numeric_token(character),
-                    'a'..='z' | 'A'..='Z' | '_' | '-' => {
-                        self.consume_identifier_like_token(character)
-                    }
-                    _ => Token::Delimiter('-'),
-                },
-                None => Token::Delimiter('-'),
-            },
-            '.' => match self.chars.peek() {
-                Some((_, _, '0'..='9')) => self.consume_numeric_token(character),
-                _ => Token::Delimiter('.'),
-            },
+            '+' | '-' | '.' => {
+                let next_character = self.peek_character(0);
+                let next_next_character = self.peek_character(1);
+
+                if starts_number(Some(character), next_character, next_next_character) {
+                    self.consume_numeric_token(character)
+                } else if starts_identifier(Some(character), next_character, next_next_character) {
+                    self.consume_identifier_like_token(character)
+                } else {
+                    Token::Delimiter(character)
+                }
+            }
 
             // at keywords
             '@' => {
@@ -388,7 +561,6 @@ impl<I: Iterator<Item = char>> Iterator for Tokenizer<I> {
                 }
             }
 
-            '\\' => unimplemented!("escape sequences"),
             ':' => Token::Colon(),
             ';' => Token::Semicolon(),
             ',' => Token::Comma(),
@@ -402,9 +574,8 @@ impl<I: Iterator<Item = char>> Iterator for Tokenizer<I> {
         };
 
         Some(TokenAt {
-            line,
-            column,
             token,
+            span: Span::new(start, self.position()),
         })
     }
 }
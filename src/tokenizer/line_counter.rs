@@ -1,37 +1,51 @@
+use crate::span::Position;
+
+/// Keeps track of the position of each character
 pub struct LineCounter<I: Iterator<Item = char>> {
-    line: usize,
-    column: usize,
+    position: Position,
     chars: I,
+    /// every character that has been read so far, so the text of a span can be looked up
+    source: String,
 }
 
 impl<I: Iterator<Item = char>> LineCounter<I> {
     pub fn new(chars: I) -> Self {
         Self {
-            line: 0,
-            column: 0,
+            position: Position::default(),
             chars,
+            source: String::new(),
         }
     }
+
+    /// the position of the next character, or of the end of the input once everything has been consumed
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// the characters that have been read so far
+    pub fn source(&self) -> &str {
+        &self.source
+    }
 }
 
 impl<I: Iterator<Item = char>> Iterator for LineCounter<I> {
-    type Item = (usize, usize, char);
+    type Item = (Position, char);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let line = self.line;
-        let column = self.column;
-        match self.chars.next() {
-            Some('\n') => {
-                self.line += 1;
-                self.column = 0;
-                Some((line, column, '\n'))
-            }
-            Some(character) => {
-                self.column += 1;
-                Some((line, column, character))
-            }
-            None => None,
+        let position = self.position;
+        let character = self.chars.next()?;
+
+        self.position.offset += character.len_utf8();
+        self.source.push(character);
+
+        if character == '\n' {
+            self.position.line += 1;
+            self.position.column = 0;
+        } else {
+            self.position.column += 1;
         }
+
+        Some((position, character))
     }
 }
 
@@ -39,6 +53,14 @@ impl<I: Iterator<Item = char>> Iterator for LineCounter<I> {
 mod tests {
     use super::*;
 
+    fn at(offset: usize, line: usize, column: usize) -> Position {
+        Position {
+            offset,
+            line,
+            column,
+        }
+    }
+
     #[test]
     fn empty_string() {
         let input = "";
@@ -50,7 +72,7 @@ mod tests {
     fn one_character() {
         let input = "a";
         let mut line_counter = LineCounter::new(input.chars());
-        assert_eq!(line_counter.next(), Some((0, 0, 'a')));
+        assert_eq!(line_counter.next(), Some((at(0, 0, 0), 'a')));
         assert_eq!(line_counter.next(), None);
     }
 
@@ -58,9 +80,9 @@ mod tests {
     fn one_row() {
         let input = "abc";
         let mut line_counter = LineCounter::new(input.chars());
-        assert_eq!(line_counter.next(), Some((0, 0, 'a')));
-        assert_eq!(line_counter.next(), Some((0, 1, 'b')));
-        assert_eq!(line_counter.next(), Some((0, 2, 'c')));
+        assert_eq!(line_counter.next(), Some((at(0, 0, 0), 'a')));
+        assert_eq!(line_counter.next(), Some((at(1, 0, 1), 'b')));
+        assert_eq!(line_counter.next(), Some((at(2, 0, 2), 'c')));
         assert_eq!(line_counter.next(), None);
     }
 
@@ -68,13 +90,13 @@ mod tests {
     fn two_rows() {
         let input = "abc\ndef";
         let mut line_counter = LineCounter::new(input.chars());
-        assert_eq!(line_counter.next(), Some((0, 0, 'a')));
-        assert_eq!(line_counter.next(), Some((0, 1, 'b')));
-        assert_eq!(line_counter.next(), Some((0, 2, 'c')));
-        assert_eq!(line_counter.next(), Some((0, 3, '\n')));
-        assert_eq!(line_counter.next(), Some((1, 0, 'd')));
-        assert_eq!(line_counter.next(), Some((1, 1, 'e')));
-        assert_eq!(line_counter.next(), Some((1, 2, 'f')));
+        assert_eq!(line_counter.next(), Some((at(0, 0, 0), 'a')));
+        assert_eq!(line_counter.next(), Some((at(1, 0, 1), 'b')));
+        assert_eq!(line_counter.next(), Some((at(2, 0, 2), 'c')));
+        assert_eq!(line_counter.next(), Some((at(3, 0, 3), '\n')));
+        assert_eq!(line_counter.next(), Some((at(4, 1, 0), 'd')));
+        assert_eq!(line_counter.next(), Some((at(5, 1, 1), 'e')));
+        assert_eq!(line_counter.next(), Some((at(6, 1, 2), 'f')));
         assert_eq!(line_counter.next(), None);
     }
 
@@ -82,14 +104,14 @@ mod tests {
     fn two_rows_with_carriage_return() {
         let input = "abc\r\ndef";
         let mut line_counter = LineCounter::new(input.chars());
-        assert_eq!(line_counter.next(), Some((0, 0, 'a')));
-        assert_eq!(line_counter.next(), Some((0, 1, 'b')));
-        assert_eq!(line_counter.next(), Some((0, 2, 'c')));
-        assert_eq!(line_counter.next(), Some((0, 3, '\r')));
-        assert_eq!(line_counter.next(), Some((0, 4, '\n')));
-        assert_eq!(line_counter.next(), Some((1, 0, 'd')));
-        assert_eq!(line_counter.next(), Some((1, 1, 'e')));
-        assert_eq!(line_counter.next(), Some((1, 2, 'f')));
+        assert_eq!(line_counter.next(), Some((at(0, 0, 0), 'a')));
+        assert_eq!(line_counter.next(), Some((at(1, 0, 1), 'b')));
+        assert_eq!(line_counter.next(), Some((at(2, 0, 2), 'c')));
+        assert_eq!(line_counter.next(), Some((at(3, 0, 3), '\r')));
+        assert_eq!(line_counter.next(), Some((at(4, 0, 4), '\n')));
+        assert_eq!(line_counter.next(), Some((at(5, 1, 0), 'd')));
+        assert_eq!(line_counter.next(), Some((at(6, 1, 1), 'e')));
+        assert_eq!(line_counter.next(), Some((at(7, 1, 2), 'f')));
         assert_eq!(line_counter.next(), None);
     }
 
@@ -97,14 +119,26 @@ mod tests {
     fn trailing_newline() {
         let input = "abc\ndef\n";
         let mut line_counter = LineCounter::new(input.chars());
-        assert_eq!(line_counter.next(), Some((0, 0, 'a')));
-        assert_eq!(line_counter.next(), Some((0, 1, 'b')));
-        assert_eq!(line_counter.next(), Some((0, 2, 'c')));
-        assert_eq!(line_counter.next(), Some((0, 3, '\n')));
-        assert_eq!(line_counter.next(), Some((1, 0, 'd')));
-        assert_eq!(line_counter.next(), Some((1, 1, 'e')));
-        assert_eq!(line_counter.next(), Some((1, 2, 'f')));
-        assert_eq!(line_counter.next(), Some((1, 3, '\n')));
+        assert_eq!(line_counter.next(), Some((at(0, 0, 0), 'a')));
+        assert_eq!(line_counter.next(), Some((at(1, 0, 1), 'b')));
+        assert_eq!(line_counter.next(), Some((at(2, 0, 2), 'c')));
+        assert_eq!(line_counter.next(), Some((at(3, 0, 3), '\n')));
+        assert_eq!(line_counter.next(), Some((at(4, 1, 0), 'd')));
+        assert_eq!(line_counter.next(), Some((at(5, 1, 1), 'e')));
+        assert_eq!(line_counter.next(), Some((at(6, 1, 2), 'f')));
+        assert_eq!(line_counter.next(), Some((at(7, 1, 3), '\n')));
+        assert_eq!(line_counter.next(), None);
+    }
+
+    #[test]
+    fn multi_byte_characters() {
+        let input = "é\n🐈a";
+        let mut line_counter = LineCounter::new(input.chars());
+        assert_eq!(line_counter.next(), Some((at(0, 0, 0), 'é')));
+        assert_eq!(line_counter.next(), Some((at(2, 0, 1), '\n')));
+        assert_eq!(line_counter.next(), Some((at(3, 1, 0), '🐈')));
+        assert_eq!(line_counter.next(), Some((at(7, 1, 1), 'a')));
         assert_eq!(line_counter.next(), None);
+        assert_eq!(line_counter.position(), at(8, 1, 2));
     }
 }
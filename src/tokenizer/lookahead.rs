@@ -0,0 +1,89 @@
+use std::collections::VecDeque;
+
+/// An iterator adapter like `Peekable` that can look more than one item ahead
+pub struct Lookahead<I: Iterator> {
+    buffer: VecDeque<I::Item>,
+    iter: I,
+}
+
+impl<I: Iterator> Lookahead<I> {
+    pub fn new(iter: I) -> Self {
+        Self {
+            buffer: VecDeque::new(),
+            iter,
+        }
+    }
+
+    /// returns a reference to the next item without consuming it
+    pub fn peek(&mut self) -> Option<&I::Item> {
+        self.peek_nth(0)
+    }
+
+    /// returns a reference to the item `n` places ahead without consuming anything
+    ///
+    /// `peek_nth(0)` is the same as `peek()`
+    pub fn peek_nth(&mut self, n: usize) -> Option<&I::Item> {
+        while self.buffer.len() <= n {
+            self.buffer.push_back(self.iter.next()?);
+        }
+
+        self.buffer.get(n)
+    }
+}
+
+impl<I: Iterator> Lookahead<I> {
+    /// returns a reference to the underlying iterator
+    ///
+    /// this may be ahead of what has been consumed if items were peeked
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+}
+
+impl<I: Iterator> Iterator for Lookahead<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.buffer.pop_front() {
+            Some(item) => Some(item),
+            None => self.iter.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let mut lookahead = Lookahead::new("".chars());
+        assert_eq!(lookahead.peek(), None);
+        assert_eq!(lookahead.peek_nth(2), None);
+        assert_eq!(lookahead.next(), None);
+    }
+
+    #[test]
+    fn peek() {
+        let mut lookahead = Lookahead::new("ab".chars());
+        assert_eq!(lookahead.peek(), Some(&'a'));
+        assert_eq!(lookahead.peek(), Some(&'a'));
+        assert_eq!(lookahead.next(), Some('a'));
+        assert_eq!(lookahead.peek(), Some(&'b'));
+        assert_eq!(lookahead.next(), Some('b'));
+        assert_eq!(lookahead.peek(), None);
+    }
+
+    #[test]
+    fn peek_nth() {
+        let mut lookahead = Lookahead::new("abc".chars());
+        assert_eq!(lookahead.peek_nth(2), Some(&'c'));
+        assert_eq!(lookahead.peek_nth(3), None);
+        assert_eq!(lookahead.peek_nth(1), Some(&'b'));
+        assert_eq!(lookahead.next(), Some('a'));
+        assert_eq!(lookahead.peek_nth(1), Some(&'c'));
+        assert_eq!(lookahead.next(), Some('b'));
+        assert_eq!(lookahead.next(), Some('c'));
+        assert_eq!(lookahead.next(), None);
+    }
+}
@@ -44,6 +44,17 @@ mod comments {
     fn delimiter() {
         assert_tokens("/", vec![Token::Delimiter('/')]);
     }
+
+    #[test]
+    fn has_comments() {
+        let mut tokenizer = Tokenizer::new("a /* b */ \"/* c */\"".chars());
+        tokenizer.by_ref().for_each(drop);
+        assert!(tokenizer.has_comments());
+
+        let mut tokenizer = Tokenizer::new("a \"/* c */\" d/e".chars());
+        tokenizer.by_ref().for_each(drop);
+        assert!(!tokenizer.has_comments());
+    }
 }
 
 mod identifiers {
@@ -83,6 +94,16 @@ mod identifiers {
     fn leading_double_hyphen() {
         assert_tokens("--abc", vec![Token::Identifier("--abc".to_owned())]);
     }
+
+    #[test]
+    fn non_ascii() {
+        assert_tokens("café", vec![Token::Identifier("café".to_owned())]);
+    }
+
+    #[test]
+    fn emoji() {
+        assert_tokens("🐈", vec![Token::Identifier("🐈".to_owned())]);
+    }
 }
 
 mod functions {
@@ -258,6 +279,14 @@ mod at_keywords {
     fn delimiter() {
         assert_tokens("@", vec![Token::Delimiter('@')]);
     }
+
+    #[test]
+    fn vendor_prefix() {
+        assert_tokens(
+            "@-webkit-keyframes",
+            vec![Token::AtKeyword("-webkit-keyframes".to_owned())],
+        );
+    }
 }
 
 mod hashes {
@@ -310,6 +339,11 @@ mod hashes {
         );
     }
 
+    #[test]
+    fn leading_hyphen() {
+        assert_tokens("#-a", vec![Token::Hash("-a".to_owned(), HashType::Id)]);
+    }
+
     #[test]
     fn followed_by_colon() {
         assert_tokens(
@@ -391,42 +425,161 @@ mod strings {
     }
 }
 
+mod escapes {
+    use super::*;
+
+    #[test]
+    fn identifier_with_escaped_colon() {
+        assert_tokens(
+            "sm\\:hidden",
+            vec![Token::Identifier("sm:hidden".to_owned())],
+        );
+    }
+
+    #[test]
+    fn identifier_starting_with_escape() {
+        assert_tokens("\\31 23", vec![Token::Identifier("123".to_owned())]);
+    }
+
+    #[test]
+    fn identifier_with_hex_escape() {
+        assert_tokens("a\\62 c", vec![Token::Identifier("abc".to_owned())]);
+    }
+
+    #[test]
+    fn hex_escape_only_consumes_six_digits() {
+        assert_tokens("a\\00006212", vec![Token::Identifier("ab12".to_owned())]);
+    }
+
+    #[test]
+    fn hex_escape_of_zero() {
+        assert_tokens("a\\0", vec![Token::Identifier("a\u{FFFD}".to_owned())]);
+    }
+
+    #[test]
+    fn hex_escape_out_of_range() {
+        assert_tokens("a\\110000", vec![Token::Identifier("a\u{FFFD}".to_owned())]);
+    }
+
+    #[test]
+    fn hex_escape_surrogate() {
+        assert_tokens("a\\d800", vec![Token::Identifier("a\u{FFFD}".to_owned())]);
+    }
+
+    #[test]
+    fn escape_at_end_of_file() {
+        assert_tokens("a\\", vec![Token::Identifier("a\u{FFFD}".to_owned())]);
+    }
+
+    #[test]
+    fn escaped_newline_is_not_an_escape() {
+        assert_tokens(
+            "a\\\n",
+            vec![
+                Token::Identifier("a".to_owned()),
+                Token::Delimiter('\\'),
+                Token::Whitespace(),
+            ],
+        );
+    }
+
+    #[test]
+    fn function() {
+        assert_tokens("f\\6f o(", vec![Token::Function("foo".to_owned())]);
+    }
+
+    #[test]
+    fn hash() {
+        assert_tokens(
+            "#\\31 23",
+            vec![Token::Hash("123".to_owned(), HashType::Id)],
+        );
+    }
+
+    #[test]
+    fn at_keyword() {
+        assert_tokens("@\\6d edia", vec![Token::AtKeyword("media".to_owned())]);
+    }
+
+    #[test]
+    fn dimension() {
+        assert_tokens(
+            "10\\70 x",
+            vec![Token::Dimension(10.0, Sign::Signless, "px".to_owned())],
+        );
+    }
+
+    #[test]
+    fn string_with_hex_escape() {
+        assert_tokens("\"\\201C\"", vec![Token::String("\u{201C}".to_owned())]);
+    }
+
+    #[test]
+    fn string_with_hex_escape_and_whitespace() {
+        assert_tokens("\"\\41 B\"", vec![Token::String("AB".to_owned())]);
+    }
+
+    #[test]
+    fn string_with_escaped_character() {
+        assert_tokens("\"\\g\"", vec![Token::String("g".to_owned())]);
+    }
+
+    #[test]
+    fn url() {
+        assert_tokens("url(a\\29 b)", vec![Token::Url("a)b".to_owned())]);
+    }
+
+    #[test]
+    fn url_with_escaped_newline() {
+        assert_tokens(
+            "url(a\\\nb)",
+            vec![
+                Token::BadUrl(),
+                Token::Delimiter('\\'),
+                Token::Whitespace(),
+                Token::Identifier("b".to_owned()),
+                Token::CloseParenthesis(),
+            ],
+        );
+    }
+}
+
 mod numbers {
     use super::*;
 
     #[test]
     fn zero() {
-        assert_tokens("0", vec![Token::Number(0.0)]);
+        assert_tokens("0", vec![Token::Number(0.0, Sign::Signless)]);
     }
 
     #[test]
     fn one() {
-        assert_tokens("1", vec![Token::Number(1.0)]);
+        assert_tokens("1", vec![Token::Number(1.0, Sign::Signless)]);
     }
 
     #[test]
     fn negative_one() {
-        assert_tokens("-1", vec![Token::Number(-1.0)]);
+        assert_tokens("-1", vec![Token::Number(-1.0, Sign::Minus)]);
     }
 
     #[test]
     fn positive_one() {
-        assert_tokens("+1", vec![Token::Number(1.0)]);
+        assert_tokens("+1", vec![Token::Number(1.0, Sign::Plus)]);
     }
 
     #[test]
     fn one_point_zero() {
-        assert_tokens("1.0", vec![Token::Number(1.0)]);
+        assert_tokens("1.0", vec![Token::Number(1.0, Sign::Signless)]);
     }
 
     #[test]
     fn one_point_five() {
-        assert_tokens("1.5", vec![Token::Number(1.5)]);
+        assert_tokens("1.5", vec![Token::Number(1.5, Sign::Signless)]);
     }
 
     #[test]
     fn point_five() {
-        assert_tokens(".5", vec![Token::Number(0.5)]);
+        assert_tokens(".5", vec![Token::Number(0.5, Sign::Signless)]);
     }
 
     #[test]
@@ -456,12 +609,35 @@ mod numbers {
 
     #[test]
     fn zero_px() {
-        assert_tokens("0px", vec![Token::Dimension(0.0, "px".to_owned())]);
+        assert_tokens(
+            "0px",
+            vec![Token::Dimension(0.0, Sign::Signless, "px".to_owned())],
+        );
     }
 
     #[test]
     fn negative_three_em() {
-        assert_tokens("-3em", vec![Token::Dimension(-3.0, "em".to_owned())]);
+        assert_tokens(
+            "-3em",
+            vec![Token::Dimension(-3.0, Sign::Minus, "em".to_owned())],
+        );
+    }
+
+    #[test]
+    fn plus_point_without_digits() {
+        assert_tokens(
+            "+.a",
+            vec![
+                Token::Delimiter('+'),
+                Token::Delimiter('.'),
+                Token::Identifier("a".to_owned()),
+            ],
+        );
+    }
+
+    #[test]
+    fn minus_point_without_digits() {
+        assert_tokens("-.", vec![Token::Delimiter('-'), Token::Delimiter('.')]);
     }
 }
 
@@ -544,13 +720,13 @@ fn curly_brackets() {
 #[test]
 fn delimiters() {
     assert_tokens(
-        "<>*~🐈",
+        "<>*~!",
         vec![
             Token::Delimiter('<'),
             Token::Delimiter('>'),
             Token::Delimiter('*'),
             Token::Delimiter('~'),
-            Token::Delimiter('🐈'),
+            Token::Delimiter('!'),
         ],
     );
 }
@@ -638,13 +814,13 @@ fn complex_file() {
             Token::Colon(),
             Token::Whitespace(),
             Token::Function("rgb".to_owned()),
-            Token::Number(255.0),
+            Token::Number(255.0, Sign::Signless),
             Token::Comma(),
             Token::Whitespace(),
-            Token::Number(255.0),
+            Token::Number(255.0, Sign::Signless),
             Token::Comma(),
             Token::Whitespace(),
-            Token::Number(255.0),
+            Token::Number(255.0, Sign::Signless),
             Token::CloseParenthesis(),
             Token::Semicolon(),
             Token::Whitespace(),
@@ -656,7 +832,7 @@ fn complex_file() {
             Token::Identifier("min-width".to_owned()),
             Token::Colon(),
             Token::Whitespace(),
-            Token::Dimension(600.0, "px".to_owned()),
+            Token::Dimension(600.0, Sign::Signless, "px".to_owned()),
             Token::CloseParenthesis(),
             Token::Whitespace(),
             Token::OpenCurlyBracket(),
@@ -684,3 +860,46 @@ fn complex_file() {
         ],
     );
 }
+
+mod unicode_ranges {
+    use super::*;
+
+    // unicode ranges are put back together by the parser, since `u+a` can also be part of a selector
+
+    #[test]
+    fn single() {
+        assert_tokens(
+            "U+26",
+            vec![
+                Token::Identifier("U".to_owned()),
+                Token::Number(26.0, Sign::Plus),
+            ],
+        );
+    }
+
+    #[test]
+    fn range() {
+        assert_tokens(
+            "u+0-ff",
+            vec![
+                Token::Identifier("u".to_owned()),
+                Token::Dimension(0.0, Sign::Plus, "-ff".to_owned()),
+            ],
+        );
+    }
+
+    #[test]
+    fn selector() {
+        assert_tokens(
+            "u+a {}",
+            vec![
+                Token::Identifier("u".to_owned()),
+                Token::Delimiter('+'),
+                Token::Identifier("a".to_owned()),
+                Token::Whitespace(),
+                Token::OpenCurlyBracket(),
+                Token::CloseCurlyBracket(),
+            ],
+        );
+    }
+}
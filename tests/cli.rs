@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// runs the command line tool with the arguments
+fn run(arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_css-parser"))
+        .args(arguments)
+        .output()
+        .expect("the command line tool can be run")
+}
+
+/// writes a stylesheet to a file that only this test uses
+fn write_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("css-parser-{}-{name}", std::process::id()));
+    fs::write(&path, contents).expect("the file can be written");
+    path
+}
+
+#[test]
+fn fmt_rewrites_a_file() {
+    let path = write_file("fmt.css", "a{color:red}");
+
+    let output = run(&["fmt", path.to_str().unwrap()]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        fs::read_to_string(&path).unwrap()
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn fmt_keeps_a_file_with_comments() {
+    let contents = "/* licence */\na{color:red}";
+    let path = write_file("fmt-comments.css", contents);
+
+    let output = run(&["fmt", path.to_str().unwrap()]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("comments would be removed"));
+    assert_eq!(contents, fs::read_to_string(&path).unwrap());
+    fs::remove_file(path).unwrap();
+}
+
+/// runs the command line tool and parses what it printed as JSON
+fn run_json(arguments: &[&str]) -> (Output, serde_json::Value) {
+    let output = run(arguments);
+    let json = serde_json::from_slice(&output.stdout).expect("the output is valid JSON");
+    (output, json)
+}
+
+#[test]
+fn json_for_a_valid_file() {
+    let path = write_file("valid.css", "a { color: red }");
+    let file_name = path.to_str().unwrap();
+
+    let (output, json) = run_json(&["--format", "json", "--ast", file_name]);
+
+    assert!(output.status.success());
+    assert_eq!(1, json.as_array().unwrap().len());
+    assert_eq!(file_name, json[0]["file"]);
+    assert_eq!(true, json[0]["success"]);
+    assert_eq!(serde_json::json!([]), json[0]["errors"]);
+    assert!(json[0]["ast"]["rules"].is_array());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn json_for_a_file_with_errors() {
+    let path = write_file("invalid.css", "a { color: red }\nb { color: ; }");
+    let file_name = path.to_str().unwrap();
+
+    let (output, json) = run_json(&["--format", "json", file_name]);
+
+    assert!(!output.status.success());
+    assert_eq!(false, json[0]["success"]);
+    assert!(json[0].get("ast").is_none());
+
+    let error = &json[0]["errors"][0];
+    assert_eq!(file_name, error["file"]);
+    assert_eq!("wrong_token", error["kind"]);
+    assert_eq!(2, error["line"]);
+    assert_eq!(12, error["column"]);
+    assert!(error["message"].is_string());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn json_for_a_missing_file() {
+    let (output, json) = run_json(&["--format", "json", "css-parser-does-not-exist.css"]);
+
+    assert!(!output.status.success());
+    assert_eq!(false, json[0]["success"]);
+    assert_eq!("io", json[0]["errors"][0]["kind"]);
+    assert!(json[0]["errors"][0]["line"].is_null());
+}